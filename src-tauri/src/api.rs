@@ -0,0 +1,411 @@
+//! Opt-in loopback HTTP API so editor plugins, bookmarklets and scripts can
+//! capture ideas without focusing the capture window.
+//!
+//! Every request must carry `Authorization: Bearer <token>`. The server only
+//! binds `127.0.0.1` and is off until enabled in settings. While the app is
+//! locked, ideas can be captured but not read back.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::app_lock::AppLock;
+use crate::db::Db;
+use crate::error::{Error, Result};
+use crate::ideas::{self, Idea};
+
+const CONFIG_FILE_NAME: &str = "api.json";
+const DEFAULT_PORT: u16 = 21457;
+const MAX_BODY_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiConfig {
+    pub enabled: bool,
+    pub port: u16,
+    pub token: String,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+            token: new_token(),
+        }
+    }
+}
+
+fn new_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+fn load_config(path: &Path) -> ApiConfig {
+    let Ok(raw) = std::fs::read_to_string(path) else {
+        return ApiConfig::default();
+    };
+    serde_json::from_str(&raw).unwrap_or_else(|e| {
+        log::warn!("Ignoring unreadable {}: {e}", path.display());
+        ApiConfig::default()
+    })
+}
+
+fn save_config(path: &Path, config: &ApiConfig) -> Result<()> {
+    let json = serde_json::to_string_pretty(config)
+        .map_err(|e| Error::Invalid(format!("could not encode API config: {e}")))?;
+    std::fs::write(path, json)?;
+    Ok(())
+}
+
+struct Running {
+    server: Arc<Server>,
+    thread: JoinHandle<()>,
+}
+
+struct Inner {
+    config: ApiConfig,
+    running: Option<Running>,
+}
+
+/// Managed state: the persisted config plus the listener, if one is up.
+pub struct ApiServer {
+    config_path: PathBuf,
+    inner: Mutex<Inner>,
+    /// Copy of `config.token` behind its own lock: `stop` joins the server
+    /// thread while holding `inner`, so request handling must not need it.
+    token: Mutex<String>,
+}
+
+impl ApiServer {
+    pub fn new(config_dir: &Path) -> Self {
+        let config_path = config_dir.join(CONFIG_FILE_NAME);
+        let config = load_config(&config_path);
+        Self {
+            config_path,
+            token: Mutex::new(config.token.clone()),
+            inner: Mutex::new(Inner {
+                config,
+                running: None,
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn token(&self) -> String {
+        self.token
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Start listening if the saved config asks for it. Called from setup.
+    pub fn start_if_enabled(&self, app: &AppHandle) {
+        let mut inner = self.lock();
+        if inner.config.enabled {
+            if let Err(e) = start(app, &mut inner) {
+                log::error!("Capture API not started: {e}");
+            }
+        }
+    }
+}
+
+fn start(app: &AppHandle, inner: &mut Inner) -> Result<()> {
+    stop(inner);
+    let server = Server::http(("127.0.0.1", inner.config.port))
+        .map(Arc::new)
+        .map_err(|e| {
+            Error::Invalid(format!(
+                "could not listen on port {}: {e}",
+                inner.config.port
+            ))
+        })?;
+    let thread = {
+        let server = Arc::clone(&server);
+        let app = app.clone();
+        std::thread::Builder::new()
+            .name("glimt-api".into())
+            .spawn(move || {
+                for request in server.incoming_requests() {
+                    handle(&app, request);
+                }
+            })?
+    };
+    log::info!("Capture API listening on 127.0.0.1:{}", inner.config.port);
+    inner.running = Some(Running { server, thread });
+    Ok(())
+}
+
+fn stop(inner: &mut Inner) {
+    if let Some(running) = inner.running.take() {
+        running.server.unblock();
+        // Joining releases the port before a restart tries to bind it again.
+        let _ = running.thread.join();
+    }
+}
+
+// ── Routing ──────────────────────────────────────────────
+
+#[derive(Debug, PartialEq)]
+enum Route {
+    Preflight,
+    CreateIdea,
+    ListIdeas { query: Option<String> },
+    GetIdea(String),
+    NotFound,
+}
+
+fn route(method: &Method, url: &str) -> Route {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    match (method, segments.as_slice()) {
+        (Method::Options, _) => Route::Preflight,
+        (Method::Post, ["ideas"]) => Route::CreateIdea,
+        (Method::Get, ["ideas"]) => Route::ListIdeas {
+            query: url::form_urlencoded::parse(query.as_bytes())
+                .find(|(key, _)| key == "q")
+                .map(|(_, value)| value.into_owned())
+                .filter(|q| !q.trim().is_empty()),
+        },
+        (Method::Get, ["ideas", id]) => Route::GetIdea((*id).to_owned()),
+        _ => Route::NotFound,
+    }
+}
+
+/// Compare without bailing at the first differing byte.
+fn token_matches(expected: &str, header: Option<&str>) -> bool {
+    let Some(given) = header.and_then(|h| h.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NewIdea {
+    text: String,
+    source_app: Option<String>,
+}
+
+struct Reply {
+    status: u16,
+    body: Option<String>,
+}
+
+impl Reply {
+    fn json(status: u16, value: &impl Serialize) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self {
+                status,
+                body: Some(body),
+            },
+            Err(e) => Self::error(500, &e.to_string()),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, &serde_json::json!({ "error": message }))
+    }
+}
+
+impl From<Error> for Reply {
+    fn from(e: Error) -> Self {
+        let status = match e {
+            Error::Invalid(_) => 400,
+            Error::NotFound(_) => 404,
+            _ => 500,
+        };
+        Self::error(status, &e.to_string())
+    }
+}
+
+fn handle(app: &AppHandle, mut request: Request) {
+    let route = route(request.method(), request.url());
+    let reply = if route == Route::Preflight {
+        Reply {
+            status: 204,
+            body: None,
+        }
+    } else {
+        let authorization = request
+            .headers()
+            .iter()
+            .find(|h| h.field.equiv("Authorization"))
+            .map(|h| h.value.as_str().to_owned());
+        let token = app.state::<ApiServer>().token();
+        if token_matches(&token, authorization.as_deref()) {
+            dispatch(app, route, &mut request).unwrap_or_else(Reply::from)
+        } else {
+            Reply::error(401, "missing or invalid bearer token")
+        }
+    };
+    crate::log_err("API response", request.respond(build_response(reply)));
+}
+
+fn dispatch(app: &AppHandle, route: Route, request: &mut Request) -> Result<Reply> {
+    let db = app
+        .try_state::<Db>()
+        .ok_or_else(|| Error::Invalid("the database is not available".into()))?;
+    let locked = app.state::<AppLock>().is_locked();
+    match route {
+        Route::ListIdeas { .. } | Route::GetIdea(_) if locked => {
+            Ok(Reply::error(423, "Glimt is locked"))
+        }
+        Route::CreateIdea => {
+            let mut body = String::new();
+            request
+                .as_reader()
+                .take(MAX_BODY_BYTES)
+                .read_to_string(&mut body)?;
+            let new: NewIdea = serde_json::from_str(&body)
+                .map_err(|e| Error::Invalid(format!("expected {{\"text\": ...}}: {e}")))?;
+            let source_app = new.source_app.as_deref().unwrap_or("api");
+            let idea = ideas::create(&db.conn(), new.text.trim(), Some(source_app))?;
+            // Same event the capture window sends, so the dashboard reloads
+            // and embeds the new idea.
+            crate::log_err("emit idea-saved", app.emit("idea-saved", ()));
+            Ok(Reply::json(201, &idea))
+        }
+        Route::ListIdeas { query } => {
+            let conn = db.conn();
+            let found: Vec<Idea> = match query {
+                Some(q) => ideas::search_fts(&conn, &q)?,
+                None => ideas::list(&conn, false, None)?,
+            };
+            Ok(Reply::json(200, &found))
+        }
+        Route::GetIdea(id) => {
+            let idea = ideas::get(&db.conn(), &id)?
+                .filter(|idea| idea.deleted_at.is_none())
+                .ok_or(Error::NotFound(id))?;
+            Ok(Reply::json(200, &idea))
+        }
+        Route::Preflight => unreachable!("answered before authentication"),
+        Route::NotFound => Ok(Reply::error(404, "no such endpoint")),
+    }
+}
+
+fn build_response(reply: Reply) -> tiny_http::ResponseBox {
+    let mut response = match reply.body {
+        Some(body) => Response::from_string(body)
+            .with_header(header("Content-Type", "application/json"))
+            .boxed(),
+        None => Response::empty(reply.status).boxed(),
+    }
+    .with_status_code(reply.status);
+    // Bookmarklets run on arbitrary origins; the token is what guards access.
+    for (name, value) in [
+        ("Access-Control-Allow-Origin", "*"),
+        ("Access-Control-Allow-Methods", "GET, POST, OPTIONS"),
+        (
+            "Access-Control-Allow-Headers",
+            "Authorization, Content-Type",
+        ),
+        ("Access-Control-Allow-Private-Network", "true"),
+    ] {
+        response.add_header(header(name, value));
+    }
+    response
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("static header is valid")
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_api_config(api: State<'_, ApiServer>) -> ApiConfig {
+    api.lock().config.clone()
+}
+
+/// Enable or disable the API and optionally move it to another port. On a
+/// bind failure the API stays off and the error is returned.
+#[tauri::command]
+pub fn set_api_config(
+    app: AppHandle,
+    api: State<'_, ApiServer>,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<ApiConfig> {
+    let mut inner = api.lock();
+    if let Some(port) = port {
+        if port < 1024 {
+            return Err(Error::Invalid("port must be 1024 or higher".into()));
+        }
+        inner.config.port = port;
+    }
+    inner.config.enabled = enabled;
+    let started = if enabled {
+        start(&app, &mut inner)
+    } else {
+        stop(&mut inner);
+        Ok(())
+    };
+    if started.is_err() {
+        inner.config.enabled = false;
+    }
+    save_config(&api.config_path, &inner.config)?;
+    started.map(|()| inner.config.clone())
+}
+
+/// Issue a new token; clients holding the old one get 401 from now on.
+#[tauri::command]
+pub fn regenerate_api_token(api: State<'_, ApiServer>) -> Result<ApiConfig> {
+    let mut inner = api.lock();
+    inner.config.token = new_token();
+    *api.token
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = inner.config.token.clone();
+    save_config(&api.config_path, &inner.config)?;
+    Ok(inner.config.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_requests() {
+        assert_eq!(route(&Method::Post, "/ideas"), Route::CreateIdea);
+        assert_eq!(
+            route(&Method::Get, "/ideas?q=hello%20world"),
+            Route::ListIdeas {
+                query: Some("hello world".into())
+            }
+        );
+        assert_eq!(
+            route(&Method::Get, "/ideas/?q="),
+            Route::ListIdeas { query: None }
+        );
+        assert_eq!(
+            route(&Method::Get, "/ideas/abc-123"),
+            Route::GetIdea("abc-123".into())
+        );
+        assert_eq!(route(&Method::Delete, "/ideas/abc"), Route::NotFound);
+        assert_eq!(route(&Method::Options, "/ideas"), Route::Preflight);
+    }
+
+    #[test]
+    fn requires_exact_bearer_token() {
+        assert!(token_matches("secret", Some("Bearer secret")));
+        assert!(!token_matches("secret", Some("Bearer secreT")));
+        assert!(!token_matches("secret", Some("secret")));
+        assert!(!token_matches("secret", Some("Bearer secret2")));
+        assert!(!token_matches("secret", None));
+    }
+}
@@ -0,0 +1,305 @@
+//! App lock: after a period without activity the main window's content is
+//! hidden until the PIN is entered again.
+//!
+//! Activity is the main window gaining focus or the webview reporting input
+//! through `report_activity`. While locked, only the commands quick capture
+//! and the lock screen need are accepted ([`ALLOWED_WHILE_LOCKED`]), so
+//! capturing keeps working but nothing can be read back. Edits are limited
+//! to ideas captured since the app locked.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::db::now_millis;
+use crate::error::{Error, Result};
+use crate::settings::SettingsStore;
+
+const FILE_NAME: &str = "app-lock.json";
+/// Emitted to every window with `true` when the app locks, `false` when it
+/// unlocks.
+pub const CHANGED_EVENT: &str = "app-lock-changed";
+const CHECK_EVERY: Duration = Duration::from_secs(15);
+/// Slows down guessing from the lock screen.
+const FAILED_ATTEMPT_DELAY: Duration = Duration::from_secs(1);
+
+/// Commands that stay available while locked: saving and transcribing new
+/// ideas, the settings capture reads, and unlocking. `update_idea` checks
+/// [`AppLock::locked_since`] itself.
+const ALLOWED_WHILE_LOCKED: [&str; 20] = [
+    "db_status",
+    "encryption_status",
+    "unlock_database",
+    "create_idea",
+    "update_idea",
+    "store_embedding",
+    "export_idea_markdown",
+    "get_settings",
+    "get_shortcuts",
+    "list_input_devices",
+    "toggle_recording",
+    "take_recording",
+    "keep_recording",
+    "take_clip",
+    "native_transcription_status",
+    "download_whisper_model",
+    "transcribe_audio",
+    "app_lock_status",
+    "report_activity",
+    "unlock_app",
+];
+
+/// Kept apart from `settings.json`, which every window can read.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LockFile {
+    /// Argon2 PHC string of the PIN.
+    pin_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLockStatus {
+    pub locked: bool,
+    pub pin_set: bool,
+}
+
+fn hash_pin(pin: &str) -> Result<String> {
+    let salt = SaltString::encode_b64(uuid::Uuid::new_v4().as_bytes())
+        .map_err(|e| Error::Invalid(format!("could not hash PIN: {e}")))?;
+    Argon2::default()
+        .hash_password(pin.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| Error::Invalid(format!("could not hash PIN: {e}")))
+}
+
+fn verify_pin(hash: &str, pin: &str) -> bool {
+    PasswordHash::new(hash).is_ok_and(|parsed| {
+        Argon2::default()
+            .verify_password(pin.as_bytes(), &parsed)
+            .is_ok()
+    })
+}
+
+fn load(path: &Path) -> LockFile {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+struct Inner {
+    pin_hash: Option<String>,
+    /// When the app locked, in milliseconds; `None` while unlocked.
+    locked_since: Option<i64>,
+    last_activity: Instant,
+}
+
+/// Managed state: the PIN, whether the app is locked, and when it was last
+/// used.
+pub struct AppLock {
+    path: PathBuf,
+    inner: Mutex<Inner>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl AppLock {
+    /// Starts locked when a PIN and auto-lock are both set, since opening
+    /// Glimt counts as coming back to it.
+    pub fn new(config_dir: &Path, auto_lock: bool) -> Self {
+        let path = config_dir.join(FILE_NAME);
+        let pin_hash = load(&path).pin_hash;
+        Self {
+            path,
+            inner: Mutex::new(Inner {
+                locked_since: (auto_lock && pin_hash.is_some()).then(now_millis),
+                pin_hash,
+                last_activity: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked_since().is_some()
+    }
+
+    /// When the app locked, if it is locked. Ideas created since then are
+    /// the ones capture may still edit.
+    pub fn locked_since(&self) -> Option<i64> {
+        lock(&self.inner).locked_since
+    }
+
+    /// Whether `command` may run right now.
+    pub fn allows(&self, command: &str) -> bool {
+        !self.is_locked() || ALLOWED_WHILE_LOCKED.contains(&command)
+    }
+
+    /// Record activity, postponing the next auto-lock.
+    pub fn touch(&self) {
+        lock(&self.inner).last_activity = Instant::now();
+    }
+
+    fn status(&self) -> AppLockStatus {
+        let inner = lock(&self.inner);
+        AppLockStatus {
+            locked: inner.locked_since.is_some(),
+            pin_set: inner.pin_hash.is_some(),
+        }
+    }
+
+    fn set_locked(&self, app: &AppHandle, locked: bool) -> Result<()> {
+        {
+            let mut inner = lock(&self.inner);
+            if locked && inner.pin_hash.is_none() {
+                return Err(Error::Invalid("set a PIN before locking Glimt".into()));
+            }
+            if inner.locked_since.is_some() == locked {
+                return Ok(());
+            }
+            inner.locked_since = locked.then(now_millis);
+            inner.last_activity = Instant::now();
+        }
+        log::info!("App {}", if locked { "locked" } else { "unlocked" });
+        crate::log_err("emit app lock", app.emit(CHANGED_EVENT, locked));
+        Ok(())
+    }
+
+    /// Lock if auto-lock is on and nothing happened for long enough.
+    fn lock_if_idle(&self, app: &AppHandle) {
+        let minutes = app.state::<SettingsStore>().get().auto_lock_minutes;
+        if minutes == 0 {
+            return;
+        }
+        let idle = {
+            let inner = lock(&self.inner);
+            inner.locked_since.is_none()
+                && inner.pin_hash.is_some()
+                && inner.last_activity.elapsed() >= Duration::from_secs(u64::from(minutes) * 60)
+        };
+        if idle {
+            crate::log_err("auto-lock", self.set_locked(app, true));
+        }
+    }
+
+    /// Start the idle timer thread.
+    pub fn start(app: &AppHandle) {
+        let app = app.clone();
+        let spawned = std::thread::Builder::new()
+            .name("glimt-app-lock".into())
+            .spawn(move || loop {
+                std::thread::sleep(CHECK_EVERY);
+                app.state::<AppLock>().lock_if_idle(&app);
+            });
+        crate::log_err("start app lock timer", spawned);
+    }
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+#[tauri::command]
+pub fn app_lock_status(lock: State<'_, AppLock>) -> AppLockStatus {
+    lock.status()
+}
+
+/// Called by the main window on input, at most every few seconds.
+#[tauri::command]
+pub fn report_activity(lock: State<'_, AppLock>) {
+    lock.touch();
+}
+
+#[tauri::command]
+pub fn lock_app(app: AppHandle, lock: State<'_, AppLock>) -> Result<()> {
+    lock.set_locked(&app, true)
+}
+
+#[tauri::command]
+pub async fn unlock_app(app: AppHandle, pin: String) -> Result<()> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppLock>();
+        let hash = lock(&state.inner).pin_hash.clone();
+        if hash.is_some_and(|hash| !verify_pin(&hash, &pin)) {
+            std::thread::sleep(FAILED_ATTEMPT_DELAY);
+            return Err(Error::Invalid("wrong PIN".into()));
+        }
+        state.set_locked(&app, false)
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("unlock failed: {e}")))?
+}
+
+/// Set, change or remove (empty `pin`) the PIN. Changing or removing one
+/// needs the `current` PIN.
+#[tauri::command]
+pub async fn set_app_lock_pin(
+    app: AppHandle,
+    current: Option<String>,
+    pin: Option<String>,
+) -> Result<AppLockStatus> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<AppLock>();
+        let existing = lock(&state.inner).pin_hash.clone();
+        if let Some(existing) = existing {
+            if !verify_pin(&existing, current.as_deref().unwrap_or_default()) {
+                std::thread::sleep(FAILED_ATTEMPT_DELAY);
+                return Err(Error::Invalid("the current PIN is wrong".into()));
+            }
+        }
+        let pin_hash = match pin.filter(|pin| !pin.is_empty()) {
+            Some(pin) => Some(hash_pin(&pin)?),
+            None => None,
+        };
+        let file = LockFile { pin_hash };
+        let json = serde_json::to_string_pretty(&file)
+            .map_err(|e| Error::Invalid(format!("could not encode app lock: {e}")))?;
+        std::fs::write(&state.path, json)?;
+        lock(&state.inner).pin_hash = file.pin_hash;
+        Ok(state.status())
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("setting the PIN failed: {e}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    #[test]
+    fn pins_verify_and_gate_reads_while_locked() {
+        let hash = hash_pin("2468").unwrap();
+        assert!(verify_pin(&hash, "2468"));
+        assert!(!verify_pin(&hash, "1357"));
+        assert!(!verify_pin("not a hash", "2468"));
+
+        let dir = TestDir::new("app-lock");
+        let state = AppLock::new(&dir, true);
+        assert!(!state.is_locked(), "no PIN, so nothing to lock with");
+
+        std::fs::write(
+            dir.join(FILE_NAME),
+            serde_json::to_string(&LockFile {
+                pin_hash: Some(hash),
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        let before = now_millis();
+        let state = AppLock::new(&dir, true);
+        assert!(state.is_locked());
+        assert!(state.locked_since().is_some_and(|since| since >= before));
+        assert!(state.allows("create_idea"));
+        assert!(!state.allows("get_ideas"));
+        assert!(!state.allows("hybrid_search"));
+        assert!(AppLock::new(&dir, false).allows("get_ideas"));
+    }
+}
@@ -0,0 +1,585 @@
+//! Portable backups of the whole store in a single zip archive:
+//!
+//! - `manifest.json`: archive format, app and schema version, row counts
+//! - `ideas.jsonl`: one idea per line
+//! - `embeddings.jsonl`: one vector per line
+//! - `settings.json`
+//!
+//! Archives from an older schema import as long as their rows still parse.
+//! Archives from a newer one are refused instead of being half-read.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+
+use rusqlite::{params, Connection};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::attachments::Blobs;
+use crate::db::{now_millis, Db};
+use crate::embeddings;
+use crate::encryption::Encryption;
+use crate::error::{Error, Result};
+use crate::ideas::{self, Idea};
+use crate::migrations::CURRENT_SCHEMA_VERSION;
+use crate::recordings::Recordings;
+use crate::settings::{Settings, SettingsStore};
+use crate::vault;
+use crate::vector_index::VectorIndex;
+
+const FORMAT: &str = "glimt-archive";
+/// Bump when the file layout changes, not when the schema does.
+const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST: &str = "manifest.json";
+const IDEAS: &str = "ideas.jsonl";
+const EMBEDDINGS: &str = "embeddings.jsonl";
+const SETTINGS: &str = "settings.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    pub format: String,
+    pub format_version: u32,
+    pub schema_version: i64,
+    pub app_version: String,
+    pub created_at: i64,
+    pub ideas: usize,
+    pub embeddings: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EmbeddingRow {
+    idea_id: String,
+    model: String,
+    vector: Vec<f32>,
+    created_at: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportMode {
+    /// Add ideas that are missing and take the archive's copy of ideas it
+    /// has a newer version of. Settings are left alone.
+    Merge,
+    /// Delete every idea first and restore the archive's preferences.
+    Replace,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    pub added: usize,
+    pub updated: usize,
+    /// Ideas already here in the same or a newer version.
+    pub skipped: usize,
+    pub embeddings: usize,
+}
+
+struct Contents {
+    manifest: Manifest,
+    ideas: Vec<Idea>,
+    embeddings: Vec<EmbeddingRow>,
+    settings: Option<Settings>,
+}
+
+fn zip_err(e: zip::result::ZipError) -> Error {
+    Error::Invalid(format!("archive error: {e}"))
+}
+
+fn encode_err(e: serde_json::Error) -> Error {
+    Error::Invalid(format!("could not encode archive: {e}"))
+}
+
+// ── Writing ──────────────────────────────────────────────
+
+fn all_embeddings(conn: &Connection) -> Result<Vec<EmbeddingRow>> {
+    let mut stmt = conn.prepare(
+        "SELECT idea_id, model, dims, vector, created_at FROM embeddings ORDER BY idea_id, model",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, i64>(2)?,
+            row.get::<_, Vec<u8>>(3)?,
+            row.get::<_, i64>(4)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for row in rows {
+        let (idea_id, model, dims, bytes, created_at) = row?;
+        match embeddings::decode(&bytes, dims as usize) {
+            Some(vector) => out.push(EmbeddingRow {
+                idea_id,
+                model,
+                vector,
+                created_at,
+            }),
+            None => log::warn!("Not archiving corrupted embedding for idea {idea_id}"),
+        }
+    }
+    Ok(out)
+}
+
+fn write_lines<T: Serialize>(zip: &mut ZipWriter<File>, rows: &[T]) -> Result<()> {
+    for row in rows {
+        serde_json::to_writer(&mut *zip, row).map_err(encode_err)?;
+        zip.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Write the archive next to `path` and move it into place once complete,
+/// so a failed export never leaves a truncated file behind.
+fn write_archive(path: &Path, contents: &Contents) -> Result<()> {
+    let partial = path.with_extension("partial");
+    let result = (|| {
+        let mut zip = ZipWriter::new(File::create(&partial)?);
+        let options = SimpleFileOptions::default();
+
+        zip.start_file(MANIFEST, options).map_err(zip_err)?;
+        serde_json::to_writer_pretty(&mut zip, &contents.manifest).map_err(encode_err)?;
+        zip.start_file(IDEAS, options).map_err(zip_err)?;
+        write_lines(&mut zip, &contents.ideas)?;
+        zip.start_file(EMBEDDINGS, options).map_err(zip_err)?;
+        write_lines(&mut zip, &contents.embeddings)?;
+        if let Some(settings) = &contents.settings {
+            zip.start_file(SETTINGS, options).map_err(zip_err)?;
+            serde_json::to_writer_pretty(&mut zip, settings).map_err(encode_err)?;
+        }
+        zip.finish().map_err(zip_err)?;
+        std::fs::rename(&partial, path)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&partial);
+    }
+    result
+}
+
+// ── Reading ──────────────────────────────────────────────
+
+fn read_lines<T: DeserializeOwned>(archive: &mut ZipArchive<File>, name: &str) -> Result<Vec<T>> {
+    let file = archive.by_name(name).map_err(zip_err)?;
+    let mut rows = Vec::new();
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let row = serde_json::from_str(&line)
+            .map_err(|e| Error::Invalid(format!("{name} line {}: {e}", i + 1)))?;
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn check_manifest(manifest: &Manifest) -> Result<()> {
+    if manifest.format != FORMAT {
+        return Err(Error::Invalid("not a Glimt archive".into()));
+    }
+    if manifest.format_version > FORMAT_VERSION || manifest.schema_version > CURRENT_SCHEMA_VERSION
+    {
+        return Err(Error::Invalid(format!(
+            "archive was made by a newer version of Glimt ({}, schema {}); update to import it",
+            manifest.app_version, manifest.schema_version
+        )));
+    }
+    Ok(())
+}
+
+fn read_archive(path: &Path) -> Result<Contents> {
+    let mut archive = ZipArchive::new(File::open(path)?).map_err(zip_err)?;
+    let manifest: Manifest = {
+        let file = archive
+            .by_name(MANIFEST)
+            .map_err(|_| Error::Invalid("not a Glimt archive: no manifest".into()))?;
+        serde_json::from_reader(file)
+            .map_err(|e| Error::Invalid(format!("unreadable manifest: {e}")))?
+    };
+    check_manifest(&manifest)?;
+
+    let ideas = read_lines(&mut archive, IDEAS)?;
+    let embeddings = read_lines(&mut archive, EMBEDDINGS)?;
+    // Settings are optional, and one from a newer build is read as far as
+    // this one understands it.
+    let settings = match archive.by_name(SETTINGS) {
+        Ok(mut file) => {
+            let mut raw = String::new();
+            file.read_to_string(&mut raw)?;
+            serde_json::from_str(&raw)
+                .map_err(|e| log::warn!("Ignoring archived settings: {e}"))
+                .ok()
+        }
+        Err(_) => None,
+    };
+    Ok(Contents {
+        manifest,
+        ideas,
+        embeddings,
+        settings,
+    })
+}
+
+/// Every exported file of the ideas here, to clean up after a replace.
+fn exported_files(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt =
+        conn.prepare("SELECT markdown_path FROM ideas WHERE markdown_path IS NOT NULL")?;
+    let rows = stmt.query_map([], |row| row.get(0))?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+/// Write the archive's rows in one transaction. Returns the summary and
+/// the ids whose contents now come from the archive.
+///
+/// Ids end up in file names, so ideas whose id is not a UUID get a new one,
+/// and their embeddings follow. The archive's `markdown_path` points into
+/// another machine's vault and is dropped.
+fn apply(
+    conn: &mut Connection,
+    contents: &Contents,
+    mode: ImportMode,
+) -> Result<(ImportSummary, Vec<String>)> {
+    let tx = conn.transaction()?;
+    if mode == ImportMode::Replace {
+        // Embeddings, attachments, recordings and revisions go with their
+        // ideas through the foreign key cascade. The caller prunes the files.
+        tx.execute("DELETE FROM ideas", [])?;
+    }
+
+    let mut summary = ImportSummary::default();
+    let mut taken = HashSet::new();
+    let mut renamed = HashMap::new();
+    for archived in &contents.ideas {
+        if taken.contains(&archived.id) || renamed.contains_key(&archived.id) {
+            continue;
+        }
+        let mut idea = Idea {
+            markdown_path: None,
+            ..archived.clone()
+        };
+        if !ideas::is_valid_id(&idea.id) {
+            idea.id = uuid::Uuid::new_v4().to_string();
+            renamed.insert(archived.id.clone(), idea.id.clone());
+        }
+        match ideas::get(&tx, &idea.id)? {
+            Some(existing) if existing.updated_at >= idea.updated_at => {
+                summary.skipped += 1;
+                continue;
+            }
+            Some(_) => {
+                // In place, so attachments, the recording and revisions
+                // stay. Embeddings of the old text go.
+                ideas::overwrite(&tx, &idea)?;
+                embeddings::delete_for_idea(&tx, &idea.id)?;
+                summary.updated += 1;
+            }
+            None => {
+                ideas::insert(&tx, &idea)?;
+                summary.added += 1;
+            }
+        }
+        taken.insert(idea.id.clone());
+    }
+
+    for row in &contents.embeddings {
+        let idea_id = renamed.get(&row.idea_id).unwrap_or(&row.idea_id);
+        if !taken.contains(idea_id) || row.vector.is_empty() {
+            continue;
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO embeddings (idea_id, model, dims, vector, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                idea_id,
+                row.model,
+                row.vector.len() as i64,
+                embeddings::encode(&row.vector),
+                row.created_at
+            ],
+        )?;
+        summary.embeddings += 1;
+    }
+    tx.commit()?;
+    Ok((summary, taken.into_iter().collect()))
+}
+
+/// Preferences that travel with an archive. The export folder, microphone
+/// and shortcuts belong to the machine and are kept.
+fn restore_preferences(settings: &mut Settings, archived: &Settings) {
+    settings.theme = archived.theme;
+    settings.stt_model.clone_from(&archived.stt_model);
+    settings.auto_title_enabled = archived.auto_title_enabled;
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+/// Write every idea, embedding and the settings to a zip archive at `path`.
+/// Archives are not encrypted, so one of an encrypted database is only
+/// written with `allow_plaintext`.
+#[tauri::command]
+pub async fn export_archive(
+    app: AppHandle,
+    path: String,
+    allow_plaintext: bool,
+) -> Result<Manifest> {
+    if app.state::<Encryption>().passphrase().is_some() && !allow_plaintext {
+        return Err(Error::Invalid(
+            "the database is encrypted, but the archive would not be".into(),
+        ));
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        let (ideas, embeddings) = {
+            let db = app.state::<Db>();
+            let conn = db.conn();
+            (ideas::list_all(&conn)?, all_embeddings(&conn)?)
+        };
+        let contents = Contents {
+            manifest: Manifest {
+                format: FORMAT.into(),
+                format_version: FORMAT_VERSION,
+                schema_version: CURRENT_SCHEMA_VERSION,
+                app_version: app.package_info().version.to_string(),
+                created_at: now_millis(),
+                ideas: ideas.len(),
+                embeddings: embeddings.len(),
+            },
+            ideas,
+            embeddings,
+            settings: Some(app.state::<SettingsStore>().get()),
+        };
+        write_archive(Path::new(&path), &contents)?;
+        log::info!(
+            "Exported {} ideas and {} embeddings to {path}",
+            contents.manifest.ideas,
+            contents.manifest.embeddings
+        );
+        Ok(contents.manifest)
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("export failed: {e}")))?
+}
+
+/// Import an archive written by [`export_archive`], merging it into the
+/// current ideas or replacing them.
+#[tauri::command]
+pub async fn import_archive(
+    app: AppHandle,
+    path: String,
+    mode: ImportMode,
+) -> Result<ImportSummary> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let contents = read_archive(Path::new(&path))?;
+        let (summary, changed) = {
+            let db = app.state::<Db>();
+            let mut conn = db.conn();
+            let replaced = match mode {
+                ImportMode::Replace => exported_files(&conn)?,
+                ImportMode::Merge => Vec::new(),
+            };
+            let applied = apply(&mut conn, &contents, mode)?;
+            // Rebuilt from the table on the next search.
+            app.state::<VectorIndex>().clear();
+            if mode == ImportMode::Replace {
+                crate::log_err("prune attachments", app.state::<Blobs>().prune(&conn));
+                crate::log_err("prune recordings", app.state::<Recordings>().prune(&conn));
+                for path in &replaced {
+                    crate::log_err("remove exported idea", vault::remove_file(Some(path)));
+                }
+            }
+            vault::reexport(&app, &conn, &applied.1);
+            applied
+        };
+
+        if mode == ImportMode::Replace {
+            if let Some(archived) = &contents.settings {
+                app.state::<SettingsStore>()
+                    .update(&app, |settings| restore_preferences(settings, archived))?;
+            }
+        }
+        if mode == ImportMode::Replace || !changed.is_empty() {
+            crate::log_err(
+                "emit imported ideas",
+                app.emit(ideas::CHANGED_EVENT, &changed),
+            );
+        }
+        log::info!(
+            "Imported {path} ({mode:?}): {} added, {} updated, {} skipped, {} embeddings",
+            summary.added,
+            summary.updated,
+            summary.skipped,
+            summary.embeddings
+        );
+        Ok(summary)
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("import failed: {e}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use crate::{attachments, revisions};
+
+    const A: &str = "0b6f3c9e-2d4a-4f5e-9a1b-7c8d9e0f1a2b";
+    const B: &str = "1c7a4d0f-3e5b-4a6f-8b2c-8d9e0f1a2b3c";
+    const C: &str = "2d8b5e1a-4f6c-4b7a-9c3d-9e0f1a2b3c4d";
+
+    fn idea(id: &str, updated_at: i64, text: &str) -> Idea {
+        Idea {
+            updated_at,
+            ..ideas::test_idea(id, text)
+        }
+    }
+
+    fn contents(ideas: Vec<Idea>, embeddings: Vec<EmbeddingRow>) -> Contents {
+        Contents {
+            manifest: Manifest {
+                format: FORMAT.into(),
+                format_version: FORMAT_VERSION,
+                schema_version: CURRENT_SCHEMA_VERSION,
+                app_version: "test".into(),
+                created_at: 0,
+                ideas: ideas.len(),
+                embeddings: embeddings.len(),
+            },
+            ideas,
+            embeddings,
+            settings: None,
+        }
+    }
+
+    fn open() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "foreign_keys", true).unwrap();
+        crate::migrations::run(&mut conn, None).unwrap();
+        conn
+    }
+
+    #[test]
+    fn round_trips_and_merges_by_updated_at() {
+        let source = open();
+        ideas::insert(&source, &idea(A, 10, "first")).unwrap();
+        ideas::insert(&source, &idea(B, 30, "second, edited")).unwrap();
+        embeddings::store(&source, A, "m", &[0.25, -1.5]).unwrap();
+        embeddings::store(&source, B, "m", &[1.0, 2.0]).unwrap();
+
+        let ideas = ideas::list_all(&source).unwrap();
+        let embeddings = all_embeddings(&source).unwrap();
+        let dir = TestDir::new("archive");
+        let path = dir.join("glimt.zip");
+        let contents = Contents {
+            settings: Some(Settings::default()),
+            ..contents(ideas, embeddings)
+        };
+        write_archive(&path, &contents).unwrap();
+        let read = read_archive(&path).unwrap();
+        assert_eq!(read.ideas.len(), 2);
+        assert_eq!(read.embeddings, contents.embeddings);
+        assert_eq!(read.settings, Some(Settings::default()));
+
+        // A is newer here, B older, C only exists here.
+        let mut target = open();
+        ideas::insert(&target, &idea(A, 20, "first, edited here")).unwrap();
+        ideas::insert(&target, &idea(B, 20, "second")).unwrap();
+        ideas::insert(&target, &idea(C, 20, "third")).unwrap();
+        embeddings::store(&target, B, "m", &[9.0, 9.0]).unwrap();
+        let blobs = Blobs::new(dir.join("blobs"));
+        let upload = attachments::Upload::new("notes.txt", b"kept".to_vec()).unwrap();
+        let attached = attachments::add(&target, &blobs, B, &upload).unwrap();
+        target
+            .execute(
+                "INSERT INTO idea_revisions (idea_id, text, title, created_at)
+                 VALUES (?1, 'draft', NULL, 5)",
+                [B],
+            )
+            .unwrap();
+        // Attaching counts as an edit; put B back behind the archive.
+        target
+            .execute("UPDATE ideas SET updated_at = 20 WHERE id = ?1", [B])
+            .unwrap();
+
+        let (summary, mut changed) = apply(&mut target, &read, ImportMode::Merge).unwrap();
+        changed.sort();
+        assert_eq!((summary.added, summary.updated, summary.skipped), (0, 1, 1));
+        assert_eq!(summary.embeddings, 1);
+        assert_eq!(changed, [B]);
+        let text = |conn: &Connection, id| ideas::get(conn, id).unwrap().unwrap().text;
+        assert_eq!(text(&target, A), "first, edited here");
+        assert_eq!(text(&target, B), "second, edited");
+        assert_eq!(ideas::search_fts(&target, "edited").unwrap().len(), 2);
+        let stored = embeddings::load_all(&target, "m").unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].vector, [1.0, 2.0]);
+        let merged = ideas::get(&target, B).unwrap().unwrap();
+        assert_eq!(merged.attachments, [attached]);
+        let kept: Vec<String> = revisions::list(&target, B)
+            .unwrap()
+            .into_iter()
+            .map(|revision| revision.text)
+            .collect();
+        assert_eq!(kept, ["second", "draft"], "the local version is kept too");
+
+        let (summary, _) = apply(&mut target, &read, ImportMode::Replace).unwrap();
+        assert_eq!((summary.added, summary.embeddings), (2, 2));
+        assert!(ideas::get(&target, C).unwrap().is_none());
+        assert_eq!(text(&target, A), "first");
+        assert_eq!(blobs.prune(&target).unwrap(), 1);
+    }
+
+    #[test]
+    fn renames_foreign_ids_and_drops_foreign_paths() {
+        let foreign = "../../outside";
+        let read = contents(
+            vec![Idea {
+                markdown_path: Some("/elsewhere/note.md".into()),
+                ..idea(foreign, 10, "from afar")
+            }],
+            vec![EmbeddingRow {
+                idea_id: foreign.into(),
+                model: "m".into(),
+                vector: vec![1.0],
+                created_at: 0,
+            }],
+        );
+        let mut conn = open();
+        let (summary, changed) = apply(&mut conn, &read, ImportMode::Merge).unwrap();
+        assert_eq!((summary.added, summary.embeddings), (1, 1));
+        assert!(ideas::is_valid_id(&changed[0]));
+        let stored = ideas::get(&conn, &changed[0]).unwrap().unwrap();
+        assert_eq!(stored.markdown_path, None);
+        assert_eq!(
+            embeddings::load_all(&conn, "m").unwrap()[0].idea_id,
+            changed[0]
+        );
+    }
+
+    #[test]
+    fn refuses_archives_from_a_newer_schema() {
+        let mut manifest = Manifest {
+            format: FORMAT.into(),
+            format_version: FORMAT_VERSION,
+            schema_version: CURRENT_SCHEMA_VERSION,
+            app_version: "9.9.9".into(),
+            created_at: 0,
+            ideas: 0,
+            embeddings: 0,
+        };
+        assert!(check_manifest(&manifest).is_ok());
+        manifest.schema_version = 1;
+        assert!(check_manifest(&manifest).is_ok());
+        manifest.schema_version = CURRENT_SCHEMA_VERSION + 1;
+        assert!(check_manifest(&manifest).is_err());
+        manifest.schema_version = CURRENT_SCHEMA_VERSION;
+        manifest.format = "something-else".into();
+        assert!(check_manifest(&manifest).is_err());
+    }
+}
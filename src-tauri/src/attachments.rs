@@ -0,0 +1,485 @@
+//! Files and images attached to ideas.
+//!
+//! Contents live in a content-addressed store under the app data dir,
+//! named by their SHA-256, so the same screenshot attached twice is kept
+//! once. The `attachments` table maps ideas to blobs; rows go with their
+//! idea via `ON DELETE CASCADE` and [`Blobs::prune`] then removes files no
+//! row refers to.
+//!
+//! Hashing and thumbnailing happen in an [`Upload`] before the database
+//! lock is taken; only writing the blob and its row happen under it, so a
+//! prune cannot remove a blob between the two.
+
+use std::path::{Path, PathBuf};
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::ipc::{InvokeBody, Request, Response};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::db::{now_millis, Db};
+use crate::error::{Error, Result};
+use crate::{ideas, vault};
+
+/// Longest side of a thumbnail, in pixels.
+const THUMBNAIL_SIZE: u32 = 320;
+const THUMBNAIL_DIR: &str = "thumbnails";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attachment {
+    pub id: String,
+    /// File name as attached, e.g. `screenshot.png`.
+    pub name: String,
+    pub mime: String,
+    pub size: i64,
+    /// Whether [`read_attachment`] can return a thumbnail.
+    pub thumbnail: bool,
+    pub created_at: i64,
+}
+
+/// An attachment with the blob it points to, for export.
+pub(crate) struct Stored {
+    pub attachment: Attachment,
+    pub hash: String,
+}
+
+impl Stored {
+    /// File name in an export folder: the start of the hash keeps names
+    /// unique, the rest keeps them readable.
+    pub(crate) fn export_name(&self) -> String {
+        let name: String = self
+            .attachment
+            .name
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        format!("{}-{name}", &self.hash[..12])
+    }
+
+    pub(crate) fn is_image(&self) -> bool {
+        self.attachment.mime.starts_with("image/")
+    }
+}
+
+/// A file ready to attach: named, hashed, typed and thumbnailed, which is
+/// the slow part and needs no database.
+pub(crate) struct Upload {
+    name: String,
+    data: Vec<u8>,
+    hash: String,
+    mime: String,
+    /// PNG thumbnail, if the data is an image this build can decode.
+    thumbnail: Option<Vec<u8>>,
+}
+
+impl Upload {
+    /// Prepare `data` to be attached as `name`; only its file name is kept.
+    pub(crate) fn new(name: &str, data: Vec<u8>) -> Result<Self> {
+        let name = Path::new(name)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .filter(|name| !name.is_empty())
+            .ok_or_else(|| Error::Invalid("attachment needs a file name".into()))?;
+        Ok(Self {
+            hash: format!("{:x}", Sha256::digest(&data)),
+            mime: mime_type(&name, &data),
+            thumbnail: make_thumbnail(&data),
+            name,
+            data,
+        })
+    }
+}
+
+/// Managed state: the blob store directory.
+pub struct Blobs {
+    dir: PathBuf,
+}
+
+impl Blobs {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub(crate) fn path(&self, hash: &str) -> PathBuf {
+        self.dir.join(&hash[..2]).join(hash)
+    }
+
+    fn thumbnail_path(&self, hash: &str) -> PathBuf {
+        self.dir.join(THUMBNAIL_DIR).join(format!("{hash}.png"))
+    }
+
+    /// Store the upload unless a blob with the same contents exists.
+    /// Returns whether it has a thumbnail.
+    fn put(&self, upload: &Upload) -> Result<bool> {
+        let path = self.path(&upload.hash);
+        if !path.is_file() {
+            std::fs::create_dir_all(path.parent().unwrap_or(&self.dir))?;
+            let partial = path.with_extension("part");
+            std::fs::write(&partial, &upload.data)?;
+            std::fs::rename(&partial, &path)?;
+        }
+        let thumbnail = self.thumbnail_path(&upload.hash);
+        if thumbnail.is_file() {
+            return Ok(true);
+        }
+        let Some(png) = &upload.thumbnail else {
+            return Ok(false);
+        };
+        let written = std::fs::create_dir_all(self.dir.join(THUMBNAIL_DIR))
+            .and_then(|()| std::fs::write(&thumbnail, png));
+        if let Err(e) = &written {
+            log::warn!("Could not write thumbnail {}: {e}", thumbnail.display());
+        }
+        Ok(written.is_ok())
+    }
+
+    /// Delete blobs and thumbnails no attachment refers to any more.
+    /// Returns how many blobs went.
+    pub(crate) fn prune(&self, conn: &Connection) -> Result<usize> {
+        let mut stmt = conn.prepare("SELECT 1 FROM attachments WHERE hash = ?1 LIMIT 1")?;
+        let mut removed = 0;
+        let Ok(shards) = std::fs::read_dir(&self.dir) else {
+            return Ok(0);
+        };
+        for shard in shards.flatten() {
+            if shard.file_name() == THUMBNAIL_DIR || !shard.path().is_dir() {
+                continue;
+            }
+            for blob in std::fs::read_dir(shard.path())?.flatten() {
+                let hash = blob.file_name().to_string_lossy().into_owned();
+                if stmt.exists(params![hash])? {
+                    continue;
+                }
+                std::fs::remove_file(blob.path())?;
+                let _ = std::fs::remove_file(self.thumbnail_path(&hash));
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// A PNG thumbnail of `data` if it is an image this build can decode.
+fn make_thumbnail(data: &[u8]) -> Option<Vec<u8>> {
+    let image = image::load_from_memory(data).ok()?;
+    let mut png = Vec::new();
+    image
+        .thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+        .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| log::warn!("Could not make a thumbnail: {e}"))
+        .ok()?;
+    Some(png)
+}
+
+fn mime_type(name: &str, data: &[u8]) -> String {
+    if let Some(kind) = infer::get(data) {
+        return kind.mime_type().to_owned();
+    }
+    let extension = Path::new(name)
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase());
+    match extension.as_deref() {
+        Some("txt" | "md" | "csv" | "log") => "text/plain",
+        Some("svg") => "image/svg+xml",
+        Some("json") => "application/json",
+        _ => "application/octet-stream",
+    }
+    .to_owned()
+}
+
+// ── Repository ───────────────────────────────────────────
+
+fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Stored> {
+    Ok(Stored {
+        attachment: Attachment {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            mime: row.get(2)?,
+            size: row.get(3)?,
+            thumbnail: row.get::<_, i64>(4)? == 1,
+            created_at: row.get(5)?,
+        },
+        hash: row.get(6)?,
+    })
+}
+
+const COLUMNS: &str = "id, name, mime, size, thumbnail, created_at, hash";
+
+/// Attach the upload to an idea.
+pub(crate) fn add(
+    conn: &Connection,
+    blobs: &Blobs,
+    idea_id: &str,
+    upload: &Upload,
+) -> Result<Attachment> {
+    if !ideas::exists(conn, idea_id)? {
+        return Err(Error::NotFound(idea_id.to_owned()));
+    }
+    let thumbnail = blobs.put(upload)?;
+    let attachment = Attachment {
+        id: uuid::Uuid::new_v4().to_string(),
+        name: upload.name.clone(),
+        mime: upload.mime.clone(),
+        size: upload.data.len() as i64,
+        thumbnail,
+        created_at: now_millis(),
+    };
+    conn.execute(
+        "INSERT INTO attachments (id, idea_id, hash, name, mime, size, thumbnail, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            attachment.id,
+            idea_id,
+            upload.hash,
+            attachment.name,
+            attachment.mime,
+            attachment.size,
+            attachment.thumbnail as i64,
+            attachment.created_at
+        ],
+    )?;
+    touch(conn, idea_id)?;
+    Ok(attachment)
+}
+
+/// Detach an attachment. Returns the idea it belonged to.
+pub fn remove(conn: &Connection, blobs: &Blobs, id: &str) -> Result<String> {
+    let idea_id: String = conn
+        .query_row(
+            "DELETE FROM attachments WHERE id = ?1 RETURNING idea_id",
+            params![id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| Error::Invalid(format!("there is no attachment {id}")))?;
+    touch(conn, &idea_id)?;
+    blobs.prune(conn)?;
+    Ok(idea_id)
+}
+
+/// Attachments count as an edit, so vault sync keeps the newer file.
+fn touch(conn: &Connection, idea_id: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE ideas SET updated_at = ?1 WHERE id = ?2",
+        params![now_millis(), idea_id],
+    )?;
+    Ok(())
+}
+
+fn get(conn: &Connection, id: &str) -> Result<Stored> {
+    conn.query_row(
+        &format!("SELECT {COLUMNS} FROM attachments WHERE id = ?1"),
+        params![id],
+        from_row,
+    )
+    .optional()?
+    .ok_or_else(|| Error::Invalid(format!("there is no attachment {id}")))
+}
+
+/// An idea's attachments, oldest first.
+pub(crate) fn for_idea(conn: &Connection, idea_id: &str) -> Result<Vec<Stored>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {COLUMNS} FROM attachments WHERE idea_id = ?1 ORDER BY created_at"
+    ))?;
+    let rows = stmt.query_map(params![idea_id], from_row)?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+/// Re-export the idea and tell every window it changed.
+fn announce(app: &AppHandle, conn: &Connection, idea_id: String) {
+    let ids = [idea_id];
+    vault::reexport(app, conn, &ids);
+    crate::log_err(
+        "emit ideas change",
+        app.emit(ideas::CHANGED_EVENT, &ids[..]),
+    );
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+/// Attach the upload and announce it, off the async runtime.
+async fn attach(
+    app: AppHandle,
+    id: String,
+    prepare: impl FnOnce() -> Result<Upload> + Send + 'static,
+) -> Result<Attachment> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let upload = prepare()?;
+        let db = app.state::<Db>();
+        let conn = db.conn();
+        let attachment = add(&conn, &app.state::<Blobs>(), &id, &upload)?;
+        announce(&app, &conn, id);
+        Ok(attachment)
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("attaching failed: {e}")))?
+}
+
+/// Attach a file from disk, e.g. one picked in a file dialog.
+#[tauri::command]
+pub async fn attach_file(app: AppHandle, id: String, path: String) -> Result<Attachment> {
+    attach(app, id, move || Upload::new(&path, std::fs::read(&path)?)).await
+}
+
+/// Attach raw bytes, e.g. a pasted screenshot. The idea and file name come
+/// in the `x-idea-id` and `x-name` headers.
+#[tauri::command]
+pub async fn attach_data(app: AppHandle, request: Request<'_>) -> Result<Attachment> {
+    let InvokeBody::Raw(data) = request.body() else {
+        return Err(Error::Invalid("expected raw attachment bytes".into()));
+    };
+    let header = |key: &str| {
+        request
+            .headers()
+            .get(key)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned)
+            .ok_or_else(|| Error::Invalid(format!("missing {key} header")))
+    };
+    let id = header("x-idea-id")?;
+    // Percent-encoded, since header values can't carry every file name.
+    let name = percent_encoding::percent_decode_str(&header("x-name")?)
+        .decode_utf8_lossy()
+        .into_owned();
+    let data = data.to_vec();
+    attach(app, id, move || Upload::new(&name, data)).await
+}
+
+#[tauri::command]
+pub fn remove_attachment(
+    app: AppHandle,
+    db: State<'_, Db>,
+    blobs: State<'_, Blobs>,
+    id: String,
+) -> Result<()> {
+    let conn = db.conn();
+    let idea_id = remove(&conn, &blobs, &id)?;
+    announce(&app, &conn, idea_id);
+    Ok(())
+}
+
+/// The attachment's bytes, or its PNG thumbnail.
+#[tauri::command]
+pub async fn read_attachment(
+    app: AppHandle,
+    id: String,
+    thumbnail: Option<bool>,
+) -> Result<Response> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let stored = get(&app.state::<Db>().conn(), &id)?;
+        let blobs = app.state::<Blobs>();
+        let path = if thumbnail.unwrap_or(false) && stored.attachment.thumbnail {
+            blobs.thumbnail_path(&stored.hash)
+        } else {
+            blobs.path(&stored.hash)
+        };
+        Ok(Response::new(std::fs::read(path)?))
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("reading the attachment failed: {e}")))?
+}
+
+/// Copy an attachment out to `path`, e.g. one chosen in a save dialog.
+#[tauri::command]
+pub async fn save_attachment(app: AppHandle, id: String, path: String) -> Result<()> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let stored = get(&app.state::<Db>().conn(), &id)?;
+        std::fs::copy(app.state::<Blobs>().path(&stored.hash), path)?;
+        Ok(())
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("saving the attachment failed: {e}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    fn open() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "foreign_keys", true).unwrap();
+        crate::migrations::run(&mut conn, None).unwrap();
+        conn
+    }
+
+    fn upload(name: &str, data: &[u8]) -> Upload {
+        Upload::new(name, data.to_vec()).unwrap()
+    }
+
+    fn store(name: &str) -> (TestDir, Blobs) {
+        let dir = TestDir::new(name);
+        let blobs = Blobs::new(dir.to_path_buf());
+        (dir, blobs)
+    }
+
+    #[test]
+    fn shares_blobs_and_prunes_them_with_the_idea() {
+        let conn = open();
+        let (_dir, blobs) = store("blobs");
+        let a = ideas::create(&conn, "first", None).unwrap();
+        let b = ideas::create(&conn, "second", None).unwrap();
+
+        let one = add(
+            &conn,
+            &blobs,
+            &a.id,
+            &upload("/tmp/notes.txt", b"same bytes"),
+        )
+        .unwrap();
+        add(&conn, &blobs, &b.id, &upload("copy.txt", b"same bytes")).unwrap();
+        assert_eq!(one.name, "notes.txt");
+        assert_eq!(one.mime, "text/plain");
+        assert!(!one.thumbnail);
+
+        let stored = get(&conn, &one.id).unwrap();
+        assert!(blobs.path(&stored.hash).is_file());
+        assert_eq!(
+            ideas::get(&conn, &a.id).unwrap().unwrap().attachments,
+            vec![one]
+        );
+
+        ideas::delete(&conn, &a.id).unwrap();
+        assert_eq!(
+            blobs.prune(&conn).unwrap(),
+            0,
+            "still used by the other idea"
+        );
+        ideas::delete(&conn, &b.id).unwrap();
+        assert_eq!(blobs.prune(&conn).unwrap(), 1);
+        assert!(!blobs.path(&stored.hash).exists());
+
+        assert!(matches!(
+            add(&conn, &blobs, &a.id, &upload("late.txt", b"x")),
+            Err(Error::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn thumbnails_images() {
+        let conn = open();
+        let (_dir, blobs) = store("thumbnails");
+        let idea = ideas::create(&conn, "shot", None).unwrap();
+
+        let mut png = Vec::new();
+        image::RgbImage::new(800, 400)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let shot = add(&conn, &blobs, &idea.id, &upload("screen shot.png", &png)).unwrap();
+        assert_eq!(shot.mime, "image/png");
+        assert!(shot.thumbnail);
+
+        let stored = get(&conn, &shot.id).unwrap();
+        let thumbnail = image::open(blobs.thumbnail_path(&stored.hash)).unwrap();
+        assert_eq!((thumbnail.width(), thumbnail.height()), (320, 160));
+        assert!(stored.export_name().ends_with("-screen-shot.png"));
+    }
+}
@@ -0,0 +1,303 @@
+//! Decoding recorded audio into the 16 kHz mono samples Whisper expects.
+//!
+//! Containers (WAV, WebM/Matroska, Ogg) and most codecs go through
+//! symphonia. It has no Opus decoder, so Opus packets, which is what
+//! MediaRecorder produces, are handed to libopus in `native-stt` builds.
+
+use std::io::Cursor;
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CodecParameters, DecoderOptions, CODEC_TYPE_NULL, CODEC_TYPE_OPUS};
+use symphonia::core::errors::Error as DecodeError;
+use symphonia::core::formats::{FormatOptions, Packet};
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::error::{Error, Result};
+
+/// Sample rate Whisper models are trained on.
+pub const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+/// Opus always decodes at 48 kHz regardless of the input rate.
+#[cfg(feature = "native-stt")]
+const OPUS_SAMPLE_RATE: u32 = 48_000;
+
+fn invalid(e: impl std::fmt::Display) -> Error {
+    Error::Invalid(format!("could not decode audio: {e}"))
+}
+
+/// Decode a recording in any supported container to 16 kHz mono.
+pub fn decode_to_whisper(bytes: Vec<u8>) -> Result<Vec<f32>> {
+    let source = MediaSourceStream::new(Box::new(Cursor::new(bytes)), Default::default());
+    let probed = symphonia::default::get_probe()
+        .format(
+            &Hint::new(),
+            source,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(invalid)?;
+    let mut format = probed.format;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| invalid("no audio track"))?;
+    let track_id = track.id;
+    let mut decoder = PacketDecoder::new(&track.codec_params)?;
+
+    let mut interleaved = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(DecodeError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(DecodeError::ResetRequired) => break,
+            Err(e) => return Err(invalid(e)),
+        };
+        if packet.track_id() == track_id {
+            decoder.decode(&packet, &mut interleaved)?;
+        }
+    }
+
+    let mono = downmix(&interleaved, decoder.channels);
+    Ok(resample(&mono, decoder.rate, WHISPER_SAMPLE_RATE))
+}
+
+struct PacketDecoder {
+    kind: Kind,
+    channels: usize,
+    rate: u32,
+}
+
+enum Kind {
+    Symphonia(Box<dyn symphonia::core::codecs::Decoder>),
+    #[cfg(feature = "native-stt")]
+    Opus(opus::Decoder, Vec<f32>),
+}
+
+impl PacketDecoder {
+    fn new(params: &CodecParameters) -> Result<Self> {
+        let channels = params
+            .channels
+            .map_or(1, |channels| channels.count())
+            .max(1);
+        if params.codec == CODEC_TYPE_OPUS {
+            return Self::opus(channels);
+        }
+        let rate = params
+            .sample_rate
+            .ok_or_else(|| invalid("unknown sample rate"))?;
+        let decoder = symphonia::default::get_codecs()
+            .make(params, &DecoderOptions::default())
+            .map_err(invalid)?;
+        Ok(Self {
+            kind: Kind::Symphonia(decoder),
+            channels,
+            rate,
+        })
+    }
+
+    #[cfg(feature = "native-stt")]
+    fn opus(channels: usize) -> Result<Self> {
+        let (layout, channels) = if channels == 1 {
+            (opus::Channels::Mono, 1)
+        } else {
+            (opus::Channels::Stereo, 2)
+        };
+        let decoder = opus::Decoder::new(OPUS_SAMPLE_RATE, layout).map_err(invalid)?;
+        // 120 ms is the longest frame Opus allows.
+        let buffer = vec![0.0; OPUS_SAMPLE_RATE as usize * 120 / 1000 * channels];
+        Ok(Self {
+            kind: Kind::Opus(decoder, buffer),
+            channels,
+            rate: OPUS_SAMPLE_RATE,
+        })
+    }
+
+    #[cfg(not(feature = "native-stt"))]
+    fn opus(_channels: usize) -> Result<Self> {
+        Err(invalid(
+            "Opus audio needs a build with the native-stt feature",
+        ))
+    }
+
+    /// Append the packet's samples, interleaved, to `out`. Corrupt packets
+    /// are skipped rather than failing the whole recording.
+    fn decode(&mut self, packet: &Packet, out: &mut Vec<f32>) -> Result<()> {
+        match &mut self.kind {
+            Kind::Symphonia(decoder) => match decoder.decode(packet) {
+                Ok(decoded) => {
+                    let mut samples =
+                        SampleBuffer::<f32>::new(decoded.capacity() as u64, *decoded.spec());
+                    samples.copy_interleaved_ref(decoded);
+                    out.extend_from_slice(samples.samples());
+                }
+                Err(DecodeError::DecodeError(e)) => log::warn!("Skipping audio packet: {e}"),
+                Err(e) => return Err(invalid(e)),
+            },
+            #[cfg(feature = "native-stt")]
+            Kind::Opus(decoder, buffer) => {
+                match decoder.decode_float(&packet.data, buffer, false) {
+                    Ok(frames) => out.extend_from_slice(&buffer[..frames * self.channels]),
+                    Err(e) => log::warn!("Skipping Opus packet: {e}"),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn downmix(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+    interleaved
+        .chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+/// Resample mono audio. Downsampling averages the input each output sample
+/// covers, which is enough of a low-pass for speech; upsampling interpolates.
+pub fn resample(input: &[f32], from: u32, to: u32) -> Vec<f32> {
+    if from == to || input.is_empty() {
+        return input.to_vec();
+    }
+    let step = f64::from(from) / f64::from(to);
+    let len = (input.len() as f64 / step) as usize;
+    (0..len)
+        .map(|i| {
+            let pos = i as f64 * step;
+            let start = pos as usize;
+            if step > 1.0 {
+                let end = ((pos + step) as usize).clamp(start + 1, input.len());
+                input[start..end].iter().sum::<f32>() / (end - start) as f32
+            } else {
+                let next = input.get(start + 1).copied().unwrap_or(input[start]);
+                let frac = (pos - start as f64) as f32;
+                input[start] + (next - input[start]) * frac
+            }
+        })
+        .collect()
+}
+
+/// Encode mono samples as a 16-bit PCM WAV file.
+pub fn encode_wav(samples: &[f32], rate: u32) -> Result<Vec<u8>> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let wav_err = |e: hound::Error| Error::Invalid(format!("could not encode audio: {e}"));
+    let mut out = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut out, spec).map_err(wav_err)?;
+    for &sample in samples {
+        let sample = (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16;
+        writer.write_sample(sample).map_err(wav_err)?;
+    }
+    writer.finalize().map_err(wav_err)?;
+    Ok(out.into_inner())
+}
+
+/// Encode mono samples as a 16-bit FLAC file, about half the size of the
+/// same WAV. The end is padded with up to one block of silence.
+pub fn encode_flac(samples: &[f32], rate: u32) -> Result<Vec<u8>> {
+    use flacenc::component::BitRepr;
+    use flacenc::error::Verify;
+
+    let flac_err =
+        |e: &dyn std::fmt::Display| Error::Invalid(format!("could not encode audio: {e}"));
+    let config = flacenc::config::Encoder::default()
+        .into_verified()
+        .map_err(|(_, e)| flac_err(&e))?;
+    let mut ints: Vec<i32> = samples
+        .iter()
+        .map(|&sample| (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i32)
+        .collect();
+    // flacenc counts a short last block in the stream's minimum block size,
+    // which symphonia then reads as variable-size blocking and rejects. A
+    // little trailing silence keeps every block full.
+    ints.resize(ints.len().next_multiple_of(config.block_size), 0);
+    let source = flacenc::source::MemSource::from_samples(&ints, 1, 16, rate as usize);
+    let stream = flacenc::encode_with_fixed_block_size(&config, source, config.block_size)
+        .map_err(|e| flac_err(&e))?;
+    let mut sink = flacenc::bitsink::MemSink::<u8>::new();
+    stream.write(&mut sink).map_err(|e| flac_err(&e))?;
+    Ok(sink.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 16-bit PCM WAV file holding `frames` of a 440 Hz tone.
+    fn wav(rate: u32, channels: u16, frames: u32) -> Vec<u8> {
+        let data_len = frames * u32::from(channels) * 2;
+        let mut out = Vec::new();
+        out.extend_from_slice(b"RIFF");
+        out.extend_from_slice(&(36 + data_len).to_le_bytes());
+        out.extend_from_slice(b"WAVEfmt ");
+        out.extend_from_slice(&16u32.to_le_bytes());
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&channels.to_le_bytes());
+        out.extend_from_slice(&rate.to_le_bytes());
+        out.extend_from_slice(&(rate * u32::from(channels) * 2).to_le_bytes());
+        out.extend_from_slice(&(channels * 2).to_le_bytes());
+        out.extend_from_slice(&16u16.to_le_bytes());
+        out.extend_from_slice(b"data");
+        out.extend_from_slice(&data_len.to_le_bytes());
+        for i in 0..frames {
+            let t = i as f32 / rate as f32;
+            let sample = ((t * 440.0 * std::f32::consts::TAU).sin() * 16_000.0) as i16;
+            for _ in 0..channels {
+                out.extend_from_slice(&sample.to_le_bytes());
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn decodes_stereo_wav_to_16k_mono() {
+        let samples = decode_to_whisper(wav(48_000, 2, 48_000)).unwrap();
+        assert!((samples.len() as i64 - 16_000).abs() <= 1);
+        let peak = samples.iter().fold(0.0f32, |max, s| max.max(s.abs()));
+        assert!(peak > 0.4 && peak <= 1.0, "peak {peak}");
+    }
+
+    #[test]
+    fn encoded_wav_decodes_back() {
+        let tone: Vec<f32> = (0..1600).map(|i| (i as f32 / 10.0).sin() * 0.5).collect();
+        let decoded = decode_to_whisper(encode_wav(&tone, WHISPER_SAMPLE_RATE).unwrap()).unwrap();
+        assert_eq!(decoded.len(), tone.len());
+        assert!(tone.iter().zip(&decoded).all(|(a, b)| (a - b).abs() < 1e-3));
+    }
+
+    #[test]
+    fn encoded_flac_decodes_back() {
+        let tone: Vec<f32> = (0..16_000).map(|i| (i as f32 / 10.0).sin() * 0.5).collect();
+        let flac = encode_flac(&tone, WHISPER_SAMPLE_RATE).unwrap();
+        assert!(flac.starts_with(b"fLaC"));
+        let decoded = decode_to_whisper(flac).unwrap();
+        let (audio, padding) = decoded.split_at(tone.len());
+        assert!(tone.iter().zip(audio).all(|(a, b)| (a - b).abs() < 1e-3));
+        assert!(padding.len() < 4096 && padding.iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn rejects_unknown_formats() {
+        assert!(decode_to_whisper(b"definitely not audio".to_vec()).is_err());
+    }
+
+    #[test]
+    fn resamples_both_directions() {
+        let ramp: Vec<f32> = (0..480).map(|i| i as f32).collect();
+        let down = resample(&ramp, 48_000, 16_000);
+        assert_eq!(down.len(), 160);
+        assert_eq!(down[1], 4.0);
+
+        let up = resample(&[0.0, 1.0], 8_000, 16_000);
+        assert_eq!(up, vec![0.0, 0.5, 1.0, 1.0]);
+    }
+}
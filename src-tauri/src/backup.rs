@@ -0,0 +1,331 @@
+//! Scheduled snapshots of `glimt.db` into the app data `backups` folder.
+//!
+//! Snapshots use SQLite's online backup API from a separate read-only
+//! connection, so commands keep running while one is taken. Each copy is
+//! checked with `PRAGMA integrity_check` before it counts, and only the
+//! newest `backup_keep` are kept. An encrypted database gives encrypted
+//! backups under the same passphrase.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use chrono::Local;
+use rusqlite::backup::Backup;
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::db::{now_millis, Db};
+use crate::encryption::Encryption;
+use crate::error::{Error, Result};
+use crate::ideas;
+use crate::migrations::{self, CURRENT_SCHEMA_VERSION};
+use crate::settings::SettingsStore;
+use crate::vector_index::VectorIndex;
+
+const PREFIX: &str = "glimt-";
+const EXTENSION: &str = "db";
+/// How often the scheduler checks whether a backup is due.
+const CHECK_EVERY: Duration = Duration::from_secs(60);
+/// Pages copied per backup step, with a pause between steps so writers are
+/// not starved on a large database.
+const PAGES_PER_STEP: std::os::raw::c_int = 256;
+const STEP_PAUSE: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackupSchedule {
+    Off,
+    Hourly,
+    #[default]
+    Daily,
+}
+
+impl BackupSchedule {
+    fn period(self) -> Option<Duration> {
+        match self {
+            Self::Off => None,
+            Self::Hourly => Some(Duration::from_secs(60 * 60)),
+            Self::Daily => Some(Duration::from_secs(24 * 60 * 60)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    /// File name inside the backups folder; what `restore_backup` takes.
+    pub name: String,
+    pub created_at: i64,
+    pub size: u64,
+}
+
+/// `Ok` if SQLite finds nothing wrong with the database behind `conn`.
+fn verify(conn: &Connection) -> Result<()> {
+    let result: String = conn.query_row("PRAGMA integrity_check", [], |row| row.get(0))?;
+    if result == "ok" {
+        Ok(())
+    } else {
+        Err(Error::Invalid(format!("integrity check failed: {result}")))
+    }
+}
+
+/// Open `path` read-only, keyed with `passphrase` if it is encrypted.
+fn open_read_only(path: &Path, passphrase: Option<&str>) -> Result<Connection> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    if let Some(passphrase) = passphrase {
+        conn.pragma_update(None, "key", passphrase)?;
+    }
+    Ok(conn)
+}
+
+/// Copy `src` to `dest` and verify the copy. It is written under a
+/// temporary name first, so a failed snapshot never looks like a backup.
+fn snapshot(src: &Connection, dest: &Path, passphrase: Option<&str>) -> Result<()> {
+    let partial = dest.with_extension("partial");
+    let result = (|| {
+        let mut copy = Connection::open(&partial)?;
+        if let Some(passphrase) = passphrase {
+            // SQLCipher only copies pages between databases with the same key.
+            copy.pragma_update(None, "key", passphrase)?;
+        }
+        Backup::new(src, &mut copy)?.run_to_completion(PAGES_PER_STEP, STEP_PAUSE, None)?;
+        verify(&copy)?;
+        drop(copy);
+        std::fs::rename(&partial, dest)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&partial);
+    }
+    result
+}
+
+/// Overwrite the database behind `conn` with the backup at `path`, then
+/// bring an older backup's schema up to date.
+fn restore_into(conn: &mut Connection, path: &Path, passphrase: Option<&str>) -> Result<()> {
+    let src = open_read_only(path, passphrase)?;
+    verify(&src)?;
+    let version = migrations::schema_version(&src)?;
+    if version > CURRENT_SCHEMA_VERSION {
+        return Err(Error::Invalid(format!(
+            "backup is from a newer version of Glimt (schema {version}); update to restore it"
+        )));
+    }
+    Backup::new(&src, conn)?.run_to_completion(PAGES_PER_STEP, STEP_PAUSE, None)?;
+    migrations::run(conn, None).map_err(|e| Error::Invalid(e.to_string()))?;
+    Ok(())
+}
+
+/// Managed state: where the database lives and where its backups go.
+pub struct Backups {
+    db_path: PathBuf,
+    dir: PathBuf,
+}
+
+impl Backups {
+    pub fn new(db_path: PathBuf, dir: PathBuf) -> Self {
+        Self { db_path, dir }
+    }
+
+    /// Every backup, newest first.
+    pub fn list(&self) -> Result<Vec<BackupInfo>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut backups: Vec<BackupInfo> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let is_backup = name.starts_with(PREFIX)
+                    && Path::new(&name)
+                        .extension()
+                        .is_some_and(|ext| ext == EXTENSION);
+                let meta = entry.metadata().ok().filter(|_| is_backup)?;
+                let created_at = meta
+                    .modified()
+                    .ok()?
+                    .duration_since(UNIX_EPOCH)
+                    .ok()?
+                    .as_millis() as i64;
+                Some(BackupInfo {
+                    name,
+                    created_at,
+                    size: meta.len(),
+                })
+            })
+            .collect();
+        // Names carry a sortable timestamp.
+        backups.sort_by(|a, b| b.name.cmp(&a.name));
+        Ok(backups)
+    }
+
+    /// Paths of every backup, for re-encrypting them.
+    #[cfg(feature = "encryption")]
+    pub(crate) fn files(&self) -> Result<Vec<PathBuf>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .map(|backup| self.dir.join(backup.name))
+            .collect())
+    }
+
+    /// Take a snapshot now, with the passphrase the database is open with.
+    pub fn create(&self, passphrase: Option<&str>) -> Result<BackupInfo> {
+        std::fs::create_dir_all(&self.dir)?;
+        // Millisecond names keep two quick snapshots apart and sort by age.
+        let name = format!(
+            "{PREFIX}{}.{EXTENSION}",
+            Local::now().format("%Y%m%d-%H%M%S-%3f")
+        );
+        let src = open_read_only(&self.db_path, passphrase)?;
+        snapshot(&src, &self.dir.join(&name), passphrase)?;
+        log::info!("Backed up the database to {name}");
+        self.list()?
+            .into_iter()
+            .find(|backup| backup.name == name)
+            .ok_or_else(|| Error::Invalid(format!("backup {name} was not written")))
+    }
+
+    /// Delete all but the newest `keep` backups.
+    pub fn prune(&self, keep: usize) -> Result<()> {
+        for old in self.list()?.into_iter().skip(keep.max(1)) {
+            crate::log_err(
+                "remove old backup",
+                std::fs::remove_file(self.dir.join(&old.name)),
+            );
+        }
+        Ok(())
+    }
+
+    /// A backup by name. Only names from [`Self::list`] are accepted, so a
+    /// name cannot reach outside the backups folder.
+    fn path_of(&self, name: &str) -> Result<PathBuf> {
+        self.list()?
+            .into_iter()
+            .find(|backup| backup.name == name)
+            .map(|backup| self.dir.join(backup.name))
+            .ok_or_else(|| Error::Invalid(format!("no backup named {name}")))
+    }
+
+    /// Take a backup if the schedule says one is due, then rotate. Waits
+    /// while the database is locked.
+    fn run_if_due(&self, app: &AppHandle) -> Result<()> {
+        if app.try_state::<Db>().is_none() {
+            return Ok(());
+        }
+        let settings = app.state::<SettingsStore>().get();
+        let Some(period) = settings.backup_schedule.period() else {
+            return Ok(());
+        };
+        let latest = self.list()?.first().map(|backup| backup.created_at);
+        if latest.is_some_and(|at| now_millis() - at < period.as_millis() as i64) {
+            return Ok(());
+        }
+        self.create(app.state::<Encryption>().passphrase().as_deref())?;
+        self.prune(settings.backup_keep as usize)
+    }
+
+    /// Start the scheduler thread.
+    pub fn start(app: &AppHandle) {
+        let app = app.clone();
+        let spawned = std::thread::Builder::new()
+            .name("glimt-backup".into())
+            .spawn(move || loop {
+                crate::log_err("scheduled backup", app.state::<Backups>().run_if_due(&app));
+                std::thread::sleep(CHECK_EVERY);
+            });
+        crate::log_err("start backup scheduler", spawned);
+    }
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+#[tauri::command]
+pub fn list_backups(backups: State<'_, Backups>) -> Result<Vec<BackupInfo>> {
+    backups.list()
+}
+
+#[tauri::command]
+pub async fn backup_now(app: AppHandle) -> Result<BackupInfo> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let backups = app.state::<Backups>();
+        let created = backups.create(app.state::<Encryption>().passphrase().as_deref())?;
+        backups.prune(app.state::<SettingsStore>().get().backup_keep as usize)?;
+        Ok(created)
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("backup failed: {e}")))?
+}
+
+/// Replace the database with the backup `name`. The current database is
+/// backed up first, so a restore can itself be undone.
+#[tauri::command]
+pub async fn restore_backup(app: AppHandle, name: String) -> Result<()> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let backups = app.state::<Backups>();
+        let path = backups.path_of(&name)?;
+        let passphrase = app.state::<Encryption>().passphrase();
+        backups.create(passphrase.as_deref())?;
+        {
+            let db = app
+                .try_state::<Db>()
+                .ok_or_else(|| Error::Invalid("unlock the database first".into()))?;
+            let mut conn = db.conn();
+            restore_into(&mut conn, &path, passphrase.as_deref())?;
+            // Rebuilt from the restored table on the next search.
+            app.state::<VectorIndex>().clear();
+        }
+        log::info!("Restored the database from {name}");
+        crate::log_err(
+            "emit restored ideas",
+            app.emit(ideas::CHANGED_EVENT, Vec::<String>::new()),
+        );
+        Ok(())
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("restore failed: {e}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    #[test]
+    fn snapshots_verify_and_restore() {
+        let dir = TestDir::new("backup");
+
+        let mut live = Connection::open(dir.join("glimt.db")).unwrap();
+        migrations::run(&mut live, None).unwrap();
+        ideas::create(&live, "kept in the backup", None).unwrap();
+
+        let backups = Backups::new(dir.join("glimt.db"), dir.join("backups"));
+        let first = backups.create(None).unwrap();
+        assert!(first.name.starts_with(PREFIX));
+        ideas::create(&live, "written after the backup", None).unwrap();
+
+        restore_into(&mut live, &backups.path_of(&first.name).unwrap(), None).unwrap();
+        let restored = ideas::list_all(&live).unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].text, "kept in the backup");
+        assert_eq!(
+            ideas::search_fts(&live, "kept").unwrap()[0].id,
+            restored[0].id
+        );
+
+        backups.create(None).unwrap();
+        backups.create(None).unwrap();
+        assert_eq!(backups.list().unwrap().len(), 3);
+        backups.prune(2).unwrap();
+        let left = backups.list().unwrap();
+        assert_eq!(left.len(), 2);
+        assert!(left.iter().all(|backup| backup.name != first.name));
+        assert!(backups.path_of("../glimt.db").is_err());
+
+        std::fs::write(dir.join("backups/glimt-corrupt.db"), b"not a database").unwrap();
+        assert!(restore_into(&mut live, &dir.join("backups/glimt-corrupt.db"), None).is_err());
+    }
+}
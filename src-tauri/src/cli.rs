@@ -0,0 +1,282 @@
+//! Headless subcommands (`glimt add "text"`, `glimt list`, …) that work on the
+//! same `glimt.db` as the desktop app without starting a webview.
+
+use std::io::{IsTerminal, Read};
+use std::path::PathBuf;
+
+use chrono::{Local, TimeZone};
+use rusqlite::{params, Connection};
+
+use crate::db::{self, Db};
+use crate::error::{Error, Result};
+use crate::export;
+use crate::ideas::{self, Idea};
+
+const USAGE: &str = "\
+Usage: glimt <command> [options]
+
+Commands:
+  add [text]           Save an idea (reads stdin when text is omitted or \"-\")
+  list [--archived]    List ideas, newest first
+  search <query>       Full-text search across all ideas
+  export <dir>         Write every idea as a Markdown file into <dir>
+  archive <id>         Archive an idea (an unambiguous id prefix is enough)
+  help                 Show this message
+
+Options:
+  --json               Print list/search results as JSON
+  --                   Read everything after it as text, even words like --json
+
+Run without a command to start the desktop app, or hand these to the
+instance that is already running:
+  --capture            Toggle the capture window
+  --record             Start or stop a quick voice recording
+  --add <text>         Save an idea through the running app";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Add(Option<String>),
+    List { archived: bool, json: bool },
+    Search { query: String, json: bool },
+    Export(PathBuf),
+    Archive(String),
+    Help,
+}
+
+/// Parse `args` (without the program name). `None` means the arguments are
+/// not a CLI invocation and the desktop app should start instead.
+fn parse(args: &[String]) -> Option<std::result::Result<Command, String>> {
+    let (name, rest) = args.split_first()?;
+    let (mut json, mut archived) = (false, false);
+    let mut positional: Vec<&str> = Vec::new();
+    let mut words = rest.iter().map(String::as_str);
+    while let Some(arg) = words.next() {
+        match arg {
+            "--json" => json = true,
+            "--archived" => archived = true,
+            "--" => positional.extend(words.by_ref()),
+            _ => positional.push(arg),
+        }
+    }
+    let joined = (!positional.is_empty()).then(|| positional.join(" "));
+
+    let command = match name.as_str() {
+        "add" => Ok(Command::Add(joined.filter(|t| t != "-"))),
+        "list" => Ok(Command::List { archived, json }),
+        "search" => joined
+            .map(|query| Command::Search { query, json })
+            .ok_or_else(|| "search needs a query".to_owned()),
+        "export" => match positional.as_slice() {
+            [dir] => Ok(Command::Export(PathBuf::from(dir))),
+            _ => Err("export needs exactly one directory".to_owned()),
+        },
+        "archive" => match positional.as_slice() {
+            [id] => Ok(Command::Archive((*id).to_owned())),
+            _ => Err("archive needs exactly one idea id".to_owned()),
+        },
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        _ => return None,
+    };
+    Some(command)
+}
+
+/// Run a CLI invocation and return its exit code, or `None` if `args` should
+/// launch the desktop app.
+pub fn run(args: &[String]) -> Option<i32> {
+    let command = parse(args)?;
+    attach_console();
+
+    let command = match command {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("glimt: {message}\n\n{USAGE}");
+            return Some(2);
+        }
+    };
+    if command == Command::Help {
+        println!("{USAGE}");
+        return Some(0);
+    }
+
+    match open_db().and_then(|db| execute(&db.conn(), command)) {
+        Ok(()) => Some(0),
+        Err(e) => {
+            eprintln!("glimt: {e}");
+            Some(1)
+        }
+    }
+}
+
+fn open_db() -> Result<Db> {
+    let path = db::default_path()
+        .ok_or_else(|| Error::Invalid("could not determine the config directory".into()))?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let passphrase = crate::encryption::Encryption::startup_passphrase(&path);
+    Db::open(&path, passphrase.as_deref()).map_err(|e| Error::Invalid(e.to_string()))
+}
+
+fn execute(conn: &Connection, command: Command) -> Result<()> {
+    match command {
+        Command::Add(text) => {
+            let text = match text {
+                Some(text) => text,
+                None => read_stdin()?,
+            };
+            let idea = ideas::create(conn, text.trim(), Some("cli"))?;
+            println!("{}", idea.id);
+        }
+        Command::List { archived, json } => print_ideas(&ideas::list(conn, archived, None)?, json)?,
+        Command::Search { query, json } => print_ideas(&ideas::search_fts(conn, &query)?, json)?,
+        Command::Export(dir) => {
+            let all: Vec<_> = ideas::list_all(conn)?
+                .into_iter()
+                .filter(|idea| idea.deleted_at.is_none())
+                .collect();
+            for idea in &all {
+                export::write_idea(&dir, idea)?;
+            }
+            println!("Exported {} ideas to {}", all.len(), dir.display());
+        }
+        Command::Archive(prefix) => {
+            let id = resolve_id(conn, &prefix)?;
+            ideas::set_archived(conn, &id, true)?;
+            println!("Archived {id}");
+        }
+        Command::Help => unreachable!("handled before opening the database"),
+    }
+    Ok(())
+}
+
+fn read_stdin() -> Result<String> {
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        return Err(Error::Invalid(
+            "add needs text as an argument or on stdin".into(),
+        ));
+    }
+    let mut text = String::new();
+    stdin.read_to_string(&mut text)?;
+    Ok(text)
+}
+
+/// Accept a full id or any prefix that matches exactly one idea. Ideas in
+/// the trash are not matched.
+fn resolve_id(conn: &Connection, prefix: &str) -> Result<String> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM ideas
+         WHERE substr(id, 1, length(?1)) = ?1 AND deleted_at IS NULL",
+    )?;
+    let matches: Vec<String> = stmt
+        .query_map(params![prefix], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+    match matches.as_slice() {
+        [id] => Ok(id.clone()),
+        [] => Err(Error::NotFound(prefix.to_owned())),
+        _ => Err(Error::Invalid(format!(
+            "\"{prefix}\" matches {} ideas; use a longer prefix",
+            matches.len()
+        ))),
+    }
+}
+
+fn print_ideas(ideas: &[Idea], json: bool) -> Result<()> {
+    if json {
+        let out = serde_json::to_string_pretty(ideas)
+            .map_err(|e| Error::Invalid(format!("could not encode JSON: {e}")))?;
+        println!("{out}");
+        return Ok(());
+    }
+    for idea in ideas {
+        let created = Local
+            .timestamp_millis_opt(idea.created_at)
+            .single()
+            .map(|d| d.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        let summary = idea
+            .title
+            .as_deref()
+            .unwrap_or_else(|| idea.text.lines().next().unwrap_or_default());
+        let summary: String = summary.chars().take(72).collect();
+        let short: String = idea.id.chars().take(8).collect();
+        println!("{short}  {created}  {summary}");
+    }
+    Ok(())
+}
+
+/// Release builds use the Windows GUI subsystem, which starts without a
+/// console; reattach to the invoking terminal so output is visible.
+#[cfg(windows)]
+fn attach_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    // SAFETY: AttachConsole has no preconditions; failure just means no console.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(windows))]
+fn attach_console() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn leaves_app_arguments_alone() {
+        assert!(parse(&args(&[])).is_none());
+        assert!(parse(&args(&["--minimized"])).is_none());
+    }
+
+    #[test]
+    fn parses_subcommands() {
+        assert_eq!(
+            parse(&args(&["add", "buy", "milk"])),
+            Some(Ok(Command::Add(Some("buy milk".into()))))
+        );
+        assert_eq!(parse(&args(&["add", "-"])), Some(Ok(Command::Add(None))));
+        assert_eq!(
+            parse(&args(&["add", "use", "--force", "carefully"])),
+            Some(Ok(Command::Add(Some("use --force carefully".into()))))
+        );
+        assert_eq!(
+            parse(&args(&["search", "--json", "--", "--json", "flag"])),
+            Some(Ok(Command::Search {
+                query: "--json flag".into(),
+                json: true
+            }))
+        );
+        assert_eq!(
+            parse(&args(&["list", "--archived"])),
+            Some(Ok(Command::List {
+                archived: true,
+                json: false
+            }))
+        );
+        assert!(matches!(
+            parse(&args(&["archive"])),
+            Some(Err(message)) if message.contains("archive")
+        ));
+    }
+
+    #[test]
+    fn resolves_unique_id_prefixes() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        crate::migrations::run(&mut conn, None).unwrap();
+        let idea = ideas::create(&conn, "prefix me", None).unwrap();
+
+        assert_eq!(resolve_id(&conn, &idea.id[..6]).unwrap(), idea.id);
+        assert!(matches!(resolve_id(&conn, "zzzz"), Err(Error::NotFound(_))));
+
+        crate::trash::trash(&conn, &idea.id).unwrap();
+        assert!(matches!(
+            resolve_id(&conn, &idea.id),
+            Err(Error::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,193 @@
+//! Clipboard capture: the clip shortcut takes the text just copied (on
+//! Linux, the text just highlighted) and either opens the capture window
+//! with it or saves it straight away, depending on [`ClipMode`].
+//!
+//! A link in the copied text, or the `SourceURL` browsers put in copied
+//! HTML on Windows, is kept as the idea's `source_url`.
+
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::db::Db;
+use crate::settings::SettingsStore;
+use crate::{foreground, ideas, vault};
+
+/// Emitted to the capture window when a clip arrives while it already has
+/// focus, so it takes the clip without waiting for a focus change.
+const CAPTURED_EVENT: &str = "clip-captured";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipMode {
+    /// Open the capture window with the text, to edit before saving.
+    #[default]
+    Prefill,
+    /// Save the text as an idea without showing anything.
+    Save,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Clip {
+    pub text: String,
+    pub source_url: Option<String>,
+}
+
+/// Managed state: the clip waiting for the capture window to pick it up.
+#[derive(Default)]
+pub struct Clips(Mutex<Option<Clip>>);
+
+impl Clips {
+    fn lock(&self) -> MutexGuard<'_, Option<Clip>> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The first http(s) link in `text`, without the brackets or punctuation
+/// around it.
+fn find_url(text: &str) -> Option<String> {
+    text.split_whitespace().find_map(|word| {
+        let word = word
+            .trim_start_matches(['<', '(', '[', '"', '\''])
+            .trim_end_matches(['>', ')', ']', '"', '\'', '.', ',', ';', ':', '!', '?']);
+        let url = url::Url::parse(word).ok()?;
+        matches!(url.scheme(), "http" | "https").then(|| url.to_string())
+    })
+}
+
+/// The page a copy was made from: the `SourceURL:` header of Windows HTML
+/// clipboard data, else a link in the text itself.
+fn source_url(text: &str, html: Option<&str>) -> Option<String> {
+    html.and_then(|html| {
+        html.lines()
+            .take_while(|line| !line.starts_with('<'))
+            .find_map(|line| line.strip_prefix("SourceURL:"))
+            .and_then(find_url)
+    })
+    .or_else(|| find_url(text))
+}
+
+/// Read the clipboard. On Linux the primary selection is tried first, since
+/// it holds whatever is highlighted without needing a copy.
+fn read() -> Option<Clip> {
+    let mut clipboard = match arboard::Clipboard::new() {
+        Ok(clipboard) => clipboard,
+        Err(e) => {
+            log::warn!("Clipboard unavailable: {e}");
+            return None;
+        }
+    };
+
+    #[cfg(target_os = "linux")]
+    let text = {
+        use arboard::{GetExtLinux, LinuxClipboardKind};
+        clipboard
+            .get()
+            .clipboard(LinuxClipboardKind::Primary)
+            .text()
+            .ok()
+            .filter(|text| !text.trim().is_empty())
+            .or_else(|| clipboard.get_text().ok())
+    };
+    #[cfg(not(target_os = "linux"))]
+    let text = clipboard.get_text().ok();
+
+    let text = text?.trim().to_owned();
+    if text.is_empty() {
+        return None;
+    }
+    let html = clipboard.get().html().ok();
+    Some(Clip {
+        source_url: source_url(&text, html.as_deref()),
+        text,
+    })
+}
+
+/// Handle the clip shortcut. With nothing to take, the capture window opens
+/// empty as it would for quick capture.
+pub fn capture(app: &AppHandle) {
+    let Some(clip) = read() else {
+        log::info!("Clip shortcut: the clipboard has no text");
+        crate::toggle_capture_window(app);
+        return;
+    };
+    match app.state::<SettingsStore>().get().clip_mode {
+        ClipMode::Prefill => prefill(app, clip),
+        ClipMode::Save => save(app, &clip),
+    }
+}
+
+fn prefill(app: &AppHandle, clip: Clip) {
+    let Some(window) = app.get_webview_window("capture") else {
+        return;
+    };
+    *app.state::<Clips>().lock() = Some(clip);
+    if window.is_focused().unwrap_or(false) {
+        crate::log_err("emit clip", window.emit(CAPTURED_EVENT, ()));
+        return;
+    }
+    // Showing the window focuses it, which makes it take the clip.
+    app.state::<foreground::Sources>().record("capture");
+    crate::log_err("show capture", window.show());
+    crate::log_err("focus capture", window.set_focus());
+}
+
+fn save(app: &AppHandle, clip: &Clip) {
+    let Some(db) = app.try_state::<Db>() else {
+        log::error!("Clip not saved: the database is not available");
+        return;
+    };
+    let conn = db.conn();
+    let source_app = foreground::detect();
+    match ideas::create_with_url(
+        &conn,
+        &clip.text,
+        source_app.as_deref(),
+        clip.source_url.as_deref(),
+    ) {
+        Ok(idea) => {
+            vault::reexport(app, &conn, &[idea.id]);
+            // Same event the capture window sends, so the dashboard reloads
+            // and embeds the new idea.
+            crate::log_err("emit idea-saved", app.emit("idea-saved", ()));
+        }
+        Err(e) => log::error!("Clip not saved: {e}"),
+    }
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+/// The clip waiting for the capture window, if any. Taking it clears it.
+#[tauri::command]
+pub fn take_clip(clips: State<'_, Clips>) -> Option<Clip> {
+    clips.lock().take()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_source_url() {
+        assert_eq!(
+            source_url("see (https://example.com/a?b=1).", None).as_deref(),
+            Some("https://example.com/a?b=1")
+        );
+        assert_eq!(source_url("ftp://example.com and plain text", None), None);
+
+        let html = "Version:0.9\r\nStartHTML:0000000105\r\nSourceURL:https://news.example/story\r\n<html><body>quoted</body></html>";
+        assert_eq!(
+            source_url("quoted https://other.example", Some(html)).as_deref(),
+            Some("https://news.example/story")
+        );
+        assert_eq!(
+            source_url("quoted", Some("<b>quoted</b>")),
+            None,
+            "markup without headers names no page"
+        );
+    }
+}
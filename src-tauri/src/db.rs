@@ -0,0 +1,127 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rusqlite::{Connection, ErrorCode};
+use serde::Serialize;
+use tauri::State;
+
+use crate::migrations::{self, MigrationError};
+
+/// File name of the idea database inside the app config directory.
+pub const DB_FILE_NAME: &str = "glimt.db";
+
+/// Must match `identifier` in `tauri.conf.json`; Tauri derives the app
+/// config directory from it.
+pub const APP_IDENTIFIER: &str = "com.glimt.app";
+
+/// Where the desktop app keeps `glimt.db`, resolved without a running app so
+/// the CLI opens the same file.
+pub fn default_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_IDENTIFIER).join(DB_FILE_NAME))
+}
+
+/// The single SQLite connection shared by all commands.
+pub struct Db(Mutex<Connection>);
+
+impl Db {
+    /// Open the database and bring its schema up to date. `passphrase`
+    /// unlocks an encrypted database (see `encryption.rs`).
+    pub fn open(path: &Path, passphrase: Option<&str>) -> Result<Self, OpenError> {
+        connect(path, passphrase).map(|conn| Self(Mutex::new(conn)))
+    }
+
+    pub fn conn(&self) -> MutexGuard<'_, Connection> {
+        // A panic while holding the lock leaves SQLite itself consistent,
+        // so recover the guard instead of poisoning every later command.
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Open, configure and migrate a connection to `path`.
+pub(crate) fn connect(path: &Path, passphrase: Option<&str>) -> Result<Connection, OpenError> {
+    let mut conn = Connection::open(path)?;
+    if let Some(passphrase) = passphrase {
+        // Must come before anything reads the file. Plain SQLite builds
+        // ignore the pragma.
+        conn.pragma_update(None, "key", passphrase)?;
+    }
+    // A missing or wrong key only shows up on the first read.
+    if let Err(e) = conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(())) {
+        return Err(match e.sqlite_error_code() {
+            Some(ErrorCode::NotADatabase) => OpenError::Locked,
+            _ => e.into(),
+        });
+    }
+    configure(&conn)?;
+    migrations::run(&mut conn, Some(path))?;
+    Ok(conn)
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum OpenError {
+    #[error("could not open database: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+    #[error(transparent)]
+    Migration(#[from] MigrationError),
+    #[error("the database is encrypted and the passphrase is missing or wrong")]
+    Locked,
+}
+
+/// Why the database is not available. Sent to the webview as-is: a
+/// migration failure keeps its fields, a locked database is `{"locked": true}`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum StartupError {
+    Migration(MigrationError),
+    Locked { locked: bool },
+}
+
+/// Whether the database is open. Managed even when `Db` is not, so every
+/// window can ask why its commands are unavailable. Changes once an
+/// encrypted database is unlocked.
+pub struct DbStatus(Mutex<Result<(), StartupError>>);
+
+impl DbStatus {
+    pub fn new(status: Result<(), StartupError>) -> Self {
+        Self(Mutex::new(status))
+    }
+
+    pub fn set(&self, status: Result<(), StartupError>) {
+        *self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = status;
+    }
+
+    fn get(&self) -> Result<(), StartupError> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+}
+
+#[tauri::command]
+pub fn db_status(status: State<'_, DbStatus>) -> Result<(), StartupError> {
+    status.get()
+}
+
+/// WAL keeps readers (webview commands, background jobs) from blocking the
+/// writer; foreign keys are needed for the `embeddings` cascade.
+fn configure(conn: &Connection) -> rusqlite::Result<()> {
+    conn.pragma_update(None, "foreign_keys", true)?;
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    conn.busy_timeout(std::time::Duration::from_secs(5))?;
+    Ok(())
+}
+
+/// Milliseconds since the Unix epoch, matching `Date.now()` on the JS side.
+pub fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,146 @@
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use tauri::State;
+
+use crate::db::{now_millis, Db};
+use crate::error::{Error, Result};
+use crate::ideas::{self, Idea, IDEA_COLUMNS};
+use crate::vector_index::VectorIndex;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredEmbedding {
+    pub idea_id: String,
+    pub vector: Vec<f32>,
+}
+
+/// Encode a vector as little-endian f32 bytes — the layout `Float32Array`
+/// produced when the webview wrote embeddings directly.
+pub fn encode(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+/// Decode a BLOB, rejecting anything whose length disagrees with `dims`.
+pub fn decode(bytes: &[u8], dims: usize) -> Option<Vec<f32>> {
+    if bytes.len() != dims * 4 {
+        return None;
+    }
+    Some(
+        bytes
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect(),
+    )
+}
+
+// ── Repository ───────────────────────────────────────────
+
+pub fn store(conn: &Connection, idea_id: &str, model: &str, vector: &[f32]) -> Result<()> {
+    if vector.is_empty() {
+        return Err(Error::Invalid("Embedding vector must not be empty".into()));
+    }
+    conn.execute(
+        "INSERT OR REPLACE INTO embeddings (idea_id, model, dims, vector, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            idea_id,
+            model,
+            vector.len() as i64,
+            encode(vector),
+            now_millis()
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn load_all(conn: &Connection, model: &str) -> Result<Vec<StoredEmbedding>> {
+    let mut stmt = conn.prepare("SELECT idea_id, dims, vector FROM embeddings WHERE model = ?1")?;
+    let rows = stmt.query_map(params![model], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, Vec<u8>>(2)?,
+        ))
+    })?;
+
+    let mut out = Vec::new();
+    for row in rows {
+        let (idea_id, dims, bytes) = row?;
+        match decode(&bytes, dims as usize) {
+            Some(vector) => out.push(StoredEmbedding { idea_id, vector }),
+            None => log::warn!("Skipping corrupted embedding for idea {idea_id}"),
+        }
+    }
+    Ok(out)
+}
+
+/// Ideas outside the trash with no vector for `model` — e.g. captured from the
+/// CLI, which has no embedding model of its own.
+pub fn ideas_missing(conn: &Connection, model: &str) -> Result<Vec<Idea>> {
+    ideas::query_ideas(
+        conn,
+        &format!(
+            "SELECT {IDEA_COLUMNS} FROM ideas
+             WHERE deleted_at IS NULL AND NOT EXISTS (
+               SELECT 1 FROM embeddings WHERE embeddings.idea_id = ideas.id AND model = ?1
+             )"
+        ),
+        params![model],
+    )
+}
+
+pub fn delete_for_idea(conn: &Connection, idea_id: &str) -> Result<()> {
+    conn.execute(
+        "DELETE FROM embeddings WHERE idea_id = ?1",
+        params![idea_id],
+    )?;
+    Ok(())
+}
+
+pub fn delete_all(conn: &Connection) -> Result<()> {
+    conn.execute("DELETE FROM embeddings", [])?;
+    Ok(())
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+#[tauri::command]
+pub fn store_embedding(
+    db: State<'_, Db>,
+    index: State<'_, VectorIndex>,
+    idea_id: String,
+    model: String,
+    vector: Vec<f32>,
+) -> Result<()> {
+    store(&db.conn(), &idea_id, &model, &vector)?;
+    index.upsert(&model, &idea_id, &vector);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_all_embeddings(db: State<'_, Db>, model: String) -> Result<Vec<StoredEmbedding>> {
+    load_all(&db.conn(), &model)
+}
+
+#[tauri::command]
+pub fn get_ideas_missing_embeddings(db: State<'_, Db>, model: String) -> Result<Vec<Idea>> {
+    ideas_missing(&db.conn(), &model)
+}
+
+#[tauri::command]
+pub fn delete_embedding(
+    db: State<'_, Db>,
+    index: State<'_, VectorIndex>,
+    idea_id: String,
+) -> Result<()> {
+    delete_for_idea(&db.conn(), &idea_id)?;
+    index.remove(&idea_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn delete_all_embeddings(db: State<'_, Db>, index: State<'_, VectorIndex>) -> Result<()> {
+    delete_all(&db.conn())?;
+    index.clear();
+    Ok(())
+}
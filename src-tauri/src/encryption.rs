@@ -0,0 +1,427 @@
+//! Optional at-rest encryption of `glimt.db` with SQLCipher, in builds with
+//! the `encryption` feature.
+//!
+//! The key is derived from a passphrase. It can be remembered in the OS
+//! keyring (Keychain, Credential Manager, Secret Service), in which case
+//! Glimt and the CLI unlock on their own. Otherwise the windows ask for it
+//! before reading anything, and `unlock_database` opens the database late.
+//! Scheduled backups are encrypted with the same key. Changing the
+//! passphrase re-encrypts the backups and pre-migration snapshots too, so
+//! no plain copy of an encrypted database is left behind.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+#[cfg(feature = "encryption")]
+use crate::backup::Backups;
+use crate::db::{Db, DbStatus, OpenError, StartupError};
+use crate::error::{Error, Result};
+
+/// Emitted to every window once the database has been unlocked.
+pub const UNLOCKED_EVENT: &str = "database-unlocked";
+/// Every SQLite file starts with this; SQLCipher files look random.
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+#[cfg(feature = "encryption")]
+const KEYRING_USER: &str = "database-passphrase";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptionStatus {
+    /// Whether this build can encrypt at all.
+    pub available: bool,
+    pub encrypted: bool,
+    pub unlocked: bool,
+    /// The passphrase is kept in the OS keyring.
+    pub remembered: bool,
+}
+
+/// Whether the file at `path` is not plain SQLite. A missing or empty file
+/// is not encrypted: it becomes a plain database when opened.
+pub fn is_encrypted(path: &Path) -> bool {
+    let mut header = [0u8; 16];
+    match std::fs::File::open(path).and_then(|mut file| file.read_exact(&mut header)) {
+        Ok(()) => &header != SQLITE_HEADER,
+        Err(_) => false,
+    }
+}
+
+#[cfg(feature = "encryption")]
+fn keyring_entry() -> keyring::Result<keyring::Entry> {
+    keyring::Entry::new(crate::db::APP_IDENTIFIER, KEYRING_USER)
+}
+
+/// The passphrase saved in the OS keyring, if any.
+#[cfg(feature = "encryption")]
+pub fn remembered_passphrase() -> Option<String> {
+    keyring_entry().and_then(|entry| entry.get_password()).ok()
+}
+
+#[cfg(not(feature = "encryption"))]
+pub fn remembered_passphrase() -> Option<String> {
+    None
+}
+
+/// Save `passphrase` to the OS keyring, or forget the saved one.
+#[cfg(feature = "encryption")]
+fn keep_in_keyring(passphrase: Option<&str>) -> Result<()> {
+    let keyring_err = |e: keyring::Error| Error::Invalid(format!("OS keyring: {e}"));
+    let entry = keyring_entry().map_err(keyring_err)?;
+    match passphrase {
+        Some(passphrase) => entry.set_password(passphrase).map_err(keyring_err),
+        None => match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(keyring_err(e)),
+        },
+    }
+}
+
+#[cfg(not(feature = "encryption"))]
+fn keep_in_keyring(_passphrase: Option<&str>) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(not(feature = "encryption"))]
+fn unavailable() -> Error {
+    Error::Invalid("this build has no database encryption (encryption feature)".into())
+}
+
+/// Copy the open database into a new file at `dest` encrypted with
+/// `passphrase`, or into a plain one without it.
+#[cfg(feature = "encryption")]
+fn export_to(conn: &rusqlite::Connection, dest: &Path, passphrase: Option<&str>) -> Result<()> {
+    let _ = std::fs::remove_file(dest);
+    conn.execute(
+        "ATTACH DATABASE ?1 AS rekeyed KEY ?2",
+        rusqlite::params![dest.to_string_lossy(), passphrase.unwrap_or_default()],
+    )?;
+    let exported = (|| {
+        conn.query_row("SELECT sqlcipher_export('rekeyed')", [], |_| Ok(()))?;
+        // `sqlcipher_export` leaves the schema version behind.
+        let version = crate::migrations::schema_version(conn)?;
+        conn.pragma_update(
+            Some(rusqlite::DatabaseName::Attached("rekeyed")),
+            "user_version",
+            version,
+        )
+    })();
+    conn.execute_batch("DETACH DATABASE rekeyed")?;
+    exported?;
+    Ok(())
+}
+
+/// Re-encrypt the database at `path` with `next`, or decrypt it. The
+/// connection is swapped for one on the new file.
+#[cfg(feature = "encryption")]
+fn rekey(
+    conn: &mut rusqlite::Connection,
+    path: &Path,
+    previous: Option<&str>,
+    next: Option<&str>,
+) -> Result<()> {
+    let rekeyed = path.with_extension("rekey");
+    if let Err(e) = export_to(conn, &rekeyed, next) {
+        let _ = std::fs::remove_file(&rekeyed);
+        return Err(e);
+    }
+    // Closing the connection folds the WAL back in. The old WAL must not
+    // outlive its database, or SQLite would replay it onto the new file.
+    *conn = rusqlite::Connection::open_in_memory()?;
+    for suffix in ["-wal", "-shm"] {
+        let mut side = path.as_os_str().to_owned();
+        side.push(suffix);
+        let _ = std::fs::remove_file(side);
+    }
+    let (key, result) = match std::fs::rename(&rekeyed, path) {
+        Ok(()) => (next, Ok(())),
+        Err(e) => {
+            let _ = std::fs::remove_file(&rekeyed);
+            (previous, Err(e.into()))
+        }
+    };
+    *conn = crate::db::connect(path, key).map_err(|e| Error::Invalid(e.to_string()))?;
+    result
+}
+
+/// Re-encrypt a copy of the database, a backup or a pre-migration snapshot,
+/// with `next`, or decrypt it. Encrypted copies are opened with `previous`.
+/// A copy that does not open could never be restored with the new
+/// passphrase, so it is deleted rather than left behind.
+#[cfg(feature = "encryption")]
+fn rekey_copy(path: &Path, previous: Option<&str>, next: Option<&str>) {
+    let from = if is_encrypted(path) { previous } else { None };
+    if from == next {
+        return;
+    }
+    let rekeyed = path.with_extension("rekey");
+    let result = (|| {
+        let conn = rusqlite::Connection::open(path)?;
+        if let Some(from) = from {
+            conn.pragma_update(None, "key", from)?;
+        }
+        export_to(&conn, &rekeyed, next)?;
+        drop(conn);
+        std::fs::rename(&rekeyed, path)?;
+        Ok::<_, Error>(())
+    })();
+    if let Err(e) = result {
+        let _ = std::fs::remove_file(&rekeyed);
+        log::warn!(
+            "Deleting {}, which could not be rekeyed: {e}",
+            path.display()
+        );
+        crate::log_err("delete database copy", std::fs::remove_file(path));
+    }
+}
+
+/// Managed state: the database file and the passphrase it was opened with.
+pub struct Encryption {
+    db_path: PathBuf,
+    passphrase: Mutex<Option<String>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl Encryption {
+    pub fn new(db_path: PathBuf, passphrase: Option<String>) -> Self {
+        Self {
+            db_path,
+            passphrase: Mutex::new(passphrase),
+        }
+    }
+
+    /// Passphrase for opening `glimt.db` or one of its backups.
+    pub fn passphrase(&self) -> Option<String> {
+        lock(&self.passphrase).clone()
+    }
+
+    /// What to open the database with at startup: the remembered
+    /// passphrase, but only if the file actually needs one.
+    pub fn startup_passphrase(db_path: &Path) -> Option<String> {
+        if is_encrypted(db_path) {
+            remembered_passphrase()
+        } else {
+            None
+        }
+    }
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+#[tauri::command]
+pub fn encryption_status(app: AppHandle, encryption: State<'_, Encryption>) -> EncryptionStatus {
+    EncryptionStatus {
+        available: cfg!(feature = "encryption"),
+        encrypted: is_encrypted(&encryption.db_path),
+        unlocked: app.try_state::<Db>().is_some(),
+        remembered: remembered_passphrase().is_some(),
+    }
+}
+
+/// Open the encrypted database with `passphrase`, optionally saving it to
+/// the OS keyring. Every window is told through [`UNLOCKED_EVENT`].
+#[tauri::command]
+pub async fn unlock_database(app: AppHandle, passphrase: String, remember: bool) -> Result<()> {
+    tauri::async_runtime::spawn_blocking(move || {
+        if app.try_state::<Db>().is_some() {
+            return Ok(());
+        }
+        let encryption = app.state::<Encryption>();
+        let opened = Db::open(&encryption.db_path, Some(&passphrase));
+        let status = app.state::<DbStatus>();
+        let db = match opened {
+            Ok(db) => db,
+            Err(OpenError::Locked) => {
+                return Err(Error::Invalid("wrong passphrase".into()));
+            }
+            Err(OpenError::Migration(e)) => {
+                // Unlocked, but unusable: let the windows show why.
+                log::error!("{e}");
+                status.set(Err(StartupError::Migration(e.clone())));
+                crate::log_err("emit unlock", app.emit(UNLOCKED_EVENT, ()));
+                return Err(Error::Invalid(e.to_string()));
+            }
+            Err(e) => return Err(Error::Invalid(e.to_string())),
+        };
+        app.manage(db);
+        *lock(&encryption.passphrase) = Some(passphrase.clone());
+        status.set(Ok(()));
+        if remember {
+            crate::log_err("remember passphrase", keep_in_keyring(Some(&passphrase)));
+        }
+        log::info!("Database unlocked");
+        crate::log_err("emit unlock", app.emit(UNLOCKED_EVENT, ()));
+        Ok(())
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("unlock failed: {e}")))?
+}
+
+/// Encrypt the database with `passphrase`, change its passphrase, or
+/// decrypt it when `passphrase` is empty. `current` must match the
+/// passphrase it is open with.
+#[tauri::command]
+pub async fn set_database_passphrase(
+    app: AppHandle,
+    current: Option<String>,
+    passphrase: Option<String>,
+    remember: bool,
+) -> Result<()> {
+    tauri::async_runtime::spawn_blocking(move || {
+        change_passphrase(&app, current, passphrase, remember)
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("changing the passphrase failed: {e}")))?
+}
+
+#[cfg(feature = "encryption")]
+fn change_passphrase(
+    app: &AppHandle,
+    current: Option<String>,
+    passphrase: Option<String>,
+    remember: bool,
+) -> Result<()> {
+    let db = app
+        .try_state::<Db>()
+        .ok_or_else(|| Error::Invalid("unlock the database first".into()))?;
+    let encryption = app.state::<Encryption>();
+    let previous = encryption.passphrase();
+    if previous.is_some() && previous != current {
+        return Err(Error::Invalid("the current passphrase is wrong".into()));
+    }
+    let next = passphrase.filter(|p| !p.is_empty());
+    {
+        let mut conn = db.conn();
+        rekey(
+            &mut conn,
+            &encryption.db_path,
+            previous.as_deref(),
+            next.as_deref(),
+        )?;
+        *lock(&encryption.passphrase) = next.clone();
+        // Still holding the connection, so no restore runs meanwhile.
+        let mut copies = crate::migrations::snapshots(&encryption.db_path);
+        copies.extend(app.state::<Backups>().files()?);
+        for copy in copies {
+            rekey_copy(&copy, previous.as_deref(), next.as_deref());
+        }
+    }
+    crate::log_err(
+        "update remembered passphrase",
+        keep_in_keyring(next.as_deref().filter(|_| remember)),
+    );
+    log::info!(
+        "Database {}",
+        if next.is_some() {
+            "encrypted"
+        } else {
+            "decrypted"
+        }
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "encryption"))]
+fn change_passphrase(
+    _app: &AppHandle,
+    _current: Option<String>,
+    _passphrase: Option<String>,
+    _remember: bool,
+) -> Result<()> {
+    Err(unavailable())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    #[test]
+    fn recognises_plain_sqlite_files() {
+        let dir = TestDir::new("encryption");
+
+        let plain = dir.join("plain.db");
+        Db::open(&plain, None).unwrap();
+        assert!(!is_encrypted(&plain));
+        assert!(!is_encrypted(&dir.join("missing.db")));
+
+        let scrambled = dir.join("scrambled.db");
+        std::fs::write(&scrambled, [0x5a; 4096]).unwrap();
+        assert!(is_encrypted(&scrambled));
+        assert!(matches!(
+            Db::open(&scrambled, Some("guess")),
+            Err(OpenError::Locked)
+        ));
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn encrypts_rekeys_and_decrypts_in_place() {
+        let dir = TestDir::new("rekey");
+        let path = dir.join("glimt.db");
+        let mut conn = crate::db::connect(&path, None).unwrap();
+        let idea = crate::ideas::create(&conn, "secret plan", None).unwrap();
+
+        rekey(&mut conn, &path, None, Some("first")).unwrap();
+        assert!(is_encrypted(&path));
+        assert!(matches!(Db::open(&path, None), Err(OpenError::Locked)));
+        assert!(crate::ideas::get(&conn, &idea.id).unwrap().is_some());
+
+        rekey(&mut conn, &path, Some("first"), Some("second")).unwrap();
+        assert!(matches!(
+            Db::open(&path, Some("first")),
+            Err(OpenError::Locked)
+        ));
+        let reopened = crate::db::connect(&path, Some("second")).unwrap();
+        assert_eq!(
+            crate::migrations::schema_version(&reopened).unwrap(),
+            crate::migrations::CURRENT_SCHEMA_VERSION
+        );
+        assert_eq!(
+            crate::ideas::search_fts(&reopened, "secret").unwrap().len(),
+            1
+        );
+        drop(reopened);
+
+        rekey(&mut conn, &path, Some("second"), None).unwrap();
+        assert!(!is_encrypted(&path));
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn rekeys_copies_or_deletes_them() {
+        let dir = TestDir::new("copies");
+        let copy = |name: &str, key: Option<&str>| {
+            let path = dir.join(name);
+            let conn = rusqlite::Connection::open(&path).unwrap();
+            if let Some(key) = key {
+                conn.pragma_update(None, "key", key).unwrap();
+            }
+            conn.execute_batch("CREATE TABLE t (x)").unwrap();
+            path
+        };
+        let opens_with = |path: &Path, key: &str| {
+            let conn = rusqlite::Connection::open(path).unwrap();
+            conn.pragma_update(None, "key", key).unwrap();
+            conn.query_row("SELECT COUNT(*) FROM t", [], |_| Ok(()))
+                .is_ok()
+        };
+        let plain = copy("plain.db", None);
+        let keyed = copy("keyed.db", Some("first"));
+        let stray = copy("stray.db", Some("forgotten"));
+
+        for path in [&plain, &keyed, &stray] {
+            rekey_copy(path, Some("first"), Some("second"));
+        }
+        assert!(opens_with(&plain, "second"));
+        assert!(opens_with(&keyed, "second"));
+        assert!(!stray.exists(), "could not be opened, so it went");
+    }
+}
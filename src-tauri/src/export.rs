@@ -0,0 +1,325 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, SecondsFormat, TimeZone, Utc};
+
+use crate::ideas::Idea;
+
+/// ISO-8601 in UTC with milliseconds, identical to JS `Date#toISOString()`.
+fn iso(millis: i64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(millis)
+        .unwrap_or_default()
+        .to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// A double-quoted YAML string. Backslashes, quotes and line breaks are
+/// escaped so the value stays on its line.
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+/// Markdown with YAML frontmatter. Must stay byte-compatible with
+/// `generateMarkdown` in `src/lib/export.ts`, which writes the same files.
+pub fn generate_markdown(idea: &Idea) -> String {
+    let mut lines = vec![
+        "---".to_owned(),
+        format!("id: \"{}\"", idea.id),
+        format!("created: \"{}\"", iso(idea.created_at)),
+        format!("updated: \"{}\"", iso(idea.updated_at)),
+    ];
+    if let Some(title) = idea.title.as_deref().filter(|t| !t.is_empty()) {
+        lines.push(format!("title: {}", quote(title)));
+    }
+    if !idea.tags.is_empty() {
+        lines.push("tags:".to_owned());
+        lines.extend(idea.tags.iter().map(|tag| format!("  - {}", quote(tag))));
+    }
+    lines.extend([
+        "---".to_owned(),
+        String::new(),
+        idea.text.clone(),
+        String::new(),
+    ]);
+    lines.join("\n")
+}
+
+/// Starts the attachment links appended to an exported idea. Everything
+/// from here on is generated, so [`parse_markdown`] drops it again.
+const ATTACHMENTS_MARKER: &str = "<!-- glimt:attachments -->";
+
+/// Append links to attachments exported next to the file, given as
+/// `(name, relative path, is image)`. Images are embedded.
+pub fn with_attachments(markdown: String, links: &[(String, String, bool)]) -> String {
+    if links.is_empty() {
+        return markdown;
+    }
+    let mut out = markdown.trim_end_matches('\n').to_owned();
+    out.push_str("\n\n");
+    out.push_str(ATTACHMENTS_MARKER);
+    for (name, path, image) in links {
+        let bang = if *image { "!" } else { "" };
+        out.push_str(&format!("\n{bang}[{}]({path})", name.replace(']', "\\]")));
+    }
+    out.push('\n');
+    out
+}
+
+/// `YYYY-MM-DD_HHmmss_<id>.md` in local time, matching `generateFilename`.
+pub fn generate_filename(idea: &Idea) -> String {
+    let created = Local
+        .timestamp_millis_opt(idea.created_at)
+        .single()
+        .unwrap_or_default();
+    format!("{}_{}.md", created.format("%Y-%m-%d_%H%M%S"), idea.id)
+}
+
+/// What [`parse_markdown`] reads back from a file, ours or anyone else's.
+#[derive(Debug, Default, PartialEq)]
+pub struct Note {
+    pub id: Option<String>,
+    pub created_at: Option<i64>,
+    /// The idea's `updated_at` when the file was written.
+    pub updated_at: Option<i64>,
+    pub title: Option<String>,
+    /// From a `tags` list or a comma-separated `tags` value.
+    pub tags: Vec<String>,
+    pub text: String,
+}
+
+/// Undo the quoting `generate_markdown` applies, and accept the plain or
+/// single-quoted values other editors may write when they touch the file.
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    if let Some(inner) = value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        unescape(inner)
+    } else if let Some(inner) = value
+        .strip_prefix('\'')
+        .and_then(|value| value.strip_suffix('\''))
+    {
+        inner.replace("''", "'")
+    } else {
+        value.to_owned()
+    }
+}
+
+/// Undo [`quote`]'s escapes. Other backslashes are kept as written.
+fn unescape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        let escaped = match (c, chars.peek()) {
+            ('\\', Some('n')) => '\n',
+            ('\\', Some(&next @ ('\\' | '"'))) => next,
+            _ => {
+                out.push(c);
+                continue;
+            }
+        };
+        chars.next();
+        out.push(escaped);
+    }
+    out
+}
+
+fn millis(value: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|at| at.timestamp_millis())
+}
+
+/// Parse Markdown with optional YAML frontmatter, the inverse of
+/// [`generate_markdown`]. Only flat `key: value` lines and the `tags` list
+/// are read; anything else an editor adds is ignored. Without frontmatter
+/// the whole file is the text.
+pub fn parse_markdown(content: &str) -> Note {
+    let content = content.replace("\r\n", "\n");
+    let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
+    let split = content
+        .strip_prefix("---\n")
+        .and_then(|rest| match rest.find("\n---\n") {
+            Some(end) => Some((&rest[..end], &rest[end + 5..])),
+            None => rest
+                .strip_suffix("\n---")
+                .map(|frontmatter| (frontmatter, "")),
+        });
+    let Some((frontmatter, body)) = split else {
+        return Note {
+            text: content.trim_end_matches('\n').to_owned(),
+            ..Note::default()
+        };
+    };
+
+    let mut note = Note::default();
+    let mut in_tags = false;
+    for line in frontmatter.lines() {
+        let item = line.trim_start().strip_prefix("- ");
+        if let Some(tag) = item.filter(|_| in_tags) {
+            note.tags
+                .push(unquote(tag).trim_start_matches('#').to_owned());
+            continue;
+        }
+        in_tags = false;
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = unquote(value);
+        match key.trim() {
+            "id" => note.id = Some(value).filter(|id| !id.is_empty()),
+            "created" => note.created_at = millis(&value),
+            "updated" => note.updated_at = millis(&value),
+            "title" => note.title = Some(value).filter(|title| !title.is_empty()),
+            "tags" if value.is_empty() => in_tags = true,
+            "tags" => {
+                let inline = value.trim_start_matches('[').trim_end_matches(']');
+                note.tags.extend(
+                    inline
+                        .split(',')
+                        .map(|tag| unquote(tag).trim_start_matches('#').to_owned())
+                        .filter(|tag| !tag.is_empty()),
+                );
+            }
+            _ => {}
+        }
+    }
+    let body = body.strip_prefix('\n').unwrap_or(body);
+    let body = body.find(ATTACHMENTS_MARKER).map_or(body, |at| &body[..at]);
+    note.text = body.trim_end_matches('\n').to_owned();
+    note
+}
+
+/// Write one idea into `dir`, creating the directory if needed.
+pub fn write_idea(dir: &Path, idea: &Idea) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(generate_filename(idea));
+    std::fs::write(&path, generate_markdown(idea))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn idea() -> Idea {
+        crate::ideas::test_idea("test-id-123", "Test idea text")
+    }
+
+    #[test]
+    fn matches_the_frontend_format() {
+        let md = generate_markdown(&Idea {
+            title: Some("He said \"hello\"".into()),
+            ..idea()
+        });
+        assert_eq!(
+            md,
+            "---\nid: \"test-id-123\"\ncreated: \"2023-11-14T22:13:20.000Z\"\nupdated: \"2023-11-14T22:13:20.000Z\"\ntitle: \"He said \\\"hello\\\"\"\n---\n\nTest idea text\n"
+        );
+    }
+
+    #[test]
+    fn escapes_line_breaks_and_backslashes() {
+        let original = Idea {
+            title: Some("C:\\new \"dir\"\nsecond line".into()),
+            tags: vec!["a\\\"b".into()],
+            updated_at: 1_700_000_000_500,
+            ..idea()
+        };
+        let md = generate_markdown(&original);
+        assert!(md.contains("title: \"C:\\\\new \\\"dir\\\"\\nsecond line\"\n"));
+        let parsed = parse_markdown(&md);
+        assert_eq!(parsed.title, original.title);
+        assert_eq!(parsed.tags, original.tags);
+        assert_eq!(parsed.updated_at, Some(original.updated_at));
+        assert_eq!(parsed.text, original.text);
+        assert_eq!(
+            parse_markdown("---\ntitle: \"C:\\temp\"\n---\nx")
+                .title
+                .as_deref(),
+            Some("C:\\temp")
+        );
+    }
+
+    #[test]
+    fn omits_title_when_absent() {
+        assert!(!generate_markdown(&idea()).contains("title:"));
+    }
+
+    #[test]
+    fn parses_its_own_output_and_editor_variants() {
+        let original = Idea {
+            title: Some("Say \"hi\"".into()),
+            tags: vec!["garden".into(), "to-do".into()],
+            text: "Body\n\nwith --- inside".into(),
+            updated_at: 1_700_000_000_500,
+            ..idea()
+        };
+        assert_eq!(
+            parse_markdown(&generate_markdown(&original)),
+            Note {
+                id: Some("test-id-123".into()),
+                created_at: Some(1_700_000_000_000),
+                updated_at: Some(1_700_000_000_500),
+                title: original.title.clone(),
+                tags: vec!["garden".into(), "to-do".into()],
+                text: original.text.clone(),
+            }
+        );
+
+        let edited = parse_markdown(
+            "---\r\nid: abc\r\ntags:\r\n  - idea\r\ntitle: 'It''s'\r\n---\r\nNew body\r\n\r\n",
+        );
+        assert_eq!(edited.id.as_deref(), Some("abc"));
+        assert_eq!(edited.title.as_deref(), Some("It's"));
+        assert_eq!(edited.updated_at, None);
+        assert_eq!(edited.tags, ["idea"]);
+        assert_eq!(edited.text, "New body");
+        assert_eq!(
+            parse_markdown("---\ntags: [a, \"#b\"]\n---\nx").tags,
+            ["a", "b"]
+        );
+
+        let plain = parse_markdown("# Just a note\n\nNo frontmatter.\n");
+        assert_eq!(plain.id, None);
+        assert_eq!(plain.text, "# Just a note\n\nNo frontmatter.");
+    }
+
+    #[test]
+    fn attachment_links_are_not_read_back_as_text() {
+        let links = [
+            (
+                "shot.png".to_owned(),
+                "attachments/ab12-shot.png".to_owned(),
+                true,
+            ),
+            (
+                "notes [v2].pdf".to_owned(),
+                "attachments/cd34-notes--v2-.pdf".to_owned(),
+                false,
+            ),
+        ];
+        let md = with_attachments(generate_markdown(&idea()), &links);
+        assert!(md.ends_with(
+            "Test idea text\n\n<!-- glimt:attachments -->\n\
+             ![shot.png](attachments/ab12-shot.png)\n\
+             [notes [v2\\].pdf](attachments/cd34-notes--v2-.pdf)\n"
+        ));
+        assert_eq!(parse_markdown(&md).text, "Test idea text");
+        assert_eq!(with_attachments("same".into(), &[]), "same");
+    }
+
+    #[test]
+    fn filename_uses_local_time_and_id() {
+        let created = Local.with_ymd_and_hms(2024, 1, 5, 3, 2, 1).unwrap();
+        let name = generate_filename(&Idea {
+            id: "test".into(),
+            created_at: created.timestamp_millis(),
+            ..idea()
+        });
+        assert_eq!(name, "2024-01-05_030201_test.md");
+    }
+}
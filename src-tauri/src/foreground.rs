@@ -0,0 +1,187 @@
+//! The application in the foreground when a capture starts, saved as the
+//! idea's `source_app`.
+//!
+//! It has to be read before the capture window is shown, since that takes
+//! focus. On X11 and the Wayland compositors that expose it (Hyprland,
+//! Sway) this is the window class; on macOS and Windows the app or process
+//! name. Other Wayland sessions don't say, and ideas get no source.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// Ideas captured while Glimt itself is in front have no outside source.
+const OWN_NAME: &str = "glimt";
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The last double-quoted value on a line, as printed by `xprop` and
+/// `lsappinfo`.
+#[cfg(any(target_os = "linux", target_os = "macos", test))]
+fn last_quoted(output: &str) -> Option<String> {
+    output.rsplit('"').nth(1).map(str::to_owned)
+}
+
+/// The focused window's `app_id`, or X11 class for XWayland windows, in
+/// `swaymsg -t get_tree` output.
+#[cfg(any(target_os = "linux", test))]
+fn sway_focused(node: &serde_json::Value) -> Option<String> {
+    if node["focused"].as_bool() == Some(true) {
+        return node["app_id"]
+            .as_str()
+            .or_else(|| node["window_properties"]["class"].as_str())
+            .map(str::to_owned);
+    }
+    ["nodes", "floating_nodes"]
+        .iter()
+        .filter_map(|key| node[key].as_array())
+        .flatten()
+        .find_map(sway_focused)
+}
+
+#[cfg(target_os = "linux")]
+fn detect_platform() -> Option<String> {
+    let has = |var: &str| std::env::var_os(var).is_some();
+    if has("HYPRLAND_INSTANCE_SIGNATURE") {
+        let json: serde_json::Value =
+            serde_json::from_str(&run("hyprctl", &["activewindow", "-j"])?).ok()?;
+        return json["class"].as_str().map(str::to_owned);
+    }
+    if has("SWAYSOCK") {
+        let tree = serde_json::from_str(&run("swaymsg", &["-t", "get_tree"])?).ok()?;
+        return sway_focused(&tree);
+    }
+    // XWayland only knows about X windows and would name a stale one.
+    if has("WAYLAND_DISPLAY") || !has("DISPLAY") {
+        return None;
+    }
+    let root = run("xprop", &["-root", "_NET_ACTIVE_WINDOW"])?;
+    let id = root.split('#').nth(1)?.split(',').next()?.trim().to_owned();
+    if id == "0x0" {
+        return None;
+    }
+    last_quoted(&run("xprop", &["-id", &id, "WM_CLASS"])?)
+}
+
+#[cfg(target_os = "macos")]
+fn detect_platform() -> Option<String> {
+    let front = run("lsappinfo", &["front"])?;
+    last_quoted(&run("lsappinfo", &["info", "-only", "name", front.trim()])?)
+}
+
+#[cfg(windows)]
+fn detect_platform() -> Option<String> {
+    use windows_sys::Win32::Foundation::CloseHandle;
+    use windows_sys::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        GetForegroundWindow, GetWindowThreadProcessId,
+    };
+
+    // SAFETY: plain Win32 calls; the buffer outlives the call that fills it
+    // and the process handle is closed before returning.
+    unsafe {
+        let window = GetForegroundWindow();
+        if window.is_null() {
+            return None;
+        }
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(window, &mut pid);
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process.is_null() {
+            return None;
+        }
+        let mut buffer = [0u16; 1024];
+        let mut len = buffer.len() as u32;
+        let ok =
+            QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, buffer.as_mut_ptr(), &mut len);
+        CloseHandle(process);
+        if ok == 0 {
+            return None;
+        }
+        let path = String::from_utf16_lossy(&buffer[..len as usize]);
+        std::path::Path::new(&path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn detect_platform() -> Option<String> {
+    None
+}
+
+/// The foreground application's name, unless it is Glimt.
+pub fn detect() -> Option<String> {
+    detect_platform()
+        .map(|name| name.trim().to_owned())
+        .filter(|name| !name.is_empty() && !name.eq_ignore_ascii_case(OWN_NAME))
+}
+
+/// Managed state: the app each capture window was last summoned from, by
+/// window label, for `create_idea` to fill in `source_app`.
+#[derive(Default)]
+pub struct Sources(Mutex<HashMap<String, String>>);
+
+impl Sources {
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, String>> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Remember the foreground app for `label`. Call before showing the
+    /// window.
+    pub fn record(&self, label: &str) {
+        match detect() {
+            Some(app) => self.lock().insert(label.to_owned(), app),
+            None => self.lock().remove(label),
+        };
+    }
+
+    pub fn get(&self, label: &str) -> Option<String> {
+        self.lock().get(label).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tool_output() {
+        assert_eq!(
+            last_quoted("WM_CLASS(STRING) = \"navigator\", \"firefox\"\n").as_deref(),
+            Some("firefox")
+        );
+        assert_eq!(
+            last_quoted("\"LSDisplayName\"=\"Safari\"\n").as_deref(),
+            Some("Safari")
+        );
+        assert_eq!(last_quoted("WM_CLASS:  not found.\n"), None);
+
+        let tree = serde_json::json!({
+            "focused": false,
+            "nodes": [{
+                "focused": false,
+                "nodes": [
+                    { "focused": false, "app_id": "foot", "nodes": [] },
+                    { "focused": true, "app_id": null, "window_properties": { "class": "Code" } }
+                ]
+            }]
+        });
+        assert_eq!(sway_focused(&tree).as_deref(), Some("Code"));
+    }
+}
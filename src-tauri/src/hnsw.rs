@@ -0,0 +1,569 @@
+//! Hierarchical navigable small world graph (Malkov & Yashunin) for cosine
+//! similarity over embedding vectors.
+//!
+//! Vectors are normalised on insert so similarity is a plain dot product.
+//! Removal only tombstones a node; the graph keeps routing through it until
+//! enough tombstones pile up that [`Hnsw::needs_compaction`] asks for a rebuild.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::io::{self, Read, Write};
+
+const MAGIC: &[u8; 8] = b"GLIMTANN";
+const FORMAT_VERSION: u32 = 1;
+const NO_ENTRY: u32 = u32::MAX;
+/// Highest layer a node can reach.
+const MAX_LEVEL: usize = 16;
+/// Bytes every node takes in a written index besides its vector: id length,
+/// deleted flag, layer count and the length of layer 0.
+const MIN_NODE_BYTES: u64 = 13;
+
+/// Max neighbours per node on upper layers; layer 0 keeps twice as many.
+const M: usize = 16;
+const EF_CONSTRUCTION: usize = 100;
+const MIN_EF_SEARCH: usize = 64;
+
+#[derive(Clone, Copy, PartialEq)]
+struct Scored {
+    score: f32,
+    node: u32,
+}
+
+impl Eq for Scored {}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then(self.node.cmp(&other.node))
+    }
+}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub struct Hnsw {
+    dims: usize,
+    ids: Vec<String>,
+    /// Node-major, `dims` floats per node, unit length.
+    vectors: Vec<f32>,
+    /// `links[node][layer]` — neighbour lists from layer 0 up to the node's level.
+    links: Vec<Vec<Vec<u32>>>,
+    deleted: Vec<bool>,
+    lookup: HashMap<String, u32>,
+    entry: u32,
+    rng: u64,
+}
+
+impl Hnsw {
+    pub fn new(dims: usize) -> Self {
+        Self {
+            dims,
+            ids: Vec::new(),
+            vectors: Vec::new(),
+            links: Vec::new(),
+            deleted: Vec::new(),
+            lookup: HashMap::new(),
+            entry: NO_ENTRY,
+            rng: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    pub fn dims(&self) -> usize {
+        self.dims
+    }
+
+    /// Live (non-tombstoned) entries.
+    pub fn len(&self) -> usize {
+        self.lookup.len()
+    }
+
+    /// True once tombstones outnumber live entries; rebuilding then restores
+    /// search quality and frees memory.
+    pub fn needs_compaction(&self) -> bool {
+        self.ids.len() > 64 && self.ids.len() - self.lookup.len() > self.lookup.len()
+    }
+
+    /// Insert `vector` for `id`, replacing any previous vector for it.
+    pub fn insert(&mut self, id: &str, vector: &[f32]) {
+        debug_assert_eq!(vector.len(), self.dims);
+        self.remove(id);
+
+        let node = self.ids.len() as u32;
+        let level = self.random_level();
+        self.ids.push(id.to_owned());
+        self.vectors.extend(normalized(vector));
+        self.links.push(vec![Vec::new(); level + 1]);
+        self.deleted.push(false);
+        self.lookup.insert(id.to_owned(), node);
+
+        if self.entry == NO_ENTRY {
+            self.entry = node;
+            return;
+        }
+
+        let query = self.vector(node).to_vec();
+        let top = self.level_of(self.entry);
+        let mut entry = self.entry;
+        for layer in (level + 1..=top).rev() {
+            entry = self.greedy(&query, entry, layer);
+        }
+
+        let mut entries = vec![entry];
+        for layer in (0..=level.min(top)).rev() {
+            let candidates = self.search_layer(&query, &entries, EF_CONSTRUCTION, layer);
+            let neighbours = self.select(&candidates, max_links(layer));
+            for &neighbour in &neighbours {
+                self.connect(neighbour, node, layer);
+            }
+            self.links[node as usize][layer] = neighbours;
+            entries = candidates.iter().map(|c| c.node).collect();
+        }
+
+        if level > top {
+            self.entry = node;
+        }
+    }
+
+    pub fn remove(&mut self, id: &str) -> bool {
+        match self.lookup.remove(id) {
+            Some(node) => {
+                self.deleted[node as usize] = true;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Up to `k` live entries most similar to `query`, best first.
+    pub fn search(&self, query: &[f32], k: usize) -> Vec<(String, f32)> {
+        if self.entry == NO_ENTRY || k == 0 || query.len() != self.dims {
+            return Vec::new();
+        }
+        let query = normalized(query);
+        let mut entry = self.entry;
+        for layer in (1..=self.level_of(self.entry)).rev() {
+            entry = self.greedy(&query, entry, layer);
+        }
+        // Tombstones take up slots in the beam, so widen it by their share.
+        let tombstones = self.ids.len() - self.lookup.len();
+        let ef = (k + tombstones.min(k * 4)).max(MIN_EF_SEARCH);
+        self.search_layer(&query, &[entry], ef, 0)
+            .into_iter()
+            .filter(|c| !self.deleted[c.node as usize])
+            .take(k)
+            .map(|c| (self.ids[c.node as usize].clone(), c.score))
+            .collect()
+    }
+
+    /// Live entries, for rebuilding a compacted graph.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &[f32])> {
+        self.lookup
+            .iter()
+            .map(|(id, &node)| (id.as_str(), self.vector(node)))
+    }
+
+    fn vector(&self, node: u32) -> &[f32] {
+        let start = node as usize * self.dims;
+        &self.vectors[start..start + self.dims]
+    }
+
+    fn level_of(&self, node: u32) -> usize {
+        self.links[node as usize].len() - 1
+    }
+
+    fn similarity(&self, query: &[f32], node: u32) -> f32 {
+        dot(query, self.vector(node))
+    }
+
+    /// Exponentially distributed level with mean 1/ln(M), from a xorshift
+    /// stream so builds are reproducible.
+    fn random_level(&mut self) -> usize {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        let uniform = ((self.rng >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        ((-uniform.ln() / (M as f64).ln()) as usize).min(MAX_LEVEL)
+    }
+
+    fn greedy(&self, query: &[f32], mut node: u32, layer: usize) -> u32 {
+        let mut best = self.similarity(query, node);
+        loop {
+            let mut improved = false;
+            for &neighbour in &self.links[node as usize][layer] {
+                let score = self.similarity(query, neighbour);
+                if score > best {
+                    best = score;
+                    node = neighbour;
+                    improved = true;
+                }
+            }
+            if !improved {
+                return node;
+            }
+        }
+    }
+
+    /// Beam search on one layer; returns up to `ef` nodes, best first.
+    fn search_layer(&self, query: &[f32], entries: &[u32], ef: usize, layer: usize) -> Vec<Scored> {
+        let mut visited: HashSet<u32> = entries.iter().copied().collect();
+        let mut candidates = BinaryHeap::new();
+        let mut found = BinaryHeap::new();
+        for &node in entries {
+            let scored = Scored {
+                score: self.similarity(query, node),
+                node,
+            };
+            candidates.push(scored);
+            found.push(Reverse(scored));
+        }
+        while found.len() > ef {
+            found.pop();
+        }
+
+        while let Some(current) = candidates.pop() {
+            let worst = found.peek().map_or(f32::MIN, |w| w.0.score);
+            if current.score < worst && found.len() >= ef {
+                break;
+            }
+            for &neighbour in &self.links[current.node as usize][layer] {
+                if !visited.insert(neighbour) {
+                    continue;
+                }
+                let score = self.similarity(query, neighbour);
+                let worst = found.peek().map_or(f32::MIN, |w| w.0.score);
+                if found.len() < ef || score > worst {
+                    let scored = Scored {
+                        score,
+                        node: neighbour,
+                    };
+                    candidates.push(scored);
+                    found.push(Reverse(scored));
+                    if found.len() > ef {
+                        found.pop();
+                    }
+                }
+            }
+        }
+
+        let mut out: Vec<Scored> = found.into_iter().map(|r| r.0).collect();
+        out.sort_unstable_by(|a, b| b.cmp(a));
+        out
+    }
+
+    /// Neighbour selection heuristic: keep a candidate only if it is closer
+    /// to the query than to every neighbour already kept, which spreads links
+    /// across clusters instead of packing them into the nearest one.
+    fn select(&self, candidates: &[Scored], max: usize) -> Vec<u32> {
+        let mut kept: Vec<u32> = Vec::with_capacity(max);
+        for candidate in candidates {
+            if kept.len() == max {
+                break;
+            }
+            let vector = self.vector(candidate.node);
+            let diverse = kept
+                .iter()
+                .all(|&k| dot(vector, self.vector(k)) < candidate.score);
+            if diverse {
+                kept.push(candidate.node);
+            }
+        }
+        // Top up with the closest leftovers so sparse regions stay connected.
+        for candidate in candidates {
+            if kept.len() == max {
+                break;
+            }
+            if !kept.contains(&candidate.node) {
+                kept.push(candidate.node);
+            }
+        }
+        kept
+    }
+
+    fn connect(&mut self, from: u32, to: u32, layer: usize) {
+        let max = max_links(layer);
+        self.links[from as usize][layer].push(to);
+        if self.links[from as usize][layer].len() <= max {
+            return;
+        }
+        let base = self.vector(from).to_vec();
+        let mut scored: Vec<Scored> = self.links[from as usize][layer]
+            .iter()
+            .map(|&node| Scored {
+                score: self.similarity(&base, node),
+                node,
+            })
+            .collect();
+        scored.sort_unstable_by(|a, b| b.cmp(a));
+        self.links[from as usize][layer] = self.select(&scored, max);
+    }
+
+    // ── Persistence ──────────────────────────────────────
+
+    pub fn write_to(&self, out: &mut impl Write) -> io::Result<()> {
+        out.write_all(MAGIC)?;
+        write_u32(out, FORMAT_VERSION)?;
+        write_u32(out, self.dims as u32)?;
+        write_u32(out, self.ids.len() as u32)?;
+        write_u32(out, self.entry)?;
+        out.write_all(&self.rng.to_le_bytes())?;
+        for (node, id) in self.ids.iter().enumerate() {
+            write_u32(out, id.len() as u32)?;
+            out.write_all(id.as_bytes())?;
+            out.write_all(&[self.deleted[node] as u8])?;
+            write_u32(out, self.links[node].len() as u32)?;
+            for layer in &self.links[node] {
+                write_u32(out, layer.len() as u32)?;
+                for &neighbour in layer {
+                    write_u32(out, neighbour)?;
+                }
+            }
+        }
+        for value in &self.vectors {
+            out.write_all(&value.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Read an index written by [`Self::write_to`] from `input`, which holds
+    /// `len` bytes. A corrupt index is an error here, not a panic in a later
+    /// search or an allocation sized by a bad header.
+    pub fn read_from(input: &mut impl Read, len: u64) -> io::Result<Self> {
+        let mut magic = [0u8; 8];
+        input.read_exact(&mut magic)?;
+        if &magic != MAGIC || read_u32(input)? != FORMAT_VERSION {
+            return Err(invalid("not a Glimt vector index"));
+        }
+        let dims = read_u32(input)? as usize;
+        let count = read_u32(input)? as usize;
+        let entry = read_u32(input)?;
+        let mut rng = [0u8; 8];
+        input.read_exact(&mut rng)?;
+        let fits = (count as u64)
+            .checked_mul(dims as u64 * 4 + MIN_NODE_BYTES)
+            .is_some_and(|bytes| bytes <= len);
+        if !fits {
+            return Err(invalid("more nodes than the file holds"));
+        }
+
+        let mut index = Self::new(dims);
+        index.entry = entry;
+        index.rng = u64::from_le_bytes(rng);
+        for node in 0..count {
+            let id_len = read_u32(input)?;
+            if u64::from(id_len) > len {
+                return Err(invalid("idea id longer than the file"));
+            }
+            let mut id = vec![0u8; id_len as usize];
+            input.read_exact(&mut id)?;
+            let id = String::from_utf8(id).map_err(|_| invalid("idea id is not UTF-8"))?;
+            let mut deleted = [0u8; 1];
+            input.read_exact(&mut deleted)?;
+            let layers = read_u32(input)? as usize;
+            if layers == 0 || layers > MAX_LEVEL + 1 {
+                return Err(invalid("bad layer count"));
+            }
+            let mut links = Vec::with_capacity(layers);
+            for _ in 0..layers {
+                let n = read_u32(input)? as usize;
+                let layer = (0..n)
+                    .map(|_| read_u32(input))
+                    .collect::<io::Result<Vec<u32>>>()?;
+                if layer.iter().any(|&l| l as usize >= count) {
+                    return Err(invalid("neighbour out of range"));
+                }
+                links.push(layer);
+            }
+            if deleted[0] == 0 {
+                index.lookup.insert(id.clone(), node as u32);
+            }
+            index.ids.push(id);
+            index.deleted.push(deleted[0] != 0);
+            index.links.push(links);
+        }
+        // Searches index `links[neighbour][layer]` and start from the entry
+        // point's top layer, so both must exist.
+        for links in &index.links {
+            for (layer, neighbours) in links.iter().enumerate() {
+                if neighbours
+                    .iter()
+                    .any(|&n| index.links[n as usize].len() <= layer)
+                {
+                    return Err(invalid("neighbour missing a layer"));
+                }
+            }
+        }
+        let top = index.links.iter().map(Vec::len).max().unwrap_or(0);
+        if (entry == NO_ENTRY) != (count == 0)
+            || (entry != NO_ENTRY && index.links.get(entry as usize).map(Vec::len) != Some(top))
+        {
+            return Err(invalid("bad entry point"));
+        }
+
+        let mut bytes = vec![0u8; count * dims * 4];
+        input.read_exact(&mut bytes)?;
+        index.vectors = bytes
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+        Ok(index)
+    }
+}
+
+fn max_links(layer: usize) -> usize {
+    if layer == 0 {
+        M * 2
+    } else {
+        M
+    }
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn normalized(vector: &[f32]) -> Vec<f32> {
+    let norm = dot(vector, vector).sqrt();
+    if norm == 0.0 {
+        return vector.to_vec();
+    }
+    vector.iter().map(|v| v / norm).collect()
+}
+
+fn write_u32(out: &mut impl Write, value: u32) -> io::Result<()> {
+    out.write_all(&value.to_le_bytes())
+}
+
+fn read_u32(input: &mut impl Read) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    input.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random vectors so failures are reproducible.
+    fn vectors(count: usize, dims: usize) -> Vec<Vec<f32>> {
+        let mut state = 42u64;
+        (0..count)
+            .map(|_| {
+                (0..dims)
+                    .map(|_| {
+                        state = state
+                            .wrapping_mul(6364136223846793005)
+                            .wrapping_add(1442695040888963407);
+                        ((state >> 33) as f32 / u32::MAX as f32) - 0.25
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn exact_top(data: &[Vec<f32>], query: &[f32], k: usize) -> Vec<String> {
+        let query = normalized(query);
+        let mut scored: Vec<(usize, f32)> = data
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i, dot(&query, &normalized(v))))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.iter().take(k).map(|(i, _)| i.to_string()).collect()
+    }
+
+    fn build(data: &[Vec<f32>]) -> Hnsw {
+        let mut index = Hnsw::new(data[0].len());
+        for (i, v) in data.iter().enumerate() {
+            index.insert(&i.to_string(), v);
+        }
+        index
+    }
+
+    #[test]
+    fn recall_matches_exact_search() {
+        let data = vectors(1000, 32);
+        let index = build(&data);
+        let queries = vectors(1030, 32).split_off(1000);
+
+        let mut hits = 0;
+        for query in &queries {
+            let expected = exact_top(&data, query, 10);
+            let found: Vec<String> = index.search(query, 10).into_iter().map(|r| r.0).collect();
+            hits += expected.iter().filter(|id| found.contains(id)).count();
+        }
+        let recall = hits as f64 / (queries.len() * 10) as f64;
+        assert!(recall > 0.95, "recall was {recall}");
+    }
+
+    #[test]
+    fn removed_and_replaced_entries() {
+        let data = vectors(300, 16);
+        let mut index = build(&data);
+
+        assert_eq!(index.search(&data[7], 1)[0].0, "7");
+        assert!(index.remove("7"));
+        assert!(index.search(&data[7], 5).iter().all(|(id, _)| id != "7"));
+
+        index.insert("8", &data[7]);
+        let (id, score) = &index.search(&data[7], 1)[0];
+        assert_eq!(id, "8");
+        assert!((score - 1.0).abs() < 1e-5);
+        assert_eq!(index.len(), 299);
+    }
+
+    #[test]
+    fn round_trips_through_bytes() {
+        let data = vectors(200, 8);
+        let mut index = build(&data);
+        index.remove("3");
+
+        let mut bytes = Vec::new();
+        index.write_to(&mut bytes).unwrap();
+        let restored = Hnsw::read_from(&mut bytes.as_slice(), bytes.len() as u64).unwrap();
+
+        assert_eq!(restored.len(), 199);
+        assert_eq!(restored.search(&data[50], 5), index.search(&data[50], 5));
+        assert!(Hnsw::read_from(&mut &bytes[..20], 20).is_err());
+    }
+
+    #[test]
+    fn rejects_corrupt_indexes() {
+        let read = |bytes: &[u8]| Hnsw::read_from(&mut &bytes[..], bytes.len() as u64);
+        let write = |index: &Hnsw| {
+            let mut bytes = Vec::new();
+            index.write_to(&mut bytes).unwrap();
+            bytes
+        };
+        let mut index = Hnsw::new(2);
+        index.ids = vec!["a".into(), "b".into()];
+        index.vectors = vec![1.0, 0.0, 0.0, 1.0];
+        index.deleted = vec![false, false];
+        // "a" reaches layer 1, "b" only layer 0.
+        index.links = vec![vec![vec![1], Vec::new()], vec![vec![0]]];
+        index.entry = 0;
+        let bytes = write(&index);
+        assert!(read(&bytes).is_ok());
+
+        let mut huge = bytes.clone();
+        huge[16..20].copy_from_slice(&1_000_000_000u32.to_le_bytes());
+        assert!(read(&huge).is_err(), "more nodes than bytes");
+
+        index.links[0][1].push(1);
+        assert!(read(&write(&index)).is_err(), "\"b\" has no layer 1");
+
+        index.links[0][1].clear();
+        index.entry = 1;
+        assert!(
+            read(&write(&index)).is_err(),
+            "the entry point is not on top"
+        );
+    }
+}
@@ -0,0 +1,601 @@
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Deserializer, Serialize};
+use tauri::{State, Window};
+
+use crate::app_lock::AppLock;
+use crate::attachments::Attachment;
+use crate::db::{now_millis, Db};
+use crate::error::{Error, Result};
+use crate::foreground::Sources;
+use crate::recordings::Recording;
+use crate::{revisions, search, tags, trash};
+
+/// Emitted to every window when ideas change outside the webview's own
+/// commands (vault sync, imports), so lists reload and embeddings backfill.
+pub const CHANGED_EVENT: &str = "ideas-changed";
+
+/// Column list shared by every query that returns full idea rows. Tags and
+/// attachments come last as JSON arrays, then the recording as an object.
+pub(crate) const IDEA_COLUMNS: &str =
+    "ideas.id, ideas.created_at, ideas.updated_at, ideas.text, ideas.title, ideas.archived, ideas.source_app, ideas.markdown_path, ideas.source_url, ideas.deleted_at, ideas.remind_at,
+     (SELECT json_group_array(t.name) FROM idea_tags it JOIN tags t ON t.id = it.tag_id WHERE it.idea_id = ideas.id),
+     (SELECT json_group_array(json_object('id', a.id, 'name', a.name, 'mime', a.mime, 'size', a.size,
+        'thumbnail', json(iif(a.thumbnail, 'true', 'false')), 'createdAt', a.created_at) ORDER BY a.created_at)
+      FROM attachments a WHERE a.idea_id = ideas.id),
+     (SELECT json_object('size', r.size, 'durationMs', r.duration_ms, 'createdAt', r.created_at)
+      FROM recordings r WHERE r.idea_id = ideas.id)";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Idea {
+    pub id: String,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub text: String,
+    pub title: Option<String>,
+    pub archived: bool,
+    pub source_app: Option<String>,
+    pub markdown_path: Option<String>,
+    /// Page a clipboard capture was copied from, when known.
+    #[serde(default)]
+    pub source_url: Option<String>,
+    /// When the idea was moved to the trash; `None` outside it.
+    #[serde(default)]
+    pub deleted_at: Option<i64>,
+    /// When to show a reminder notification for the idea.
+    #[serde(default)]
+    pub remind_at: Option<i64>,
+    /// Sorted tag names. Absent from archives written before tags existed.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Oldest first. Archives carry the list but not the files.
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    /// Kept audio of a voice idea. Archives carry neither this nor the file.
+    #[serde(default, skip_deserializing)]
+    pub recording: Option<Recording>,
+}
+
+impl Idea {
+    pub(crate) fn from_row(row: &Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            created_at: row.get(1)?,
+            updated_at: row.get(2)?,
+            text: row.get(3)?,
+            title: row.get(4)?,
+            archived: row.get::<_, i64>(5)? == 1,
+            source_app: row.get(6)?,
+            markdown_path: row.get(7)?,
+            source_url: row.get(8)?,
+            deleted_at: row.get(9)?,
+            remind_at: row.get(10)?,
+            tags: {
+                let mut tags: Vec<String> =
+                    serde_json::from_str(&row.get::<_, String>(11)?).unwrap_or_default();
+                tags.sort();
+                tags
+            },
+            attachments: serde_json::from_str(&row.get::<_, String>(12)?).unwrap_or_default(),
+            recording: row
+                .get::<_, Option<String>>(13)?
+                .and_then(|json| serde_json::from_str(&json).ok()),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceAppCount {
+    pub source_app: String,
+    pub count: i64,
+}
+
+/// Partial update. A missing field is left untouched; `title: null` clears it.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdeaUpdate {
+    pub text: Option<String>,
+    #[serde(default, deserialize_with = "present")]
+    pub title: Option<Option<String>>,
+}
+
+/// Distinguish an explicit `null` from an absent key.
+fn present<'de, D, T>(deserializer: D) -> std::result::Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+pub(crate) fn query_ideas(
+    conn: &Connection,
+    sql: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<Idea>> {
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt.query_map(params, Idea::from_row)?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+// ── Repository ───────────────────────────────────────────
+
+pub fn create(conn: &Connection, text: &str, source_app: Option<&str>) -> Result<Idea> {
+    create_with_url(conn, text, source_app, None)
+}
+
+/// [`create`], also recording the page the text came from.
+pub fn create_with_url(
+    conn: &Connection,
+    text: &str,
+    source_app: Option<&str>,
+    source_url: Option<&str>,
+) -> Result<Idea> {
+    if text.trim().is_empty() {
+        return Err(Error::Invalid("Idea text must not be empty".into()));
+    }
+    let now = now_millis();
+    let idea = Idea {
+        id: uuid::Uuid::new_v4().to_string(),
+        created_at: now,
+        updated_at: now,
+        text: text.to_owned(),
+        title: None,
+        archived: false,
+        source_app: source_app.map(str::to_owned),
+        markdown_path: None,
+        source_url: source_url.map(str::to_owned),
+        deleted_at: None,
+        remind_at: None,
+        tags: tags::extract(text),
+        attachments: Vec::new(),
+        recording: None,
+    };
+    insert(conn, &idea)?;
+    Ok(idea)
+}
+
+/// Insert a complete idea as-is, timestamps included. Tags not found in
+/// the text are linked as if added by hand.
+pub fn insert(conn: &Connection, idea: &Idea) -> Result<()> {
+    conn.execute(
+        "INSERT INTO ideas (id, created_at, updated_at, text, title, archived, source_app, markdown_path, source_url, deleted_at, remind_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        params![
+            idea.id,
+            idea.created_at,
+            idea.updated_at,
+            idea.text,
+            idea.title,
+            idea.archived as i64,
+            idea.source_app,
+            idea.markdown_path,
+            idea.source_url,
+            idea.deleted_at,
+            idea.remind_at
+        ],
+    )?;
+    tags::sync(conn, &idea.id, &idea.text)?;
+    tags::link(conn, &idea.id, &idea.tags)?;
+    Ok(())
+}
+
+/// Overwrite an existing idea with `idea`, as from an archive, in place so
+/// its attachments, recording and revisions stay. The version replaced is
+/// kept as a revision, and hand-made tags are added to the ones it had.
+/// The local `markdown_path` is kept, since it points into this machine's
+/// vault.
+pub fn overwrite(conn: &Connection, idea: &Idea) -> Result<()> {
+    revisions::record(
+        conn,
+        &idea.id,
+        &IdeaUpdate {
+            text: Some(idea.text.clone()),
+            title: Some(idea.title.clone()),
+        },
+    )?;
+    let changed = conn.execute(
+        "UPDATE ideas SET created_at = ?2, updated_at = ?3, text = ?4, title = ?5,
+           archived = ?6, source_app = ?7, source_url = ?8, deleted_at = ?9, remind_at = ?10
+         WHERE id = ?1",
+        params![
+            idea.id,
+            idea.created_at,
+            idea.updated_at,
+            idea.text,
+            idea.title,
+            idea.archived as i64,
+            idea.source_app,
+            idea.source_url,
+            idea.deleted_at,
+            idea.remind_at
+        ],
+    )?;
+    if changed == 0 {
+        return Err(Error::NotFound(idea.id.clone()));
+    }
+    tags::sync(conn, &idea.id, &idea.text)?;
+    tags::link(conn, &idea.id, &idea.tags)?;
+    Ok(())
+}
+
+/// Whether `id` is a hyphenated UUID, the only ids Glimt mints. Ids read
+/// from files or archives end up in file names, so anything else is
+/// replaced with a new one.
+pub(crate) fn is_valid_id(id: &str) -> bool {
+    id.len() == 36 && uuid::Uuid::try_parse(id).is_ok()
+}
+
+pub fn exists(conn: &Connection, id: &str) -> Result<bool> {
+    Ok(conn
+        .query_row("SELECT 1 FROM ideas WHERE id = ?1", params![id], |_| Ok(()))
+        .optional()?
+        .is_some())
+}
+
+/// Ideas in the archive or out of it, optionally only those captured from
+/// `source_app`. Ideas in the trash are in neither.
+pub fn list(conn: &Connection, archived: bool, source_app: Option<&str>) -> Result<Vec<Idea>> {
+    query_ideas(
+        conn,
+        &format!(
+            "SELECT {IDEA_COLUMNS} FROM ideas
+             WHERE archived = ?1 AND deleted_at IS NULL AND (?2 IS NULL OR source_app = ?2)
+             ORDER BY created_at DESC"
+        ),
+        params![archived as i64, source_app],
+    )
+}
+
+/// Every app ideas were captured from, with how many, busiest first.
+pub fn source_apps(conn: &Connection, archived: Option<bool>) -> Result<Vec<SourceAppCount>> {
+    let mut stmt = conn.prepare(
+        "SELECT source_app, COUNT(*) FROM ideas
+         WHERE source_app IS NOT NULL AND deleted_at IS NULL AND (?1 IS NULL OR archived = ?1)
+         GROUP BY source_app
+         ORDER BY COUNT(*) DESC, source_app",
+    )?;
+    let rows = stmt.query_map(params![archived.map(i64::from)], |row| {
+        Ok(SourceAppCount {
+            source_app: row.get(0)?,
+            count: row.get(1)?,
+        })
+    })?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+/// Every idea, those in the trash included.
+pub fn list_all(conn: &Connection) -> Result<Vec<Idea>> {
+    query_ideas(
+        conn,
+        &format!("SELECT {IDEA_COLUMNS} FROM ideas ORDER BY created_at DESC"),
+        [],
+    )
+}
+
+pub fn get(conn: &Connection, id: &str) -> Result<Option<Idea>> {
+    Ok(conn
+        .query_row(
+            &format!("SELECT {IDEA_COLUMNS} FROM ideas WHERE id = ?1"),
+            params![id],
+            Idea::from_row,
+        )
+        .optional()?)
+}
+
+/// The ideas among `ids` that are not in the trash.
+pub fn get_many(conn: &Connection, ids: &[String]) -> Result<Vec<Idea>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let placeholders = vec!["?"; ids.len()].join(", ");
+    query_ideas(
+        conn,
+        &format!(
+            "SELECT {IDEA_COLUMNS} FROM ideas WHERE id IN ({placeholders}) AND deleted_at IS NULL"
+        ),
+        params_from_iter(ids),
+    )
+}
+
+/// Apply `updates`, keeping the version replaced as a revision. Both are
+/// written together or not at all. Ideas in the trash are not found.
+pub fn update(conn: &Connection, id: &str, updates: &IdeaUpdate) -> Result<()> {
+    if matches!(&updates.text, Some(text) if text.trim().is_empty()) {
+        return Err(Error::Invalid("Idea text must not be empty".into()));
+    }
+    // A caller's transaction, such as a tag rename's, already covers both.
+    if !conn.is_autocommit() {
+        return write_update(conn, id, updates);
+    }
+    let tx = conn.unchecked_transaction()?;
+    write_update(&tx, id, updates)?;
+    tx.commit()?;
+    Ok(())
+}
+
+fn write_update(conn: &Connection, id: &str, updates: &IdeaUpdate) -> Result<()> {
+    revisions::record(conn, id, updates)?;
+    let changed = conn.execute(
+        "UPDATE ideas SET
+           updated_at = ?1,
+           text = COALESCE(?2, text),
+           title = CASE WHEN ?3 THEN ?4 ELSE title END
+         WHERE id = ?5 AND deleted_at IS NULL",
+        params![
+            now_millis(),
+            updates.text,
+            updates.title.is_some(),
+            updates.title.clone().flatten(),
+            id
+        ],
+    )?;
+    if changed == 0 {
+        return Err(Error::NotFound(id.to_owned()));
+    }
+    if let Some(text) = &updates.text {
+        tags::sync(conn, id, text)?;
+    }
+    Ok(())
+}
+
+/// Ideas in the trash are not found.
+pub fn set_archived(conn: &Connection, id: &str, archived: bool) -> Result<()> {
+    let changed = conn.execute(
+        "UPDATE ideas SET archived = ?1, updated_at = ?2 WHERE id = ?3 AND deleted_at IS NULL",
+        params![archived as i64, now_millis(), id],
+    )?;
+    if changed == 0 {
+        return Err(Error::NotFound(id.to_owned()));
+    }
+    Ok(())
+}
+
+/// Record where the idea's Markdown file lives. Not an edit, so
+/// `updated_at` is left alone.
+pub fn set_markdown_path(conn: &Connection, id: &str, path: Option<&str>) -> Result<()> {
+    conn.execute(
+        "UPDATE ideas SET markdown_path = ?1 WHERE id = ?2",
+        params![path, id],
+    )?;
+    Ok(())
+}
+
+pub fn find_by_markdown_path(conn: &Connection, path: &str) -> Result<Option<Idea>> {
+    Ok(conn
+        .query_row(
+            &format!("SELECT {IDEA_COLUMNS} FROM ideas WHERE markdown_path = ?1"),
+            params![path],
+            Idea::from_row,
+        )
+        .optional()?)
+}
+
+/// An idea with the given id and text, dated 14 November 2023, with every
+/// optional field empty.
+#[cfg(test)]
+pub(crate) fn test_idea(id: &str, text: &str) -> Idea {
+    Idea {
+        id: id.into(),
+        created_at: 1_700_000_000_000,
+        updated_at: 1_700_000_000_000,
+        text: text.into(),
+        title: None,
+        archived: false,
+        source_app: None,
+        markdown_path: None,
+        source_url: None,
+        deleted_at: None,
+        remind_at: None,
+        tags: Vec::new(),
+        attachments: Vec::new(),
+        recording: None,
+    }
+}
+
+/// Delete the idea for good. Ideas the user deletes go to the trash first
+/// and are purged from there; see [`crate::trash`].
+#[cfg(test)]
+pub fn delete(conn: &Connection, id: &str) -> Result<()> {
+    conn.execute("DELETE FROM ideas WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+/// Full-text matches for free text, best first. The text is read as words
+/// that must all match, so it cannot be an FTS5 syntax error.
+pub fn search_fts(conn: &Connection, query: &str) -> Result<Vec<Idea>> {
+    let Some(query) = search::fts_query(query) else {
+        return Ok(Vec::new());
+    };
+    query_ideas(
+        conn,
+        &format!(
+            "SELECT {IDEA_COLUMNS} FROM fts_ideas
+             JOIN ideas ON ideas.id = fts_ideas.id
+             WHERE fts_ideas MATCH ?1 AND ideas.deleted_at IS NULL
+             ORDER BY rank"
+        ),
+        params![query],
+    )
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+/// Without an explicit `source_app`, ideas from the capture windows get the
+/// app that was in front when the window was summoned.
+#[tauri::command]
+pub fn create_idea(
+    window: Window,
+    db: State<'_, Db>,
+    sources: State<'_, Sources>,
+    text: String,
+    source_app: Option<String>,
+    source_url: Option<String>,
+) -> Result<Idea> {
+    let source_app = source_app.or_else(|| sources.get(window.label()));
+    create_with_url(
+        &db.conn(),
+        &text,
+        source_app.as_deref(),
+        source_url.as_deref(),
+    )
+}
+
+#[tauri::command]
+pub fn get_ideas(
+    db: State<'_, Db>,
+    archived: Option<bool>,
+    source_app: Option<String>,
+) -> Result<Vec<Idea>> {
+    list(&db.conn(), archived.unwrap_or(false), source_app.as_deref())
+}
+
+#[tauri::command]
+pub fn get_all_ideas(db: State<'_, Db>) -> Result<Vec<Idea>> {
+    list_all(&db.conn())
+}
+
+/// `None` for ideas in the trash, which are listed by `list_trash`.
+#[tauri::command]
+pub fn get_idea(db: State<'_, Db>, id: String) -> Result<Option<Idea>> {
+    Ok(get(&db.conn(), &id)?.filter(|idea| idea.deleted_at.is_none()))
+}
+
+#[tauri::command]
+pub fn get_ideas_by_ids(db: State<'_, Db>, ids: Vec<String>) -> Result<Vec<Idea>> {
+    get_many(&db.conn(), &ids)
+}
+
+/// While the app is locked, only ideas captured since are editable, so
+/// capture can add a title to the idea it just saved.
+#[tauri::command]
+pub fn update_idea(
+    db: State<'_, Db>,
+    lock: State<'_, AppLock>,
+    id: String,
+    updates: IdeaUpdate,
+) -> Result<()> {
+    let conn = db.conn();
+    if let Some(since) = lock.locked_since() {
+        let created_at = get(&conn, &id)?.map(|idea| idea.created_at);
+        if !created_at.is_some_and(|created_at| created_at >= since) {
+            return Err(Error::Invalid("Glimt is locked".into()));
+        }
+    }
+    update(&conn, &id, &updates)
+}
+
+#[tauri::command]
+pub fn archive_idea(db: State<'_, Db>, id: String, archived: Option<bool>) -> Result<()> {
+    set_archived(&db.conn(), &id, archived.unwrap_or(true))
+}
+
+/// Move the idea to the trash. It keeps its embeddings, attachments and
+/// recording until the trash is emptied.
+#[tauri::command]
+pub fn delete_idea(db: State<'_, Db>, id: String) -> Result<()> {
+    trash::trash(&db.conn(), &id)
+}
+
+#[tauri::command]
+pub fn list_source_apps(db: State<'_, Db>, archived: Option<bool>) -> Result<Vec<SourceAppCount>> {
+    source_apps(&db.conn(), archived)
+}
+
+#[tauri::command]
+pub fn search_ideas_fts(db: State<'_, Db>, query: String) -> Result<Vec<Idea>> {
+    search_fts(&db.conn(), &query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conn() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        crate::migrations::run(&mut conn, None).unwrap();
+        conn
+    }
+
+    #[test]
+    fn update_leaves_missing_fields_and_clears_explicit_null() {
+        let conn = conn();
+        let idea = create(&conn, "first draft", None).unwrap();
+
+        let titled: IdeaUpdate = serde_json::from_str(r#"{"title":"Draft"}"#).unwrap();
+        update(&conn, &idea.id, &titled).unwrap();
+        let stored = get(&conn, &idea.id).unwrap().unwrap();
+        assert_eq!(stored.text, "first draft");
+        assert_eq!(stored.title.as_deref(), Some("Draft"));
+
+        let cleared: IdeaUpdate = serde_json::from_str(r#"{"title":null}"#).unwrap();
+        update(&conn, &idea.id, &cleared).unwrap();
+        assert_eq!(get(&conn, &idea.id).unwrap().unwrap().title, None);
+    }
+
+    #[test]
+    fn failed_update_keeps_no_revision() {
+        let conn = conn();
+        let idea = create(&conn, "before", None).unwrap();
+        conn.execute_batch(
+            "CREATE TEMP TRIGGER refuse BEFORE UPDATE OF text ON ideas
+             BEGIN SELECT RAISE(ABORT, 'refused'); END",
+        )
+        .unwrap();
+        let edit = IdeaUpdate {
+            text: Some("after".into()),
+            title: None,
+        };
+        assert!(update(&conn, &idea.id, &edit).is_err());
+        assert!(revisions::list(&conn, &idea.id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn archive_moves_idea_between_lists() {
+        let conn = conn();
+        let idea = create(&conn, "archive me", Some("terminal")).unwrap();
+        set_archived(&conn, &idea.id, true).unwrap();
+
+        assert!(list(&conn, false, None).unwrap().is_empty());
+        let archived = list(&conn, true, Some("terminal")).unwrap();
+        assert_eq!(archived.len(), 1);
+        assert!(archived[0].archived);
+        assert_eq!(archived[0].source_app.as_deref(), Some("terminal"));
+        assert!(list(&conn, true, Some("browser")).unwrap().is_empty());
+        let counts = source_apps(&conn, Some(true)).unwrap();
+        assert_eq!(
+            (counts[0].source_app.as_str(), counts[0].count),
+            ("terminal", 1)
+        );
+    }
+
+    #[test]
+    fn fts_follows_updates_and_deletes() {
+        let conn = conn();
+        let idea = create(&conn, "meeting notes from standup", None).unwrap();
+        assert_eq!(search_fts(&conn, "standup").unwrap().len(), 1);
+
+        let edit = IdeaUpdate {
+            text: Some("retro notes".into()),
+            title: None,
+        };
+        update(&conn, &idea.id, &edit).unwrap();
+        assert!(search_fts(&conn, "standup").unwrap().is_empty());
+        assert_eq!(search_fts(&conn, "retro").unwrap().len(), 1);
+        assert!(search_fts(&conn, "to-do \"retro").unwrap().is_empty());
+
+        delete(&conn, &idea.id).unwrap();
+        assert!(search_fts(&conn, "retro").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_empty_text_and_unknown_ids() {
+        let conn = conn();
+        assert!(matches!(create(&conn, "  ", None), Err(Error::Invalid(_))));
+        assert!(matches!(
+            set_archived(&conn, "missing", true),
+            Err(Error::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,321 @@
+//! Bulk import of an existing Markdown folder, such as an Obsidian vault or
+//! an Apple Notes export, into ideas.
+//!
+//! Files written by Glimt's own export keep their id and timestamps, so
+//! importing the same folder twice, or one exported on another machine,
+//! skips ideas that are already here. Other notes get a new id, as do notes
+//! whose `id` is not a UUID, and take missing dates from the file's
+//! modification time.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use rusqlite::Connection;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::db::{now_millis, Db};
+use crate::error::{Error, Result};
+use crate::export;
+use crate::ideas::{self, Idea};
+
+const EXTENSIONS: [&str; 3] = ["md", "markdown", "txt"];
+/// `source_app` of imported ideas.
+const SOURCE_APP: &str = "import";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileStatus {
+    Imported,
+    /// An idea with the file's `id` already exists.
+    Duplicate,
+    /// Nothing but whitespace after the frontmatter.
+    Empty,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileResult {
+    pub path: String,
+    pub status: FileStatus,
+    pub id: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub imported: usize,
+    pub duplicates: usize,
+    pub empty: usize,
+    pub failed: usize,
+    pub files: Vec<FileResult>,
+}
+
+impl ImportReport {
+    fn record(
+        &mut self,
+        path: &Path,
+        status: FileStatus,
+        id: Option<String>,
+        error: Option<String>,
+    ) {
+        match status {
+            FileStatus::Imported => self.imported += 1,
+            FileStatus::Duplicate => self.duplicates += 1,
+            FileStatus::Empty => self.empty += 1,
+            FileStatus::Failed => self.failed += 1,
+        }
+        self.files.push(FileResult {
+            path: path.display().to_string(),
+            status,
+            id,
+            error,
+        });
+    }
+}
+
+/// Every note below `dir`, in path order. Hidden entries such as
+/// `.obsidian` and `.trash` are skipped.
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut entries: Vec<_> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| !name.to_string_lossy().starts_with('.'))
+        })
+        .collect();
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_files(&path, out)?;
+        } else if path.extension().is_some_and(|ext| {
+            EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        }) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Turn one file into an idea, or `None` if it has no text.
+fn read_note(path: &Path) -> Result<Option<Idea>> {
+    let content = std::fs::read_to_string(path)?;
+    let mut note = export::parse_markdown(&content);
+    note.id = note.id.filter(|id| ideas::is_valid_id(id));
+    if note.text.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let modified = std::fs::metadata(path)?
+        .modified()
+        .ok()
+        .and_then(|at| at.duration_since(UNIX_EPOCH).ok())
+        .map(|since| since.as_millis() as i64);
+    let created_at = note.created_at.or(modified).unwrap_or_else(now_millis);
+    let updated_at = note.updated_at.or(modified).unwrap_or(created_at);
+    // Outside Glimt the file name is usually the note's title.
+    let stem = || {
+        path.file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+    };
+    let title = match (note.title, &note.id) {
+        (Some(title), _) => Some(title),
+        (None, None) => stem(),
+        (None, Some(_)) => None,
+    };
+
+    Ok(Some(Idea {
+        id: note.id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+        created_at,
+        updated_at: updated_at.max(created_at),
+        text: note.text,
+        title,
+        archived: false,
+        source_app: Some(SOURCE_APP.into()),
+        markdown_path: None,
+        source_url: None,
+        deleted_at: None,
+        remind_at: None,
+        tags: note.tags,
+        attachments: Vec::new(),
+        recording: None,
+    }))
+}
+
+type ReadNote = (PathBuf, Result<Option<Idea>>);
+
+/// Read every note below `dir`. Done before taking the database lock, since
+/// a large vault takes a while to read.
+fn read_dir(dir: &Path) -> Result<Vec<ReadNote>> {
+    if !dir.is_dir() {
+        return Err(Error::Invalid(format!("{} is not a folder", dir.display())));
+    }
+    let mut files = Vec::new();
+    collect_files(dir, &mut files)?;
+    Ok(files
+        .into_iter()
+        .map(|path| {
+            let note = read_note(&path);
+            (path, note)
+        })
+        .collect())
+}
+
+/// Insert the notes in one transaction, skipping ids that already exist.
+/// Each note has its own savepoint, so one that fails leaves nothing behind.
+fn insert_notes(conn: &mut Connection, notes: Vec<ReadNote>) -> Result<ImportReport> {
+    let mut tx = conn.transaction()?;
+    let mut report = ImportReport::default();
+    let mut seen = HashSet::new();
+    for (path, note) in notes {
+        let idea = match note {
+            Ok(Some(idea)) => idea,
+            Ok(None) => {
+                report.record(&path, FileStatus::Empty, None, None);
+                continue;
+            }
+            Err(e) => {
+                report.record(&path, FileStatus::Failed, None, Some(e.to_string()));
+                continue;
+            }
+        };
+        if !seen.insert(idea.id.clone()) || ideas::exists(&tx, &idea.id)? {
+            report.record(&path, FileStatus::Duplicate, Some(idea.id), None);
+            continue;
+        }
+        let note = tx.savepoint()?;
+        match ideas::insert(&note, &idea) {
+            Ok(()) => {
+                note.commit()?;
+                report.record(&path, FileStatus::Imported, Some(idea.id), None);
+            }
+            // Dropping the savepoint rolls the note back.
+            Err(e) => report.record(
+                &path,
+                FileStatus::Failed,
+                Some(idea.id),
+                Some(e.to_string()),
+            ),
+        }
+    }
+    tx.commit()?;
+    Ok(report)
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+/// Import every note below `dir` and report what happened to each file.
+#[tauri::command]
+pub async fn import_markdown(app: AppHandle, dir: String) -> Result<ImportReport> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let notes = read_dir(Path::new(&dir))?;
+        let report = insert_notes(&mut app.state::<Db>().conn(), notes)?;
+        let imported: Vec<&str> = report
+            .files
+            .iter()
+            .filter(|file| file.status == FileStatus::Imported)
+            .filter_map(|file| file.id.as_deref())
+            .collect();
+        if !imported.is_empty() {
+            crate::log_err(
+                "emit imported ideas",
+                app.emit(ideas::CHANGED_EVENT, &imported),
+            );
+        }
+        log::info!(
+            "Imported {} notes from {dir} ({} duplicates, {} empty, {} failed)",
+            report.imported,
+            report.duplicates,
+            report.empty,
+            report.failed
+        );
+        Ok(report)
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("import failed: {e}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    const KEPT_ID: &str = "0b6f3c9e-2d4a-4f5e-9a1b-7c8d9e0f1a2b";
+
+    #[test]
+    fn failed_notes_leave_nothing_behind() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        crate::migrations::run(&mut conn, None).unwrap();
+        conn.execute_batch(
+            "CREATE TEMP TRIGGER refuse BEFORE INSERT ON idea_tags
+             BEGIN SELECT RAISE(ABORT, 'refused'); END",
+        )
+        .unwrap();
+        let tagged = Idea {
+            tags: vec!["garden".into()],
+            ..ideas::test_idea(KEPT_ID, "Plant tomatoes")
+        };
+        let report = insert_notes(&mut conn, vec![("a.md".into(), Ok(Some(tagged)))]).unwrap();
+        assert_eq!((report.imported, report.failed), (0, 1));
+        assert!(!ideas::exists(&conn, KEPT_ID).unwrap());
+    }
+
+    #[test]
+    fn imports_once_and_reports_each_file() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        crate::migrations::run(&mut conn, None).unwrap();
+        let dir = TestDir::new("import");
+        std::fs::create_dir_all(dir.join("Projects")).unwrap();
+        std::fs::create_dir_all(dir.join(".obsidian")).unwrap();
+
+        let exported = Idea {
+            updated_at: 1_700_000_100_000,
+            title: Some("Kept title".into()),
+            tags: vec!["garden".into()],
+            ..ideas::test_idea(KEPT_ID, "Exported idea")
+        };
+        std::fs::write(dir.join("a.md"), export::generate_markdown(&exported)).unwrap();
+        std::fs::write(dir.join("Projects/Garden plan.md"), "Plant tomatoes\n").unwrap();
+        std::fs::write(
+            dir.join("escape.md"),
+            "---\nid: \"../../evil\"\n---\nSneaky\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("empty.md"), "---\ntitle: x\n---\n\n").unwrap();
+        std::fs::write(dir.join("broken.md"), [0xff, 0xfe, 0x00]).unwrap();
+        std::fs::write(dir.join(".obsidian/app.md"), "settings").unwrap();
+        std::fs::write(dir.join("image.png"), "not a note").unwrap();
+
+        let report = insert_notes(&mut conn, read_dir(&dir).unwrap()).unwrap();
+        assert_eq!(
+            (
+                report.imported,
+                report.duplicates,
+                report.empty,
+                report.failed
+            ),
+            (3, 0, 1, 1)
+        );
+        assert_eq!(report.files.len(), 5);
+
+        let kept = ideas::get(&conn, KEPT_ID).unwrap().unwrap();
+        assert_eq!(kept.title.as_deref(), Some("Kept title"));
+        assert_eq!(kept.created_at, exported.created_at);
+        assert_eq!(kept.updated_at, exported.updated_at);
+
+        let garden = ideas::search_fts(&conn, "tomatoes").unwrap();
+        assert_eq!(garden[0].title.as_deref(), Some("Garden plan"));
+        assert_eq!(garden[0].source_app.as_deref(), Some(SOURCE_APP));
+        let sneaky = &ideas::search_fts(&conn, "sneaky").unwrap()[0];
+        assert!(ideas::is_valid_id(&sneaky.id));
+
+        // Only notes with an id can be recognised a second time.
+        let again = insert_notes(&mut conn, read_dir(&dir).unwrap()).unwrap();
+        assert_eq!((again.imported, again.duplicates), (2, 1));
+    }
+}
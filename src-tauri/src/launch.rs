@@ -0,0 +1,102 @@
+//! Launch flags (`--capture`, `--record`, `--add "text"`). A second launch
+//! forwards its arguments to the running instance, which acts on them here.
+
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::db::Db;
+use crate::ideas;
+
+#[derive(Debug, PartialEq)]
+enum Action {
+    Capture,
+    Record,
+    Add(String),
+}
+
+/// Parse `args` (without the program name). Unknown arguments are ignored so
+/// flags added by autostart or the OS never stop a launch.
+fn parse(args: &[String]) -> Vec<Action> {
+    let mut actions = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--capture" => actions.push(Action::Capture),
+            "--record" => actions.push(Action::Record),
+            "--add" => match iter.next() {
+                Some(text) => actions.push(Action::Add(text.clone())),
+                None => log::warn!("--add needs the idea text as its next argument"),
+            },
+            other => {
+                if let Some(text) = other.strip_prefix("--add=") {
+                    actions.push(Action::Add(text.to_owned()));
+                }
+            }
+        }
+    }
+    actions
+}
+
+/// Act on the flags of this launch (at startup) or of a second launch that
+/// was redirected here. A bare second launch brings the dashboard forward.
+pub fn handle(app: &AppHandle, args: &[String], forwarded: bool) {
+    let actions = parse(args);
+    if actions.is_empty() && forwarded {
+        if let Some(window) = app.get_webview_window("main") {
+            crate::log_err("unminimize window", window.unminimize());
+            crate::log_err("show window", window.show());
+            crate::log_err("focus window", window.set_focus());
+        }
+    }
+
+    for action in actions {
+        match action {
+            Action::Capture => crate::toggle_capture_window(app),
+            Action::Record => crate::toggle_indicator_recording(app),
+            Action::Add(text) => add_idea(app, &text),
+        }
+    }
+}
+
+fn add_idea(app: &AppHandle, text: &str) {
+    let Some(db) = app.try_state::<Db>() else {
+        log::error!("--add ignored: the database is not available");
+        return;
+    };
+    let created = ideas::create(&db.conn(), text.trim(), Some("cli"));
+    match created {
+        // Same event the capture window sends, so the dashboard reloads and
+        // embeds the new idea.
+        Ok(_) => crate::log_err("emit idea-saved", app.emit("idea-saved", ())),
+        Err(e) => log::error!("--add failed: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_flags_in_order() {
+        assert_eq!(
+            parse(&args(&["--record", "--add", "call mom", "--capture"])),
+            vec![
+                Action::Record,
+                Action::Add("call mom".into()),
+                Action::Capture
+            ]
+        );
+        assert_eq!(
+            parse(&args(&["--add=inline"])),
+            vec![Action::Add("inline".into())]
+        );
+    }
+
+    #[test]
+    fn ignores_unknown_and_incomplete_flags() {
+        assert!(parse(&args(&["--minimized", "--add"])).is_empty());
+    }
+}
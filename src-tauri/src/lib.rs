@@ -1,7 +1,41 @@
+mod api;
+mod app_lock;
+mod archive;
+mod attachments;
+mod audio;
+mod backup;
+pub mod cli;
+mod clip;
+mod db;
+mod embeddings;
+mod encryption;
+mod error;
+mod export;
+mod foreground;
+mod hnsw;
+mod ideas;
+mod import;
+mod launch;
+mod migrations;
+mod recorder;
+mod recordings;
+mod reminders;
+mod revisions;
+mod search;
+mod settings;
+mod shortcuts;
+mod tags;
+#[cfg(test)]
+mod test_dir;
+mod transcribe;
+mod trash;
+mod vault;
+mod vector_index;
+
 use tauri::{
     menu::{Menu, MenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    AppHandle, Manager,
+    AppHandle, Emitter, Manager, RunEvent,
 };
 
 fn log_err<T>(context: &str, result: Result<T, impl std::fmt::Display>) {
@@ -15,16 +49,129 @@ fn toggle_capture_window(app: &AppHandle) {
         if window.is_visible().unwrap_or(false) {
             log_err("hide capture", window.hide());
         } else {
+            app.state::<foreground::Sources>().record("capture");
             log_err("show capture", window.show());
             log_err("focus capture", window.set_focus());
         }
     }
 }
 
+/// Start a quick recording, or stop the one in progress. The microphone is
+/// captured natively; the indicator only shows levels and transcribes.
+fn toggle_indicator_recording(app: &AppHandle) {
+    let recorder = app.state::<recorder::Recorder>();
+    if recorder.is_recording() {
+        match recorder.stop() {
+            Ok(recorded) => log_err(
+                "stop recording",
+                app.emit_to("indicator", "stop-recording", recorded),
+            ),
+            Err(e) => log_err(
+                "report recording error",
+                app.emit_to("indicator", "recording-error", e.to_string()),
+            ),
+        }
+        return;
+    }
+    let Some(window) = app.get_webview_window("indicator") else {
+        return;
+    };
+    app.state::<foreground::Sources>().record("indicator");
+    log_err("show indicator", window.show());
+    match recorder.start(app) {
+        Ok(()) => log_err(
+            "start recording",
+            app.emit_to("indicator", "start-recording", ()),
+        ),
+        Err(e) => log_err(
+            "report recording error",
+            app.emit_to("indicator", "recording-error", e.to_string()),
+        ),
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    let handler: fn(tauri::ipc::Invoke) -> bool = tauri::generate_handler![
+        api::get_api_config,
+        api::set_api_config,
+        api::regenerate_api_token,
+        db::db_status,
+        ideas::create_idea,
+        ideas::get_ideas,
+        ideas::get_all_ideas,
+        ideas::get_idea,
+        ideas::get_ideas_by_ids,
+        ideas::update_idea,
+        ideas::archive_idea,
+        ideas::delete_idea,
+        ideas::search_ideas_fts,
+        ideas::list_source_apps,
+        trash::list_trash,
+        trash::restore_idea,
+        trash::purge_idea,
+        trash::empty_trash,
+        reminders::set_reminder,
+        embeddings::store_embedding,
+        embeddings::get_all_embeddings,
+        embeddings::delete_embedding,
+        embeddings::delete_all_embeddings,
+        embeddings::get_ideas_missing_embeddings,
+        vector_index::search_similar,
+        search::hybrid_search,
+        revisions::list_revisions,
+        revisions::diff_revisions,
+        revisions::restore_revision,
+        tags::list_tags,
+        tags::get_ideas_by_tag,
+        tags::create_tag,
+        tags::rename_tag,
+        tags::delete_tag,
+        tags::add_idea_tag,
+        tags::remove_idea_tag,
+        attachments::attach_file,
+        attachments::attach_data,
+        attachments::remove_attachment,
+        attachments::read_attachment,
+        attachments::save_attachment,
+        recordings::keep_recording,
+        recordings::read_recording,
+        recordings::delete_recording,
+        recordings::retranscribe_recording,
+        transcribe::native_transcription_status,
+        transcribe::download_whisper_model,
+        transcribe::transcribe_audio,
+        recorder::list_input_devices,
+        recorder::toggle_recording,
+        recorder::take_recording,
+        clip::take_clip,
+        shortcuts::get_shortcuts,
+        shortcuts::set_shortcut,
+        settings::get_settings,
+        settings::set_settings,
+        vault::export_idea_markdown,
+        import::import_markdown,
+        archive::export_archive,
+        archive::import_archive,
+        backup::list_backups,
+        backup::backup_now,
+        backup::restore_backup,
+        encryption::encryption_status,
+        encryption::unlock_database,
+        encryption::set_database_passphrase,
+        app_lock::app_lock_status,
+        app_lock::report_activity,
+        app_lock::lock_app,
+        app_lock::unlock_app,
+        app_lock::set_app_lock_pin,
+    ];
+
     tauri::Builder::default()
-        .plugin(tauri_plugin_sql::Builder::new().build())
+        // Must be registered first so a second launch exits before it opens
+        // the database or creates another tray icon.
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            launch::handle(app, argv.get(1..).unwrap_or_default(), true);
+        }))
         .plugin(tauri_plugin_global_shortcut::Builder::new().build())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_persisted_scope::init())
@@ -32,6 +179,21 @@ pub fn run() {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_autostart::Builder::new().build())
+        .invoke_handler(move |invoke| {
+            // Reading ideas back is refused while the app is locked.
+            let allowed = invoke
+                .message
+                .webview_ref()
+                .try_state::<app_lock::AppLock>()
+                .map_or(true, |lock| lock.allows(invoke.message.command()));
+            if !allowed {
+                invoke.resolver.reject("Glimt is locked");
+                return true;
+            }
+            handler(invoke)
+        })
+        .manage(foreground::Sources::default())
+        .manage(clip::Clips::default())
         .setup(|app| {
             if cfg!(debug_assertions) {
                 app.handle().plugin(
@@ -41,11 +203,98 @@ pub fn run() {
                 )?;
             }
 
+            // ── Database ─────────────────────────────────────────
+            // Migrations run before any window is shown. On failure, or while
+            // an encrypted database waits for its passphrase, the webviews
+            // read why from `db_status` instead of touching data.
+            let config_dir = app.path().app_config_dir()?;
+            std::fs::create_dir_all(&config_dir)?;
+            let db_path = config_dir.join(db::DB_FILE_NAME);
+            let passphrase = encryption::Encryption::startup_passphrase(&db_path);
+            let status = match db::Db::open(&db_path, passphrase.as_deref()) {
+                Ok(db) => {
+                    app.manage(db);
+                    Ok(())
+                }
+                Err(db::OpenError::Migration(e)) => {
+                    log::error!("{e}");
+                    Err(db::StartupError::Migration(e))
+                }
+                Err(db::OpenError::Locked) => {
+                    log::info!("Database is encrypted; waiting for the passphrase");
+                    Err(db::StartupError::Locked { locked: true })
+                }
+                Err(e) => return Err(e.into()),
+            };
+            let unlocked = status.is_ok();
+            app.manage(db::DbStatus::new(status));
+            app.manage(encryption::Encryption::new(
+                db_path.clone(),
+                passphrase.filter(|_| unlocked),
+            ));
+
+            app.manage(vector_index::VectorIndex::new(
+                app.path().app_cache_dir()?.join("vector-index"),
+            ));
+            app.manage(attachments::Blobs::new(
+                app.path().app_data_dir()?.join("attachments"),
+            ));
+
+            // ── Settings ─────────────────────────────────────────
+            app.manage(settings::SettingsStore::new(&config_dir));
+
+            // ── App lock ─────────────────────────────────────────
+            let auto_lock = app
+                .state::<settings::SettingsStore>()
+                .get()
+                .auto_lock_minutes
+                > 0;
+            app.manage(app_lock::AppLock::new(&config_dir, auto_lock));
+            app_lock::AppLock::start(app.handle());
+
+            // ── Markdown vault sync ──────────────────────────────
+            app.manage(vault::Vault::default());
+            vault::Vault::start(app.handle());
+
+            // ── Scheduled backups ────────────────────────────────
+            app.manage(backup::Backups::new(
+                db_path,
+                app.path().app_data_dir()?.join("backups"),
+            ));
+            backup::Backups::start(app.handle());
+
+            // ── Transcription ────────────────────────────────────
+            app.manage(transcribe::Transcriber::new(
+                app.path().app_data_dir()?.join("whisper"),
+            ));
+
+            app.manage(recorder::Recorder::default());
+            app.manage(recordings::Recordings::new(
+                app.path().app_data_dir()?.join("recordings"),
+            ));
+
+            // ── Trash ────────────────────────────────────────────
+            trash::start(app.handle());
+
+            // ── Reminders ────────────────────────────────────────
+            reminders::start(app.handle());
+
+            // ── Global shortcuts ─────────────────────────────────
+            app.manage(shortcuts::Shortcuts::default());
+            app.state::<shortcuts::Shortcuts>()
+                .register_all(app.handle());
+
+            // ── Capture API ──────────────────────────────────────
+            app.manage(api::ApiServer::new(&config_dir));
+            app.state::<api::ApiServer>().start_if_enabled(app.handle());
+
             // ── System tray ──────────────────────────────────────
             let open_i = MenuItem::with_id(app, "open", "Open Glimt", true, None::<&str>)?;
             let capture_i = MenuItem::with_id(app, "capture", "Quick Capture", true, None::<&str>)?;
+            let empty_trash_i =
+                MenuItem::with_id(app, "empty_trash", "Empty Trash…", true, None::<&str>)?;
             let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&open_i, &capture_i, &quit_i])?;
+            let menu = Menu::with_items(app, &[&open_i, &capture_i, &empty_trash_i, &quit_i])?;
 
             let icon = app
                 .default_window_icon()
@@ -66,6 +315,9 @@ pub fn run() {
                         "capture" => {
                             toggle_capture_window(app);
                         }
+                        "empty_trash" => {
+                            trash::confirm_empty(app);
+                        }
                         "quit" => {
                             app.exit(0);
                         }
@@ -92,16 +344,32 @@ pub fn run() {
             // ── Hide main window on close (stays in tray) ────────
             if let Some(main_window) = app.get_webview_window("main") {
                 let main = main_window.clone();
-                main_window.on_window_event(move |event| {
-                    if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                main_window.on_window_event(move |event| match event {
+                    tauri::WindowEvent::CloseRequested { api, .. } => {
                         api.prevent_close();
                         log_err("hide main on close", main.hide());
                     }
+                    // Coming back to Glimt counts as activity for auto-lock.
+                    tauri::WindowEvent::Focused(true) => {
+                        main.state::<app_lock::AppLock>().touch();
+                    }
+                    _ => {}
                 });
+                log_err("show window", main_window.show());
             }
 
+            let args: Vec<String> = std::env::args().skip(1).collect();
+            launch::handle(app.handle(), &args, false);
+
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("Failed to start Glimt — is WebView2 installed?");
+        .build(tauri::generate_context!())
+        .expect("Failed to start Glimt — is WebView2 installed?")
+        .run(|app, event| {
+            if let RunEvent::Exit = event {
+                if let Some(db) = app.try_state::<db::Db>() {
+                    app.state::<vector_index::VectorIndex>().persist(&db.conn());
+                }
+            }
+        });
 }
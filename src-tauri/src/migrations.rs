@@ -0,0 +1,422 @@
+use std::path::{Path, PathBuf};
+
+use rusqlite::Connection;
+use serde::Serialize;
+
+struct Migration {
+    version: i64,
+    description: &'static str,
+    sql: &'static str,
+    /// Fills new tables from existing rows where SQL alone can't, inside
+    /// the same transaction as `sql`.
+    backfill: Option<fn(&Connection) -> rusqlite::Result<()>>,
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Initial schema — ideas, FTS, embeddings, indices, triggers",
+        sql: "
+        CREATE TABLE IF NOT EXISTS ideas (
+          id TEXT PRIMARY KEY,
+          created_at INTEGER NOT NULL,
+          updated_at INTEGER NOT NULL,
+          text TEXT NOT NULL,
+          title TEXT,
+          archived INTEGER NOT NULL DEFAULT 0,
+          source_app TEXT,
+          markdown_path TEXT
+        );
+
+        CREATE VIRTUAL TABLE IF NOT EXISTS fts_ideas
+        USING fts5(id, title, text, content='ideas', content_rowid='rowid');
+
+        CREATE TABLE IF NOT EXISTS embeddings (
+          idea_id TEXT NOT NULL REFERENCES ideas(id) ON DELETE CASCADE,
+          model TEXT NOT NULL,
+          dims INTEGER NOT NULL,
+          vector BLOB NOT NULL,
+          created_at INTEGER NOT NULL,
+          PRIMARY KEY (idea_id, model)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_ideas_archived ON ideas(archived);
+        CREATE INDEX IF NOT EXISTS idx_ideas_created_at ON ideas(created_at);
+        CREATE INDEX IF NOT EXISTS idx_ideas_archived_created ON ideas(archived, created_at);
+
+        CREATE TRIGGER IF NOT EXISTS ideas_ai AFTER INSERT ON ideas BEGIN
+          INSERT INTO fts_ideas(id, title, text) VALUES (new.id, new.title, new.text);
+        END;
+        CREATE TRIGGER IF NOT EXISTS ideas_ad AFTER DELETE ON ideas BEGIN
+          INSERT INTO fts_ideas(fts_ideas, id, title, text) VALUES('delete', old.id, old.title, old.text);
+        END;
+        CREATE TRIGGER IF NOT EXISTS ideas_au AFTER UPDATE ON ideas BEGIN
+          INSERT INTO fts_ideas(fts_ideas, id, title, text) VALUES('delete', old.id, old.title, old.text);
+          INSERT INTO fts_ideas(id, title, text) VALUES (new.id, new.title, new.text);
+        END;
+    ",
+        backfill: None,
+    },
+    Migration {
+        version: 2,
+        description: "Key FTS triggers on ideas.rowid and rebuild the index",
+        // The V1 triggers omitted `rowid`, so external-content deletes never
+        // matched and edited ideas kept matching their old text.
+        sql: "
+        DROP TRIGGER IF EXISTS ideas_ai;
+        DROP TRIGGER IF EXISTS ideas_ad;
+        DROP TRIGGER IF EXISTS ideas_au;
+
+        CREATE TRIGGER ideas_ai AFTER INSERT ON ideas BEGIN
+          INSERT INTO fts_ideas(rowid, id, title, text) VALUES (new.rowid, new.id, new.title, new.text);
+        END;
+        CREATE TRIGGER ideas_ad AFTER DELETE ON ideas BEGIN
+          INSERT INTO fts_ideas(fts_ideas, rowid, id, title, text) VALUES('delete', old.rowid, old.id, old.title, old.text);
+        END;
+        CREATE TRIGGER ideas_au AFTER UPDATE ON ideas BEGIN
+          INSERT INTO fts_ideas(fts_ideas, rowid, id, title, text) VALUES('delete', old.rowid, old.id, old.title, old.text);
+          INSERT INTO fts_ideas(rowid, id, title, text) VALUES (new.rowid, new.id, new.title, new.text);
+        END;
+
+        INSERT INTO fts_ideas(fts_ideas) VALUES('rebuild');
+    ",
+        backfill: None,
+    },
+    Migration {
+        version: 3,
+        description: "Index ideas.markdown_path for vault sync",
+        sql: "
+        CREATE INDEX IF NOT EXISTS idx_ideas_markdown_path ON ideas(markdown_path);
+    ",
+        backfill: None,
+    },
+    Migration {
+        version: 4,
+        description: "Tags and idea_tags, extracted from existing hashtags",
+        sql: "
+        CREATE TABLE tags (
+          id INTEGER PRIMARY KEY,
+          name TEXT NOT NULL UNIQUE
+        );
+
+        CREATE TABLE idea_tags (
+          idea_id TEXT NOT NULL REFERENCES ideas(id) ON DELETE CASCADE,
+          tag_id INTEGER NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+          auto INTEGER NOT NULL DEFAULT 1,
+          PRIMARY KEY (idea_id, tag_id)
+        );
+
+        CREATE INDEX idx_idea_tags_tag ON idea_tags(tag_id);
+    ",
+        backfill: Some(crate::tags::backfill),
+    },
+    Migration {
+        version: 5,
+        description: "Add ideas.source_url for clipboard captures",
+        sql: "
+        ALTER TABLE ideas ADD COLUMN source_url TEXT;
+    ",
+        backfill: None,
+    },
+    Migration {
+        version: 6,
+        description: "Attachments, pointing into the blob store",
+        sql: "
+        CREATE TABLE attachments (
+          id TEXT PRIMARY KEY,
+          idea_id TEXT NOT NULL REFERENCES ideas(id) ON DELETE CASCADE,
+          hash TEXT NOT NULL,
+          name TEXT NOT NULL,
+          mime TEXT NOT NULL,
+          size INTEGER NOT NULL,
+          thumbnail INTEGER NOT NULL DEFAULT 0,
+          created_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX idx_attachments_idea ON attachments(idea_id);
+        CREATE INDEX idx_attachments_hash ON attachments(hash);
+    ",
+        backfill: None,
+    },
+    Migration {
+        version: 7,
+        description: "Original audio of voice ideas",
+        sql: "
+        CREATE TABLE recordings (
+          idea_id TEXT PRIMARY KEY REFERENCES ideas(id) ON DELETE CASCADE,
+          size INTEGER NOT NULL,
+          duration_ms INTEGER NOT NULL,
+          created_at INTEGER NOT NULL
+        );
+    ",
+        backfill: None,
+    },
+    Migration {
+        version: 8,
+        description: "Earlier versions of edited ideas",
+        sql: "
+        CREATE TABLE idea_revisions (
+          id INTEGER PRIMARY KEY AUTOINCREMENT,
+          idea_id TEXT NOT NULL REFERENCES ideas(id) ON DELETE CASCADE,
+          text TEXT NOT NULL,
+          title TEXT,
+          created_at INTEGER NOT NULL
+        );
+
+        CREATE INDEX idx_idea_revisions_idea ON idea_revisions(idea_id, id);
+    ",
+        backfill: None,
+    },
+    Migration {
+        version: 9,
+        description: "Trash for deleted ideas",
+        sql: "
+        ALTER TABLE ideas ADD COLUMN deleted_at INTEGER;
+
+        CREATE INDEX idx_ideas_deleted_at ON ideas(deleted_at);
+    ",
+        backfill: None,
+    },
+    Migration {
+        version: 10,
+        description: "Reminders and resurfaced ideas",
+        sql: "
+        ALTER TABLE ideas ADD COLUMN remind_at INTEGER;
+        ALTER TABLE ideas ADD COLUMN resurfaced_at INTEGER;
+
+        CREATE INDEX idx_ideas_remind_at ON ideas(remind_at) WHERE remind_at IS NOT NULL;
+    ",
+        backfill: None,
+    },
+];
+
+pub const CURRENT_SCHEMA_VERSION: i64 = MIGRATIONS[MIGRATIONS.len() - 1].version;
+
+/// Why the database could not be brought up to date. Sent to the webview
+/// as-is so the error page can tell the user where their backup is.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationError {
+    pub version: i64,
+    pub description: String,
+    pub message: String,
+    pub backup_path: Option<PathBuf>,
+}
+
+impl std::fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Migration V{} (\"{}\") failed: {}",
+            self.version, self.description, self.message
+        )?;
+        if let Some(path) = &self.backup_path {
+            write!(
+                f,
+                " — your previous database was saved to {}",
+                path.display()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+pub fn schema_version(conn: &Connection) -> rusqlite::Result<i64> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+}
+
+/// Bring the schema to `CURRENT_SCHEMA_VERSION`, one transaction per version.
+///
+/// When upgrading an existing database (`user_version > 0`), a consistent copy
+/// is written next to `db_path` first. A failing version rolls back on its own,
+/// leaving the database at the last version that applied cleanly.
+pub fn run(conn: &mut Connection, db_path: Option<&Path>) -> Result<(), MigrationError> {
+    let current = schema_version(conn).map_err(|e| MigrationError {
+        version: 0,
+        description: "Read schema version".into(),
+        message: e.to_string(),
+        backup_path: None,
+    })?;
+    if current >= CURRENT_SCHEMA_VERSION {
+        return Ok(());
+    }
+
+    let pending: Vec<&Migration> = MIGRATIONS.iter().filter(|m| m.version > current).collect();
+    let first_version = pending.first().map_or(current + 1, |m| m.version);
+
+    let backup_path = match db_path {
+        Some(path) if current > 0 => {
+            Some(backup(conn, path, current).map_err(|e| MigrationError {
+                version: first_version,
+                description: "Pre-migration backup".into(),
+                message: e.to_string(),
+                backup_path: None,
+            })?)
+        }
+        _ => None,
+    };
+
+    for migration in pending {
+        apply(conn, migration).map_err(|e| MigrationError {
+            version: migration.version,
+            description: migration.description.into(),
+            message: e.to_string(),
+            backup_path: backup_path.clone(),
+        })?;
+        log::info!(
+            "Applied migration V{} ({})",
+            migration.version,
+            migration.description
+        );
+    }
+    Ok(())
+}
+
+fn apply(conn: &mut Connection, migration: &Migration) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    tx.execute_batch(migration.sql)?;
+    if let Some(backfill) = migration.backfill {
+        backfill(&tx)?;
+    }
+    tx.pragma_update(None, "user_version", migration.version)?;
+    tx.commit()
+}
+
+/// Snapshot the database as `glimt.pre-v{version}.db` using `VACUUM INTO`,
+/// which also captures pages still sitting in the WAL.
+fn backup(conn: &Connection, db_path: &Path, version: i64) -> rusqlite::Result<PathBuf> {
+    let stem = db_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("glimt");
+    let target = db_path.with_file_name(format!("{stem}.pre-v{version}.db"));
+    if target.exists() {
+        // VACUUM INTO refuses to overwrite; a stale copy from an earlier failed
+        // attempt holds the same pre-upgrade data, so replace it.
+        let _ = std::fs::remove_file(&target);
+    }
+    conn.execute("VACUUM INTO ?1", [target.to_string_lossy()])?;
+    Ok(target)
+}
+
+/// The `glimt.pre-v{version}.db` snapshots [`backup`] left next to the
+/// database.
+#[cfg(feature = "encryption")]
+pub(crate) fn snapshots(db_path: &Path) -> Vec<PathBuf> {
+    let stem = db_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("glimt");
+    let prefix = format!("{stem}.pre-v");
+    let Some(Ok(entries)) = db_path.parent().map(std::fs::read_dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".db"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    fn table_exists(conn: &Connection, name: &str) -> bool {
+        conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE name = ?1",
+            [name],
+            |row| row.get::<_, i64>(0),
+        )
+        .unwrap()
+            > 0
+    }
+
+    #[test]
+    fn applies_all_migrations_on_fresh_database() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        run(&mut conn, None).unwrap();
+
+        assert_eq!(schema_version(&conn).unwrap(), CURRENT_SCHEMA_VERSION);
+        assert!(table_exists(&conn, "ideas"));
+        assert!(table_exists(&conn, "fts_ideas"));
+        assert!(table_exists(&conn, "embeddings"));
+    }
+
+    #[test]
+    fn is_a_no_op_when_up_to_date() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        run(&mut conn, None).unwrap();
+        run(&mut conn, None).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), CURRENT_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn skips_when_ahead_of_current_version() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", CURRENT_SCHEMA_VERSION + 1)
+            .unwrap();
+        run(&mut conn, None).unwrap();
+        assert!(!table_exists(&conn, "ideas"));
+    }
+
+    #[test]
+    fn failed_migration_rolls_back_and_keeps_version() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        let broken = Migration {
+            version: 1,
+            description: "broken",
+            sql: "CREATE TABLE half (id TEXT); CREATE TABLE oops (",
+            backfill: None,
+        };
+        assert!(apply(&mut conn, &broken).is_err());
+        assert!(!table_exists(&conn, "half"));
+        assert_eq!(schema_version(&conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn backs_up_existing_database_before_upgrading() {
+        let dir = TestDir::new("migrate");
+        let path = dir.join("glimt.db");
+
+        let mut conn = Connection::open(&path).unwrap();
+        apply(&mut conn, &MIGRATIONS[0]).unwrap();
+        conn.execute(
+            "INSERT INTO ideas (id, created_at, updated_at, text) VALUES ('a', 0, 0, 'kept')",
+            [],
+        )
+        .unwrap();
+        run(&mut conn, Some(&path)).unwrap();
+
+        let copy = Connection::open(dir.join("glimt.pre-v1.db")).unwrap();
+        assert_eq!(schema_version(&copy).unwrap(), 1);
+        let text: String = copy
+            .query_row("SELECT text FROM ideas WHERE id = 'a'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(text, "kept");
+
+        drop((copy, conn));
+    }
+
+    #[test]
+    fn fresh_install_is_not_backed_up() {
+        let dir = TestDir::new("fresh");
+        let path = dir.join("glimt.db");
+
+        let mut conn = Connection::open(&path).unwrap();
+        run(&mut conn, Some(&path)).unwrap();
+        let entries = std::fs::read_dir(&dir).unwrap().count();
+        assert_eq!(entries, 1, "only glimt.db itself should exist");
+
+        drop(conn);
+    }
+}
@@ -0,0 +1,289 @@
+//! Microphone capture in Rust for the record shortcut, so quick recordings
+//! do not depend on `getUserMedia` inside the hidden indicator webview.
+//!
+//! A capture thread owns the cpal stream (it is not `Send` everywhere) and
+//! emits input levels to the indicator until it is told to stop. The
+//! finished recording is kept as a 16 kHz mono WAV until the indicator
+//! takes it for transcription, and with `keepRecordings` on, as samples
+//! until [`crate::recordings`] stores it with the saved idea.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SampleFormat, SizedSample};
+use serde::Serialize;
+use tauri::ipc::Response;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::audio;
+use crate::error::{Error, Result};
+use crate::settings::SettingsStore;
+
+/// Emitted to the indicator roughly 20 times a second while recording.
+const LEVEL_EVENT: &str = "audio-level";
+const LEVEL_INTERVAL: Duration = Duration::from_millis(50);
+/// Longer recordings are cut off rather than growing without bound.
+const MAX_RECORDING_SECS: usize = 10 * 60;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputDevice {
+    pub name: String,
+    pub is_default: bool,
+}
+
+fn device_err(e: impl std::fmt::Display) -> Error {
+    Error::Invalid(format!("microphone: {e}"))
+}
+
+pub fn list_devices() -> Result<Vec<InputDevice>> {
+    let host = cpal::default_host();
+    let default = host
+        .default_input_device()
+        .and_then(|device| device.name().ok());
+    let mut devices: Vec<InputDevice> = host
+        .input_devices()
+        .map_err(device_err)?
+        .filter_map(|device| device.name().ok())
+        .map(|name| InputDevice {
+            is_default: default.as_deref() == Some(name.as_str()),
+            name,
+        })
+        .collect();
+    devices.dedup_by(|a, b| a.name == b.name);
+    Ok(devices)
+}
+
+/// The configured device, or the default one if it has gone away.
+fn open_device(name: Option<&str>) -> Result<cpal::Device> {
+    let host = cpal::default_host();
+    if let Some(name) = name {
+        let found = host
+            .input_devices()
+            .map_err(device_err)?
+            .find(|device| device.name().is_ok_and(|n| n == name));
+        match found {
+            Some(device) => return Ok(device),
+            None => log::warn!("Input device {name} not found, using the default"),
+        }
+    }
+    host.default_input_device()
+        .ok_or_else(|| device_err("no input device available"))
+}
+
+struct Captured {
+    samples: Vec<f32>,
+    rate: u32,
+}
+
+struct Active {
+    stop: mpsc::Sender<()>,
+    thread: JoinHandle<Captured>,
+    /// The `keepRecordings` setting when the recording started.
+    keep: bool,
+}
+
+/// Managed state: the capture in progress and the last finished recording.
+/// The input device comes from the `inputDevice` setting.
+#[derive(Default)]
+pub struct Recorder {
+    active: Mutex<Option<Active>>,
+    last: Mutex<Option<Vec<u8>>>,
+    /// 16 kHz samples of the last recording, waiting to be kept.
+    kept: Mutex<Option<Vec<f32>>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl Recorder {
+    pub fn is_recording(&self) -> bool {
+        lock(&self.active).is_some()
+    }
+
+    /// Open the microphone and start capturing. Returns once the stream is
+    /// running, so device errors surface to the caller.
+    pub fn start(&self, app: &AppHandle) -> Result<()> {
+        let mut active = lock(&self.active);
+        if active.is_some() {
+            return Ok(());
+        }
+        let settings = app.state::<SettingsStore>().get();
+        let (device_name, keep) = (settings.input_device, settings.keep_recordings);
+        let (stop, stop_rx) = mpsc::channel();
+        let (ready_tx, ready_rx) = mpsc::channel();
+        let app = app.clone();
+        let thread = std::thread::Builder::new()
+            .name("glimt-recorder".into())
+            .spawn(move || capture(&app, device_name.as_deref(), &ready_tx, &stop_rx))?;
+        match ready_rx.recv() {
+            Ok(Ok(())) => {
+                *active = Some(Active { stop, thread, keep });
+                Ok(())
+            }
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(device_err("capture thread exited")),
+        }
+    }
+
+    /// Stop capturing and keep the recording as a WAV for [`Self::take`].
+    /// Returns whether anything was recorded.
+    pub fn stop(&self) -> Result<bool> {
+        let Some(Active { stop, thread, keep }) = lock(&self.active).take() else {
+            return Ok(false);
+        };
+        // The thread also stops if the sender is simply dropped.
+        let _ = stop.send(());
+        let captured = thread
+            .join()
+            .map_err(|_| device_err("capture thread panicked"))?;
+        let samples = audio::resample(&captured.samples, captured.rate, audio::WHISPER_SAMPLE_RATE);
+        let wav = if samples.is_empty() {
+            None
+        } else {
+            Some(audio::encode_wav(&samples, audio::WHISPER_SAMPLE_RATE)?)
+        };
+        let recorded = wav.is_some();
+        *lock(&self.last) = wav;
+        *lock(&self.kept) = (keep && recorded).then_some(samples);
+        Ok(recorded)
+    }
+
+    pub fn take(&self) -> Option<Vec<u8>> {
+        lock(&self.last).take()
+    }
+
+    /// The samples of the last recording, if it is to be kept. Once only.
+    pub fn take_kept(&self) -> Option<Vec<f32>> {
+        lock(&self.kept).take()
+    }
+}
+
+/// Body of the capture thread. Reports whether the stream started through
+/// `ready`, then emits levels until `stop` fires or its sender is dropped.
+fn capture(
+    app: &AppHandle,
+    device_name: Option<&str>,
+    ready: &mpsc::Sender<Result<()>>,
+    stop: &mpsc::Receiver<()>,
+) -> Captured {
+    let samples = Arc::new(Mutex::new(Vec::new()));
+    let level = Arc::new(AtomicU32::new(0));
+    let started = open_stream(device_name, &samples, &level);
+    let (stream, rate) = match started {
+        Ok(started) => started,
+        Err(e) => {
+            let _ = ready.send(Err(e));
+            return Captured {
+                samples: Vec::new(),
+                rate: audio::WHISPER_SAMPLE_RATE,
+            };
+        }
+    };
+    let _ = ready.send(Ok(()));
+
+    // Sending on `stop` or dropping its sender both end the loop.
+    while let Err(RecvTimeoutError::Timeout) = stop.recv_timeout(LEVEL_INTERVAL) {
+        let peak = f32::from_bits(level.swap(0, Ordering::Relaxed));
+        crate::log_err(
+            "emit audio level",
+            app.emit_to("indicator", LEVEL_EVENT, peak.min(1.0)),
+        );
+    }
+    drop(stream);
+    let samples = std::mem::take(&mut *lock(&samples));
+    Captured { samples, rate }
+}
+
+fn open_stream(
+    device_name: Option<&str>,
+    samples: &Arc<Mutex<Vec<f32>>>,
+    level: &Arc<AtomicU32>,
+) -> Result<(cpal::Stream, u32)> {
+    let device = open_device(device_name)?;
+    let supported = device.default_input_config().map_err(device_err)?;
+    let config = supported.config();
+    let rate = config.sample_rate.0;
+    let stream = match supported.sample_format() {
+        SampleFormat::I8 => build::<i8>(&device, &config, samples, level),
+        SampleFormat::I16 => build::<i16>(&device, &config, samples, level),
+        SampleFormat::I32 => build::<i32>(&device, &config, samples, level),
+        SampleFormat::U8 => build::<u8>(&device, &config, samples, level),
+        SampleFormat::U16 => build::<u16>(&device, &config, samples, level),
+        SampleFormat::U32 => build::<u32>(&device, &config, samples, level),
+        SampleFormat::F32 => build::<f32>(&device, &config, samples, level),
+        SampleFormat::F64 => build::<f64>(&device, &config, samples, level),
+        other => return Err(device_err(format!("unsupported sample format {other}"))),
+    }?;
+    stream.play().map_err(device_err)?;
+    Ok((stream, rate))
+}
+
+fn build<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    samples: &Arc<Mutex<Vec<f32>>>,
+    level: &Arc<AtomicU32>,
+) -> Result<cpal::Stream>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    let channels = usize::from(config.channels.max(1));
+    let limit = MAX_RECORDING_SECS * config.sample_rate.0 as usize;
+    let samples = Arc::clone(samples);
+    let level = Arc::clone(level);
+    device
+        .build_input_stream(
+            config,
+            move |data: &[T], _: &cpal::InputCallbackInfo| {
+                let mut peak = 0.0f32;
+                let mut samples = lock(&samples);
+                for frame in data.chunks_exact(channels) {
+                    let mono =
+                        frame.iter().map(|&s| s.to_sample::<f32>()).sum::<f32>() / channels as f32;
+                    peak = peak.max(mono.abs());
+                    if samples.len() < limit {
+                        samples.push(mono);
+                    }
+                }
+                // Bit patterns of non-negative floats sort like the floats.
+                level.fetch_max(peak.to_bits(), Ordering::Relaxed);
+            },
+            |e| log::warn!("Microphone stream error: {e}"),
+            None,
+        )
+        .map_err(device_err)
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+/// Same as the record shortcut: start capturing, or stop and hand off.
+#[tauri::command]
+pub fn toggle_recording(app: AppHandle) {
+    crate::toggle_indicator_recording(&app);
+}
+
+#[tauri::command]
+pub async fn list_input_devices() -> Result<Vec<InputDevice>> {
+    tauri::async_runtime::spawn_blocking(list_devices)
+        .await
+        .map_err(|e| device_err(format!("device listing failed: {e}")))?
+}
+
+/// Hand the last finished recording (a 16 kHz mono WAV) to the webview as
+/// raw bytes, once.
+#[tauri::command]
+pub fn take_recording(recorder: State<'_, Recorder>) -> Result<Response> {
+    recorder
+        .take()
+        .map(Response::new)
+        .ok_or_else(|| Error::Invalid("no recording available".into()))
+}
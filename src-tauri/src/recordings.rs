@@ -0,0 +1,299 @@
+//! Original audio of ideas recorded with the record shortcut, kept when the
+//! `keepRecordings` setting is on so they can be played back or transcribed
+//! again with another Whisper model.
+//!
+//! Recordings are 16 kHz mono FLAC files named after their idea under the
+//! app data dir. The `recordings` table holds their size; once the total
+//! passes `recordingQuotaMb`, the oldest go first. Rows go with their idea
+//! via `ON DELETE CASCADE` and [`Recordings::prune`] then removes the files.
+
+use std::path::PathBuf;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::ipc::Response;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::audio;
+use crate::db::{now_millis, Db};
+use crate::error::{Error, Result};
+use crate::ideas;
+use crate::recorder::Recorder;
+use crate::settings::SettingsStore;
+use crate::transcribe::{self, Transcriber, Transcript};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Recording {
+    /// Bytes on disk.
+    pub size: i64,
+    pub duration_ms: i64,
+    pub created_at: i64,
+}
+
+/// Audio encoded by [`encode`], ready to be saved.
+pub(crate) struct Encoded {
+    flac: Vec<u8>,
+    duration_ms: i64,
+}
+
+/// Encode 16 kHz mono `samples` as FLAC. Slow for long recordings, so do it
+/// before taking the database lock.
+pub(crate) fn encode(samples: &[f32]) -> Result<Encoded> {
+    Ok(Encoded {
+        flac: audio::encode_flac(samples, audio::WHISPER_SAMPLE_RATE)?,
+        duration_ms: samples.len() as i64 * 1000 / i64::from(audio::WHISPER_SAMPLE_RATE),
+    })
+}
+
+/// Managed state: the directory recordings are kept in.
+pub struct Recordings {
+    dir: PathBuf,
+}
+
+impl Recordings {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn path(&self, idea_id: &str) -> PathBuf {
+        self.dir.join(format!("{idea_id}.flac"))
+    }
+
+    /// Store `audio` as the idea's recording, replacing any it had.
+    pub(crate) fn save(
+        &self,
+        conn: &Connection,
+        idea_id: &str,
+        audio: &Encoded,
+    ) -> Result<Recording> {
+        if !ideas::exists(conn, idea_id)? {
+            return Err(Error::NotFound(idea_id.to_owned()));
+        }
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.path(idea_id);
+        let partial = path.with_extension("flac.part");
+        std::fs::write(&partial, &audio.flac)?;
+        std::fs::rename(&partial, &path)?;
+
+        let recording = Recording {
+            size: audio.flac.len() as i64,
+            duration_ms: audio.duration_ms,
+            created_at: now_millis(),
+        };
+        conn.execute(
+            "INSERT OR REPLACE INTO recordings (idea_id, size, duration_ms, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                idea_id,
+                recording.size,
+                recording.duration_ms,
+                recording.created_at
+            ],
+        )?;
+        Ok(recording)
+    }
+
+    /// Drop the oldest recordings until the rest fit in `quota_mb`; 0 sets
+    /// no limit. Returns the ideas whose recordings went; their files are
+    /// left to [`Self::prune`].
+    pub(crate) fn enforce_quota(&self, conn: &Connection, quota_mb: u32) -> Result<Vec<String>> {
+        let mut dropped = Vec::new();
+        if quota_mb == 0 {
+            return Ok(dropped);
+        }
+        let quota = i64::from(quota_mb) * 1024 * 1024;
+        let newest_first: Vec<(String, i64)> = conn
+            .prepare("SELECT idea_id, size FROM recordings ORDER BY created_at DESC")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        let mut total = 0;
+        for (idea_id, size) in newest_first {
+            total += size;
+            if total > quota {
+                conn.execute(
+                    "DELETE FROM recordings WHERE idea_id = ?1",
+                    params![idea_id],
+                )?;
+                dropped.push(idea_id);
+            }
+        }
+        Ok(dropped)
+    }
+
+    /// Delete files no recording row refers to any more. Returns how many
+    /// went.
+    pub(crate) fn prune(&self, conn: &Connection) -> Result<usize> {
+        let Ok(files) = std::fs::read_dir(&self.dir) else {
+            return Ok(0);
+        };
+        let mut stmt = conn.prepare("SELECT 1 FROM recordings WHERE idea_id = ?1")?;
+        let mut removed = 0;
+        for file in files.flatten() {
+            let name = file.file_name().to_string_lossy().into_owned();
+            if let Some(idea_id) = name.strip_suffix(".flac") {
+                if stmt.exists(params![idea_id])? {
+                    continue;
+                }
+            }
+            std::fs::remove_file(file.path())?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    fn read(&self, conn: &Connection, idea_id: &str) -> Result<Vec<u8>> {
+        let kept: Option<i64> = conn
+            .query_row(
+                "SELECT 1 FROM recordings WHERE idea_id = ?1",
+                params![idea_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if kept.is_none() {
+            return Err(Error::Invalid(format!(
+                "there is no recording for idea {idea_id}"
+            )));
+        }
+        Ok(std::fs::read(self.path(idea_id))?)
+    }
+}
+
+fn announce(app: &AppHandle, idea_id: String) {
+    crate::log_err(
+        "emit ideas change",
+        app.emit(ideas::CHANGED_EVENT, [idea_id]),
+    );
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+/// Keep the last recording with the idea saved from it, if `keepRecordings`
+/// was on when it started. Returns `None` when there is nothing to keep, or
+/// when the recording alone is larger than `recordingQuotaMb`.
+#[tauri::command]
+pub async fn keep_recording(
+    app: AppHandle,
+    recorder: State<'_, Recorder>,
+    id: String,
+) -> Result<Option<Recording>> {
+    let Some(samples) = recorder.take_kept() else {
+        return Ok(None);
+    };
+    tauri::async_runtime::spawn_blocking(move || {
+        let audio = encode(&samples)?;
+        let recordings = app.state::<Recordings>();
+        let db = app.state::<Db>();
+        let conn = db.conn();
+        let recording = recordings.save(&conn, &id, &audio)?;
+        let quota_mb = app.state::<SettingsStore>().get().recording_quota_mb;
+        let dropped = recordings.enforce_quota(&conn, quota_mb)?;
+        crate::log_err("prune recordings", recordings.prune(&conn));
+        if dropped.contains(&id) {
+            log::info!("Not keeping a recording larger than the {quota_mb} MB quota");
+            return Ok(None);
+        }
+        announce(&app, id);
+        Ok(Some(recording))
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("keeping the recording failed: {e}")))?
+}
+
+/// The idea's recording as FLAC bytes, for playback.
+#[tauri::command]
+pub async fn read_recording(
+    db: State<'_, Db>,
+    recordings: State<'_, Recordings>,
+    id: String,
+) -> Result<Response> {
+    recordings.read(&db.conn(), &id).map(Response::new)
+}
+
+#[tauri::command]
+pub fn delete_recording(
+    app: AppHandle,
+    db: State<'_, Db>,
+    recordings: State<'_, Recordings>,
+    id: String,
+) -> Result<()> {
+    let conn = db.conn();
+    conn.execute("DELETE FROM recordings WHERE idea_id = ?1", params![id])?;
+    recordings.prune(&conn)?;
+    announce(&app, id);
+    Ok(())
+}
+
+/// Transcribe the idea's recording again with `model`, e.g. a larger one
+/// than it was first transcribed with. The idea is left as it is; the
+/// caller decides whether to use the new text.
+#[tauri::command]
+pub async fn retranscribe_recording(
+    app: AppHandle,
+    id: String,
+    model: String,
+    language: Option<String>,
+) -> Result<Transcript> {
+    let name = transcribe::ggml_name(&model)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let flac = app
+            .state::<Recordings>()
+            .read(&app.state::<Db>().conn(), &id)?;
+        let samples = audio::decode_to_whisper(flac)?;
+        app.state::<Transcriber>()
+            .transcribe(&samples, &name, language.as_deref(), |_, _| {})
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("transcription failed: {e}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    #[test]
+    fn keeps_the_newest_recordings_within_the_quota() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "foreign_keys", true).unwrap();
+        crate::migrations::run(&mut conn, None).unwrap();
+        let dir = TestDir::new("recordings");
+        let recordings = Recordings::new(dir.to_path_buf());
+
+        let tone: Vec<f32> = (0..24_000).map(|i| (i as f32 / 10.0).sin() * 0.5).collect();
+        let old = ideas::create(&conn, "old", None).unwrap();
+        let new = ideas::create(&conn, "new", None).unwrap();
+        let tone = encode(&tone).unwrap();
+        let first = recordings.save(&conn, &old.id, &tone).unwrap();
+        assert_eq!(first.duration_ms, 1500);
+        recordings.save(&conn, &new.id, &tone).unwrap();
+        // Pretend each takes 600 KB, and the first is older.
+        conn.execute(
+            "UPDATE recordings SET size = 600 * 1024,
+               created_at = created_at - (idea_id = ?1)",
+            params![old.id],
+        )
+        .unwrap();
+
+        assert!(recordings.enforce_quota(&conn, 0).unwrap().is_empty());
+        assert_eq!(recordings.prune(&conn).unwrap(), 0, "0 sets no limit");
+
+        assert_eq!(
+            recordings.enforce_quota(&conn, 1).unwrap(),
+            [old.id.as_str()]
+        );
+        assert_eq!(recordings.prune(&conn).unwrap(), 1);
+        assert!(recordings.read(&conn, &old.id).is_err());
+        assert!(recordings
+            .read(&conn, &new.id)
+            .unwrap()
+            .starts_with(b"fLaC"));
+
+        ideas::delete(&conn, &new.id).unwrap();
+        assert_eq!(recordings.prune(&conn).unwrap(), 1);
+        assert!(matches!(
+            recordings.save(&conn, &new.id, &tone),
+            Err(Error::NotFound(_))
+        ));
+    }
+}
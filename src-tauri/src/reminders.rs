@@ -0,0 +1,313 @@
+//! Desktop notifications that bring ideas back: reminders set for a time,
+//! and old ideas resurfaced now and then.
+//!
+//! An idea's `remind_at` is announced once it passes and then cleared. With
+//! `resurfaceEnabled`, every `resurfaceEveryHours` a random unarchived idea
+//! older than `resurfaceAfterDays` is announced too. Its `resurfaced_at`
+//! keeps it from coming back until as many days have passed again.
+//!
+//! Clicking a notification shows the main window and emits [`FOCUS_EVENT`]
+//! with the idea's id, so the dashboard can scroll to it.
+
+use std::time::Duration;
+
+use chrono::{Local, TimeZone};
+use notify_rust::{Notification, NotificationResponse};
+use rusqlite::{params, Connection, OptionalExtension};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::app_lock::AppLock;
+use crate::db::{now_millis, Db};
+use crate::error::{Error, Result};
+use crate::ideas::{self, Idea, IDEA_COLUMNS};
+use crate::settings::SettingsStore;
+
+/// Emitted to the main window with an idea id when a notification is clicked.
+pub const FOCUS_EVENT: &str = "focus-idea";
+/// How often the scheduler looks for due reminders.
+const CHECK_EVERY: Duration = Duration::from_secs(30);
+const HOUR_MILLIS: i64 = 60 * 60 * 1000;
+const DAY_MILLIS: i64 = 24 * HOUR_MILLIS;
+/// Longest idea text shown in a notification, in characters.
+const PREVIEW_CHARS: usize = 140;
+
+/// Remind about the idea at `remind_at` (ms), or never when it is `None`.
+/// Not an edit, so `updated_at` is left alone. Ideas in the trash are not
+/// found.
+pub fn set(conn: &Connection, id: &str, remind_at: Option<i64>) -> Result<()> {
+    let changed = conn.execute(
+        "UPDATE ideas SET remind_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+        params![remind_at, id],
+    )?;
+    if changed == 0 {
+        return Err(Error::NotFound(id.to_owned()));
+    }
+    Ok(())
+}
+
+/// Ideas whose reminder is due at `now`, clearing it so each fires once.
+/// Reminders of ideas in the trash are cleared without firing.
+pub(crate) fn take_due(conn: &Connection, now: i64) -> Result<Vec<Idea>> {
+    let due = ideas::query_ideas(
+        conn,
+        &format!(
+            "SELECT {IDEA_COLUMNS} FROM ideas
+             WHERE remind_at <= ?1 AND deleted_at IS NULL
+             ORDER BY remind_at"
+        ),
+        params![now],
+    )?;
+    conn.execute(
+        "UPDATE ideas SET remind_at = NULL WHERE remind_at <= ?1",
+        params![now],
+    )?;
+    Ok(due)
+}
+
+/// A random unarchived idea older than `after_days` that has not been
+/// resurfaced in as long, if one is due `every_hours` after the last.
+/// Marks it as resurfaced at `now`.
+pub(crate) fn take_resurfaced(
+    conn: &Connection,
+    now: i64,
+    every_hours: u32,
+    after_days: u32,
+) -> Result<Option<Idea>> {
+    let last: Option<i64> =
+        conn.query_row("SELECT MAX(resurfaced_at) FROM ideas", [], |row| row.get(0))?;
+    if last.is_some_and(|at| now - at < i64::from(every_hours) * HOUR_MILLIS) {
+        return Ok(None);
+    }
+    let idea = conn
+        .query_row(
+            &format!(
+                "SELECT {IDEA_COLUMNS} FROM ideas
+                 WHERE archived = 0 AND deleted_at IS NULL AND created_at < ?1
+                   AND (resurfaced_at IS NULL OR resurfaced_at < ?1)
+                 ORDER BY random()
+                 LIMIT 1"
+            ),
+            params![now - i64::from(after_days) * DAY_MILLIS],
+            Idea::from_row,
+        )
+        .optional()?;
+    if let Some(idea) = &idea {
+        conn.execute(
+            "UPDATE ideas SET resurfaced_at = ?1 WHERE id = ?2",
+            params![now, idea.id],
+        )?;
+    }
+    Ok(idea)
+}
+
+/// The start of the idea's text on one line, cut to [`PREVIEW_CHARS`].
+fn preview(text: &str) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match flat.char_indices().nth(PREVIEW_CHARS) {
+        Some((cut, _)) => format!("{}…", flat[..cut].trim_end()),
+        None => flat,
+    }
+}
+
+/// Show the main window on the idea.
+fn focus(app: &AppHandle, id: &str) {
+    if let Some(window) = app.get_webview_window("main") {
+        crate::log_err("unminimize window", window.unminimize());
+        crate::log_err("show window", window.show());
+        crate::log_err("focus window", window.set_focus());
+    }
+    crate::log_err("emit focus idea", app.emit_to("main", FOCUS_EVENT, id));
+}
+
+/// Show a notification for `idea` and wait on its own thread for a click.
+/// While Glimt is locked, the idea's text is left out.
+fn announce(app: &AppHandle, summary: String, idea: &Idea) {
+    let body = if app.state::<AppLock>().is_locked() {
+        "Unlock Glimt to see it.".to_owned()
+    } else {
+        preview(&idea.text)
+    };
+    let app = app.clone();
+    let id = idea.id.clone();
+    let spawned = std::thread::Builder::new()
+        .name("glimt-notification".into())
+        .spawn(move || {
+            let shown = Notification::new()
+                .appname("Glimt")
+                .summary(&summary)
+                .body(&body)
+                // Linux only reports clicks on notifications with a default action.
+                .action("default", "Open")
+                .show();
+            let handle = match shown {
+                Ok(handle) => handle,
+                Err(e) => {
+                    log::warn!("Could not show a notification: {e}");
+                    return;
+                }
+            };
+            let waited = handle.wait_for_response(|response: &NotificationResponse| {
+                let clicked = match response {
+                    NotificationResponse::Default => true,
+                    NotificationResponse::Action(key) => key == "default",
+                    _ => false,
+                };
+                if clicked {
+                    focus(&app, &id);
+                }
+            });
+            crate::log_err("wait for notification", waited);
+        });
+    crate::log_err("show notification", spawned);
+}
+
+/// Announce due reminders and, when it is time, a resurfaced idea. Waits
+/// while the database is locked.
+fn run_due(app: &AppHandle) -> Result<()> {
+    let Some(db) = app.try_state::<Db>() else {
+        return Ok(());
+    };
+    let settings = app.state::<SettingsStore>().get();
+    let now = now_millis();
+    let (due, resurfaced) = {
+        let conn = db.conn();
+        let due = take_due(&conn, now)?;
+        let resurfaced = if settings.resurface_enabled {
+            take_resurfaced(
+                &conn,
+                now,
+                settings.resurface_every_hours,
+                settings.resurface_after_days,
+            )?
+        } else {
+            None
+        };
+        (due, resurfaced)
+    };
+    if !due.is_empty() {
+        let ids: Vec<String> = due.iter().map(|idea| idea.id.clone()).collect();
+        crate::log_err("emit ideas change", app.emit(ideas::CHANGED_EVENT, ids));
+    }
+    // Titles give as much away as the text, so neither shows while locked.
+    let locked = app.state::<AppLock>().is_locked();
+    for idea in &due {
+        let summary = idea
+            .title
+            .clone()
+            .filter(|_| !locked)
+            .unwrap_or_else(|| "Reminder".into());
+        announce(app, summary, idea);
+    }
+    if let Some(idea) = resurfaced {
+        let date = Local
+            .timestamp_millis_opt(idea.created_at)
+            .single()
+            .map(|at| at.format("%-d %B %Y").to_string())
+            .unwrap_or_default();
+        announce(app, format!("From your ideas, {date}"), &idea);
+    }
+    Ok(())
+}
+
+/// Start the scheduler thread.
+pub fn start(app: &AppHandle) {
+    #[cfg(target_os = "macos")]
+    if let Err(e) = notify_rust::set_application(&app.config().identifier) {
+        log::warn!("Notifications will not show as Glimt: {e:?}");
+    }
+    let app = app.clone();
+    let spawned = std::thread::Builder::new()
+        .name("glimt-reminders".into())
+        .spawn(move || loop {
+            crate::log_err("reminders", run_due(&app));
+            std::thread::sleep(CHECK_EVERY);
+        });
+    crate::log_err("start reminders", spawned);
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+#[tauri::command]
+pub fn set_reminder(db: State<'_, Db>, id: String, remind_at: Option<i64>) -> Result<()> {
+    set(&db.conn(), &id, remind_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        crate::migrations::run(&mut conn, None).unwrap();
+        conn
+    }
+
+    #[test]
+    fn due_reminders_fire_once() {
+        let conn = open();
+        let now = now_millis();
+        let due = ideas::create(&conn, "call back", None).unwrap();
+        let later = ideas::create(&conn, "renew passport", None).unwrap();
+        let trashed = ideas::create(&conn, "never mind", None).unwrap();
+        set(&conn, &due.id, Some(now - 1)).unwrap();
+        set(&conn, &later.id, Some(now + HOUR_MILLIS)).unwrap();
+        set(&conn, &trashed.id, Some(now - 1)).unwrap();
+        crate::trash::trash(&conn, &trashed.id).unwrap();
+
+        let fired: Vec<String> = take_due(&conn, now)
+            .unwrap()
+            .into_iter()
+            .map(|idea| idea.id)
+            .collect();
+        assert_eq!(fired, [due.id.as_str()]);
+        assert!(take_due(&conn, now).unwrap().is_empty());
+        assert_eq!(ideas::get(&conn, &due.id).unwrap().unwrap().remind_at, None);
+        assert_eq!(
+            ideas::get(&conn, &later.id).unwrap().unwrap().remind_at,
+            Some(now + HOUR_MILLIS)
+        );
+        assert!(matches!(
+            set(&conn, "missing", None),
+            Err(Error::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn resurfaces_old_ideas_on_schedule() {
+        let conn = open();
+        let now = now_millis();
+        let old = ideas::create(&conn, "old", None).unwrap();
+        let archived = ideas::create(&conn, "archived", None).unwrap();
+        ideas::create(&conn, "new", None).unwrap();
+        conn.execute(
+            "UPDATE ideas SET created_at = created_at - 40 * ?1 WHERE id IN (?2, ?3)",
+            params![DAY_MILLIS, old.id, archived.id],
+        )
+        .unwrap();
+        ideas::set_archived(&conn, &archived.id, true).unwrap();
+
+        let first = take_resurfaced(&conn, now, 24, 30).unwrap();
+        assert_eq!(first.map(|idea| idea.id), Some(old.id.clone()));
+        assert!(
+            take_resurfaced(&conn, now + HOUR_MILLIS, 24, 30)
+                .unwrap()
+                .is_none(),
+            "not due again yet"
+        );
+        assert!(
+            take_resurfaced(&conn, now + 2 * DAY_MILLIS, 24, 30)
+                .unwrap()
+                .is_none(),
+            "the only candidate was just resurfaced"
+        );
+    }
+
+    #[test]
+    fn previews_fit_on_one_line() {
+        assert_eq!(preview("buy\n\n  milk "), "buy milk");
+        let long = "word ".repeat(50);
+        let cut = preview(&long);
+        assert!(cut.ends_with('…'));
+        assert_eq!(cut.chars().count(), PREVIEW_CHARS);
+    }
+}
@@ -0,0 +1,243 @@
+//! Earlier versions of ideas, so an accidental edit can be undone.
+//!
+//! [`ideas::update`] calls [`record`] before it writes, which keeps the text
+//! and title being replaced as a revision dated when they were saved. Only
+//! the newest [`MAX_REVISIONS`] per idea are kept, and they go with their
+//! idea via `ON DELETE CASCADE`. Restoring is itself an update, so it can be
+//! undone the same way.
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use similar::{ChangeTag, TextDiff};
+use tauri::State;
+
+use crate::db::Db;
+use crate::error::{Error, Result};
+use crate::ideas::{self, Idea, IdeaUpdate};
+
+const MAX_REVISIONS: i64 = 100;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Revision {
+    pub id: i64,
+    pub text: String,
+    pub title: Option<String>,
+    /// When this version was saved, i.e. the idea's `updated_at` back then.
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Change {
+    Equal,
+    Insert,
+    Delete,
+}
+
+/// A run of words in a diff, with whether it was kept, added or removed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiffPart {
+    pub text: String,
+    pub change: Change,
+}
+
+/// Keep the idea's current text and title as a revision if `updates`
+/// would change them. Call before applying the update.
+pub(crate) fn record(conn: &Connection, idea_id: &str, updates: &IdeaUpdate) -> Result<()> {
+    let recorded = conn.execute(
+        "INSERT INTO idea_revisions (idea_id, text, title, created_at)
+         SELECT id, text, title, updated_at FROM ideas
+         WHERE id = ?1
+           AND (text IS NOT COALESCE(?2, text) OR (?3 AND title IS NOT ?4))",
+        params![
+            idea_id,
+            updates.text,
+            updates.title.is_some(),
+            updates.title.clone().flatten()
+        ],
+    )?;
+    if recorded > 0 {
+        conn.execute(
+            "DELETE FROM idea_revisions
+             WHERE idea_id = ?1 AND id NOT IN (
+               SELECT id FROM idea_revisions WHERE idea_id = ?1 ORDER BY id DESC LIMIT ?2
+             )",
+            params![idea_id, MAX_REVISIONS],
+        )?;
+    }
+    Ok(())
+}
+
+/// The idea's revisions, newest first.
+pub fn list(conn: &Connection, idea_id: &str) -> Result<Vec<Revision>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, text, title, created_at FROM idea_revisions
+         WHERE idea_id = ?1 ORDER BY id DESC",
+    )?;
+    let rows = stmt.query_map(params![idea_id], from_row)?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+fn from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Revision> {
+    Ok(Revision {
+        id: row.get(0)?,
+        text: row.get(1)?,
+        title: row.get(2)?,
+        created_at: row.get(3)?,
+    })
+}
+
+fn get(conn: &Connection, idea_id: &str, id: i64) -> Result<Revision> {
+    conn.query_row(
+        "SELECT id, text, title, created_at FROM idea_revisions
+         WHERE idea_id = ?1 AND id = ?2",
+        params![idea_id, id],
+        from_row,
+    )
+    .optional()?
+    .ok_or_else(|| Error::Invalid(format!("there is no revision {id} of idea {idea_id}")))
+}
+
+/// Word-level diff from `old` to `new`. Neighbouring words with the same
+/// change are merged into one part.
+pub fn diff(old: &str, new: &str) -> Vec<DiffPart> {
+    let mut parts: Vec<DiffPart> = Vec::new();
+    for change in TextDiff::from_words(old, new).iter_all_changes() {
+        let kind = match change.tag() {
+            ChangeTag::Equal => Change::Equal,
+            ChangeTag::Insert => Change::Insert,
+            ChangeTag::Delete => Change::Delete,
+        };
+        match parts.last_mut() {
+            Some(last) if last.change == kind => last.text.push_str(change.value()),
+            _ => parts.push(DiffPart {
+                text: change.value().to_owned(),
+                change: kind,
+            }),
+        }
+    }
+    parts
+}
+
+/// Put an earlier version back. The version it replaces becomes a revision
+/// in turn.
+pub fn restore(conn: &Connection, idea_id: &str, id: i64) -> Result<Idea> {
+    let revision = get(conn, idea_id, id)?;
+    ideas::update(
+        conn,
+        idea_id,
+        &IdeaUpdate {
+            text: Some(revision.text),
+            title: Some(revision.title),
+        },
+    )?;
+    ideas::get(conn, idea_id)?.ok_or_else(|| Error::NotFound(idea_id.to_owned()))
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+#[tauri::command]
+pub fn list_revisions(db: State<'_, Db>, id: String) -> Result<Vec<Revision>> {
+    list(&db.conn(), &id)
+}
+
+/// Diff between two versions of an idea's text. A missing `to` compares
+/// with the idea as it is now.
+#[tauri::command]
+pub fn diff_revisions(
+    db: State<'_, Db>,
+    id: String,
+    from: i64,
+    to: Option<i64>,
+) -> Result<Vec<DiffPart>> {
+    let conn = db.conn();
+    let old = get(&conn, &id, from)?.text;
+    let new = match to {
+        Some(to) => get(&conn, &id, to)?.text,
+        None => {
+            ideas::get(&conn, &id)?
+                .ok_or_else(|| Error::NotFound(id.clone()))?
+                .text
+        }
+    };
+    Ok(diff(&old, &new))
+}
+
+#[tauri::command]
+pub fn restore_revision(db: State<'_, Db>, id: String, revision: i64) -> Result<Idea> {
+    restore(&db.conn(), &id, revision)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        crate::migrations::run(&mut conn, None).unwrap();
+        conn
+    }
+
+    fn edit(text: &str) -> IdeaUpdate {
+        IdeaUpdate {
+            text: Some(text.into()),
+            title: None,
+        }
+    }
+
+    #[test]
+    fn keeps_replaced_versions_and_restores_them() {
+        let conn = open();
+        let idea = ideas::create(&conn, "first draft", None).unwrap();
+        ideas::update(&conn, &idea.id, &edit("second draft")).unwrap();
+        ideas::update(&conn, &idea.id, &edit("second draft")).unwrap();
+        let titled = IdeaUpdate {
+            text: None,
+            title: Some(Some("Drafts".into())),
+        };
+        ideas::update(&conn, &idea.id, &titled).unwrap();
+
+        let revisions = list(&conn, &idea.id).unwrap();
+        let versions: Vec<(&str, Option<&str>)> = revisions
+            .iter()
+            .map(|r| (r.text.as_str(), r.title.as_deref()))
+            .collect();
+        assert_eq!(
+            versions,
+            [("second draft", None), ("first draft", None)],
+            "unchanged saves add nothing"
+        );
+        assert_eq!(revisions[1].created_at, idea.updated_at);
+
+        let restored = restore(&conn, &idea.id, revisions[1].id).unwrap();
+        assert_eq!(restored.text, "first draft");
+        assert_eq!(restored.title, None);
+        let latest = &list(&conn, &idea.id).unwrap()[0];
+        assert_eq!(
+            (latest.text.as_str(), latest.title.as_deref()),
+            ("second draft", Some("Drafts")),
+            "restoring can be undone"
+        );
+
+        let other = ideas::create(&conn, "other", None).unwrap();
+        assert!(restore(&conn, &other.id, revisions[1].id).is_err());
+    }
+
+    #[test]
+    fn diffs_by_word() {
+        let parts = diff("buy milk and eggs", "buy oat milk and bread");
+        let rendered: Vec<(Change, &str)> =
+            parts.iter().map(|p| (p.change, p.text.as_str())).collect();
+        assert_eq!(
+            rendered,
+            [
+                (Change::Equal, "buy "),
+                (Change::Insert, "oat "),
+                (Change::Equal, "milk and "),
+                (Change::Delete, "eggs"),
+                (Change::Insert, "bread"),
+            ]
+        );
+    }
+}
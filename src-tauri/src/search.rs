@@ -0,0 +1,453 @@
+//! Hybrid search: FTS5 BM25 and vector similarity ranked separately, then
+//! merged with reciprocal-rank fusion so neither score scale dominates.
+
+use std::collections::HashMap;
+
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::db::Db;
+use crate::error::{Error, Result};
+use crate::ideas::{self, Idea, IDEA_COLUMNS};
+use crate::vector_index::VectorIndex;
+
+/// Candidates taken from each ranking before fusion.
+const CANDIDATES: usize = 200;
+/// Damping constant from the original RRF paper; keeps one list's top hit
+/// from drowning out an idea that ranks well in both.
+const RRF_K: f64 = 60.0;
+/// Same cut-off the semantic-only search used.
+const MIN_SIMILARITY: f32 = 0.3;
+const DEFAULT_LIMIT: usize = 50;
+
+// `snippet()` wraps matches in these, and `SnippetPart` splits on them, so
+// idea text never has to be escaped or parsed as markup.
+const MATCH_START: char = '\u{2}';
+const MATCH_END: char = '\u{3}';
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFilters {
+    pub archived: Option<bool>,
+    /// Inclusive lower bound on `created_at` (ms).
+    pub created_after: Option<i64>,
+    /// Exclusive upper bound on `created_at` (ms).
+    pub created_before: Option<i64>,
+    /// Only ideas with this tag.
+    pub tag: Option<String>,
+    /// Only ideas captured from this app.
+    pub source_app: Option<String>,
+}
+
+impl SearchFilters {
+    fn matches(&self, idea: &Idea) -> bool {
+        self.archived.map_or(true, |a| idea.archived == a)
+            && self.created_after.map_or(true, |t| idea.created_at >= t)
+            && self.created_before.map_or(true, |t| idea.created_at < t)
+            && self
+                .tag
+                .as_ref()
+                .map_or(true, |tag| idea.tags.contains(tag))
+            && self
+                .source_app
+                .as_ref()
+                .map_or(true, |app| idea.source_app.as_ref() == Some(app))
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchRequest {
+    pub query: String,
+    /// Query embedding; without it only full-text results are returned.
+    pub vector: Option<Vec<f32>>,
+    pub model: Option<String>,
+    #[serde(default)]
+    pub filters: SearchFilters,
+    #[serde(default)]
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    Fts,
+    Semantic,
+    Both,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SnippetPart {
+    pub text: String,
+    pub highlight: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub idea: Idea,
+    pub score: f64,
+    pub source: Source,
+    pub snippet: Option<Vec<SnippetPart>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchPage {
+    pub results: Vec<SearchHit>,
+    /// Fused hits across all pages.
+    pub total: usize,
+}
+
+/// Turn free text into an FTS5 query that cannot be a syntax error: every
+/// word becomes a quoted prefix term, and all of them must match.
+pub(crate) fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|word| word.replace('"', ""))
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{word}\"*"))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+fn parse_snippet(raw: &str) -> Vec<SnippetPart> {
+    let mut parts = Vec::new();
+    let mut highlight = false;
+    let mut current = String::new();
+    for c in raw.chars() {
+        if c == MATCH_START || c == MATCH_END {
+            if !current.is_empty() {
+                parts.push(SnippetPart {
+                    text: std::mem::take(&mut current),
+                    highlight,
+                });
+            }
+            highlight = c == MATCH_START;
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        parts.push(SnippetPart {
+            text: current,
+            highlight,
+        });
+    }
+    parts
+}
+
+/// BM25-ranked full-text matches, best first, with highlighted snippets.
+fn fts_ranked(
+    conn: &Connection,
+    query: &str,
+    filters: &SearchFilters,
+) -> Result<Vec<(Idea, Vec<SnippetPart>)>> {
+    let Some(query) = fts_query(query) else {
+        return Ok(Vec::new());
+    };
+    let sql = format!(
+        "SELECT {IDEA_COLUMNS}, snippet(fts_ideas, -1, char(2), char(3), '…', 16)
+         FROM fts_ideas
+         JOIN ideas ON ideas.rowid = fts_ideas.rowid
+         WHERE fts_ideas MATCH ?1
+           AND ideas.deleted_at IS NULL
+           AND (?2 IS NULL OR ideas.archived = ?2)
+           AND (?3 IS NULL OR ideas.created_at >= ?3)
+           AND (?4 IS NULL OR ideas.created_at < ?4)
+           AND (?5 IS NULL OR EXISTS (
+             SELECT 1 FROM idea_tags JOIN tags ON tags.id = idea_tags.tag_id
+             WHERE idea_tags.idea_id = ideas.id AND tags.name = ?5
+           ))
+           AND (?6 IS NULL OR ideas.source_app = ?6)
+         ORDER BY bm25(fts_ideas)
+         LIMIT ?7"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(
+        params![
+            query,
+            filters.archived.map(i64::from),
+            filters.created_after,
+            filters.created_before,
+            filters.tag,
+            filters.source_app,
+            CANDIDATES as i64
+        ],
+        |row| Ok((Idea::from_row(row)?, row.get::<_, String>(14)?)),
+    )?;
+    rows.map(|row| {
+        let (idea, snippet) = row?;
+        Ok((idea, parse_snippet(&snippet)))
+    })
+    .collect()
+}
+
+/// Vector matches above the similarity cut-off, best first.
+fn semantic_ranked(
+    conn: &Connection,
+    index: &VectorIndex,
+    model: &str,
+    vector: &[f32],
+    filters: &SearchFilters,
+) -> Result<Vec<Idea>> {
+    let neighbours: Vec<_> = index
+        .search(conn, model, vector, CANDIDATES)?
+        .into_iter()
+        .filter(|n| n.score >= MIN_SIMILARITY)
+        .collect();
+    let ids: Vec<String> = neighbours.iter().map(|n| n.idea_id.clone()).collect();
+    let mut by_id: HashMap<String, Idea> = ideas::get_many(conn, &ids)?
+        .into_iter()
+        .map(|idea| (idea.id.clone(), idea))
+        .collect();
+    Ok(neighbours
+        .iter()
+        .filter_map(|n| by_id.remove(&n.idea_id))
+        .filter(|idea| filters.matches(idea))
+        .collect())
+}
+
+struct Fused {
+    idea: Idea,
+    score: f64,
+    fts: bool,
+    semantic: bool,
+    snippet: Option<Vec<SnippetPart>>,
+}
+
+fn fuse(fts: Vec<(Idea, Vec<SnippetPart>)>, semantic: Vec<Idea>) -> Vec<SearchHit> {
+    let mut fused: HashMap<String, Fused> = HashMap::new();
+    for (rank, (idea, snippet)) in fts.into_iter().enumerate() {
+        fused.insert(
+            idea.id.clone(),
+            Fused {
+                idea,
+                score: 1.0 / (RRF_K + rank as f64 + 1.0),
+                fts: true,
+                semantic: false,
+                snippet: Some(snippet),
+            },
+        );
+    }
+    for (rank, idea) in semantic.into_iter().enumerate() {
+        let score = 1.0 / (RRF_K + rank as f64 + 1.0);
+        fused
+            .entry(idea.id.clone())
+            .and_modify(|hit| {
+                hit.score += score;
+                hit.semantic = true;
+            })
+            .or_insert(Fused {
+                idea,
+                score,
+                fts: false,
+                semantic: true,
+                snippet: None,
+            });
+    }
+
+    let mut hits: Vec<SearchHit> = fused
+        .into_values()
+        .map(|hit| SearchHit {
+            source: match (hit.fts, hit.semantic) {
+                (true, true) => Source::Both,
+                (true, false) => Source::Fts,
+                _ => Source::Semantic,
+            },
+            idea: hit.idea,
+            score: hit.score,
+            snippet: hit.snippet,
+        })
+        .collect();
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.idea.created_at.cmp(&a.idea.created_at))
+    });
+    hits
+}
+
+pub fn hybrid(
+    conn: &Connection,
+    index: &VectorIndex,
+    request: &SearchRequest,
+) -> Result<SearchPage> {
+    if request.query.trim().is_empty() {
+        return Ok(SearchPage {
+            results: Vec::new(),
+            total: 0,
+        });
+    }
+    let fts = fts_ranked(conn, &request.query, &request.filters)?;
+    let semantic = match (&request.vector, &request.model) {
+        (Some(vector), Some(model)) => {
+            semantic_ranked(conn, index, model, vector, &request.filters)?
+        }
+        (Some(_), None) => {
+            return Err(Error::Invalid("a query vector needs its model".into()));
+        }
+        _ => Vec::new(),
+    };
+
+    let hits = fuse(fts, semantic);
+    let total = hits.len();
+    let results = hits
+        .into_iter()
+        .skip(request.offset)
+        .take(request.limit.unwrap_or(DEFAULT_LIMIT))
+        .collect();
+    Ok(SearchPage { results, total })
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+/// Runs off the main thread: the first semantic query per model may have to
+/// build the vector index.
+#[tauri::command]
+pub async fn hybrid_search(app: AppHandle, request: SearchRequest) -> Result<SearchPage> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = app.state::<Db>();
+        let index = app.state::<VectorIndex>();
+        let conn = db.conn();
+        hybrid(&conn, &index, &request)
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("search failed: {e}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embeddings;
+
+    fn setup() -> (Connection, VectorIndex) {
+        let mut conn = Connection::open_in_memory().unwrap();
+        crate::migrations::run(&mut conn, None).unwrap();
+        let dir = std::env::temp_dir().join(format!("glimt-search-{}", uuid::Uuid::new_v4()));
+        (conn, VectorIndex::new(dir))
+    }
+
+    fn request(query: &str) -> SearchRequest {
+        SearchRequest {
+            query: query.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sanitizes_free_text_into_prefix_terms() {
+        assert_eq!(
+            fts_query("quarterly \"plan\" AND"),
+            Some("\"quarterly\"* \"plan\"* \"AND\"*".into())
+        );
+        assert_eq!(fts_query("  \"\" "), None);
+    }
+
+    #[test]
+    fn splits_snippets_on_match_markers() {
+        assert_eq!(
+            parse_snippet("a \u{2}b\u{3} c"),
+            vec![
+                SnippetPart {
+                    text: "a ".into(),
+                    highlight: false
+                },
+                SnippetPart {
+                    text: "b".into(),
+                    highlight: true
+                },
+                SnippetPart {
+                    text: " c".into(),
+                    highlight: false
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn fuses_both_rankings_and_marks_sources() {
+        let (conn, index) = setup();
+        let both = ideas::create(&conn, "pricing page for the launch", None).unwrap();
+        let keyword = ideas::create(&conn, "pricing spreadsheet", None).unwrap();
+        let meaning = ideas::create(&conn, "what should we charge customers", None).unwrap();
+        embeddings::store(&conn, &both.id, "m", &[1.0, 0.1]).unwrap();
+        embeddings::store(&conn, &keyword.id, "m", &[0.0, 1.0]).unwrap();
+        embeddings::store(&conn, &meaning.id, "m", &[0.9, 0.2]).unwrap();
+
+        let page = hybrid(
+            &conn,
+            &index,
+            &SearchRequest {
+                vector: Some(vec![1.0, 0.0]),
+                model: Some("m".into()),
+                ..request("pric")
+            },
+        )
+        .unwrap();
+
+        assert_eq!(page.total, 3);
+        assert_eq!(page.results[0].idea.id, both.id);
+        assert_eq!(page.results[0].source, Source::Both);
+        let source_of = |id: &str| {
+            page.results
+                .iter()
+                .find(|h| h.idea.id == id)
+                .unwrap()
+                .source
+        };
+        assert_eq!(source_of(&keyword.id), Source::Fts);
+        assert_eq!(source_of(&meaning.id), Source::Semantic);
+        assert!(page.results[0]
+            .snippet
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|p| p.highlight && p.text == "pricing"));
+    }
+
+    #[test]
+    fn applies_filters_and_pagination() {
+        let (conn, index) = setup();
+        for i in 0..5 {
+            ideas::create(&conn, &format!("note number {i}"), None).unwrap();
+        }
+        let archived = ideas::create(&conn, "archived note", None).unwrap();
+        ideas::set_archived(&conn, &archived.id, true).unwrap();
+
+        let active = SearchRequest {
+            filters: SearchFilters {
+                archived: Some(false),
+                ..Default::default()
+            },
+            offset: 3,
+            limit: Some(10),
+            ..request("note")
+        };
+        let page = hybrid(&conn, &index, &active).unwrap();
+        assert_eq!(page.total, 5);
+        assert_eq!(page.results.len(), 2);
+        assert!(page.results.iter().all(|h| !h.idea.archived));
+
+        let future = SearchRequest {
+            filters: SearchFilters {
+                created_after: Some(i64::MAX),
+                ..Default::default()
+            },
+            ..request("note")
+        };
+        assert_eq!(hybrid(&conn, &index, &future).unwrap().total, 0);
+
+        ideas::create(&conn, "tagged note #work", None).unwrap();
+        let tagged = SearchRequest {
+            filters: SearchFilters {
+                tag: Some("work".into()),
+                ..Default::default()
+            },
+            ..request("note")
+        };
+        assert_eq!(hybrid(&conn, &index, &tagged).unwrap().total, 1);
+    }
+}
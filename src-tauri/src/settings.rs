@@ -0,0 +1,333 @@
+//! User preferences kept by Rust in `settings.json`, so all three windows
+//! and the Rust side read the same values, and clearing a webview profile
+//! no longer resets them.
+//!
+//! The file carries a schema `version`. Older files are migrated when they
+//! are loaded. A file written by a newer Glimt is read as far as this build
+//! understands it but never overwritten, so a downgrade cannot lose settings.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::backup::BackupSchedule;
+use crate::clip::ClipMode;
+use crate::error::{Error, Result};
+use crate::shortcuts::ShortcutConfig;
+
+const FILE_NAME: &str = "settings.json";
+/// Bump together with a new step in [`migrate`].
+pub const SETTINGS_VERSION: u32 = 1;
+/// Emitted to every window with the full settings after any change.
+pub const CHANGED_EVENT: &str = "settings-changed";
+/// Fields `set_settings` leaves alone: shortcuts have to be registered
+/// through `set_shortcut` before they are saved.
+const MANAGED_FIELDS: [&str; 2] = ["version", "shortcuts"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Light,
+    Dark,
+    #[default]
+    System,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Settings {
+    pub version: u32,
+    pub theme: Theme,
+    /// Write each saved idea as Markdown into `export_dir`.
+    pub export_enabled: bool,
+    pub export_dir: Option<String>,
+    /// Whisper model id, e.g. `"Xenova/whisper-base"`.
+    pub stt_model: Option<String>,
+    pub auto_title_enabled: bool,
+    pub shortcuts: ShortcutConfig,
+    /// Input device name; `None` follows the system default.
+    pub input_device: Option<String>,
+    pub backup_schedule: BackupSchedule,
+    /// How many scheduled backups to keep; at least one.
+    pub backup_keep: u32,
+    /// Lock the app after this many idle minutes; 0 never does.
+    pub auto_lock_minutes: u32,
+    /// What the clip shortcut does with the copied text.
+    pub clip_mode: ClipMode,
+    /// Keep the audio of recordings made with the record shortcut.
+    pub keep_recordings: bool,
+    /// Space kept recordings may take, in megabytes; 0 sets no limit.
+    pub recording_quota_mb: u32,
+    /// Days ideas stay in the trash before they are purged; 0 keeps them
+    /// until it is emptied.
+    pub trash_days: u32,
+    /// Now and then, show a notification with an old idea.
+    pub resurface_enabled: bool,
+    /// Hours between resurfaced ideas; at least one.
+    pub resurface_every_hours: u32,
+    /// Only ideas at least this many days old are resurfaced.
+    pub resurface_after_days: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            theme: Theme::default(),
+            export_enabled: false,
+            export_dir: None,
+            stt_model: None,
+            auto_title_enabled: false,
+            shortcuts: ShortcutConfig::default(),
+            input_device: None,
+            backup_schedule: BackupSchedule::default(),
+            backup_keep: 7,
+            auto_lock_minutes: 0,
+            clip_mode: ClipMode::default(),
+            keep_recordings: false,
+            recording_quota_mb: 500,
+            trash_days: 30,
+            resurface_enabled: false,
+            resurface_every_hours: 24,
+            resurface_after_days: 30,
+        }
+    }
+}
+
+impl Settings {
+    /// Empty strings from the webview mean "not set".
+    fn normalize(&mut self) {
+        for value in [
+            &mut self.export_dir,
+            &mut self.stt_model,
+            &mut self.input_device,
+        ] {
+            if value.as_deref().is_some_and(str::is_empty) {
+                *value = None;
+            }
+        }
+        self.backup_keep = self.backup_keep.max(1);
+        self.resurface_every_hours = self.resurface_every_hours.max(1);
+    }
+}
+
+fn encode_err(e: serde_json::Error) -> Error {
+    Error::Invalid(format!("could not encode settings: {e}"))
+}
+
+/// Upgrade a file written by an older schema, one version at a time.
+fn migrate(value: &mut Value, from: u64) {
+    // Files without a version predate the field but already match version 1.
+    // Later schema changes add their step here as `if from < 2 { ... }`.
+    if from < u64::from(SETTINGS_VERSION) {
+        log::info!("Migrating settings from version {from} to {SETTINGS_VERSION}");
+    }
+    if let Some(fields) = value.as_object_mut() {
+        fields.insert("version".into(), SETTINGS_VERSION.into());
+    }
+}
+
+fn parse(raw: &str) -> Result<Settings> {
+    let mut value: Value = serde_json::from_str(raw).map_err(encode_err)?;
+    let version = value.get("version").and_then(Value::as_u64).unwrap_or(0);
+    if version <= u64::from(SETTINGS_VERSION) {
+        migrate(&mut value, version);
+    }
+    let mut settings: Settings = serde_json::from_value(value).map_err(encode_err)?;
+    settings.normalize();
+    Ok(settings)
+}
+
+fn load(path: &Path) -> Option<Settings> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    match parse(&raw) {
+        Ok(settings) => {
+            if settings.version > SETTINGS_VERSION {
+                log::warn!(
+                    "{} is from a newer version ({}); changes will not be saved",
+                    path.display(),
+                    settings.version
+                );
+            }
+            Some(settings)
+        }
+        Err(e) => {
+            log::warn!("Ignoring unreadable {}: {e}", path.display());
+            Some(Settings::default())
+        }
+    }
+}
+
+fn save(path: &Path, settings: &Settings) -> Result<()> {
+    if settings.version > SETTINGS_VERSION {
+        return Err(Error::Invalid(
+            "settings were saved by a newer version of Glimt; update to change them".into(),
+        ));
+    }
+    let json = serde_json::to_string_pretty(settings).map_err(encode_err)?;
+    std::fs::write(path, json)?;
+    Ok(())
+}
+
+/// Apply a partial update from the webview. Unknown keys and
+/// [`MANAGED_FIELDS`] are rejected rather than silently dropped.
+fn apply_patch(settings: &Settings, patch: Map<String, Value>) -> Result<Settings> {
+    let mut value = serde_json::to_value(settings).map_err(encode_err)?;
+    let fields = value
+        .as_object_mut()
+        .ok_or_else(|| Error::Invalid("settings are not an object".into()))?;
+    for (key, new) in patch {
+        if MANAGED_FIELDS.contains(&key.as_str()) {
+            return Err(Error::Invalid(format!("{key} cannot be changed here")));
+        }
+        let Some(slot) = fields.get_mut(&key) else {
+            return Err(Error::Invalid(format!("unknown setting: {key}")));
+        };
+        *slot = new;
+    }
+    let mut next: Settings = serde_json::from_value(value)
+        .map_err(|e| Error::Invalid(format!("invalid settings: {e}")))?;
+    next.normalize();
+    Ok(next)
+}
+
+/// Managed state: the settings file and its current contents.
+pub struct SettingsStore {
+    path: PathBuf,
+    settings: Mutex<Settings>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl SettingsStore {
+    pub fn new(config_dir: &Path) -> Self {
+        let path = config_dir.join(FILE_NAME);
+        let settings = load(&path).unwrap_or_else(|| {
+            let defaults = Settings::default();
+            if let Err(e) = save(&path, &defaults) {
+                log::warn!("Could not write {}: {e}", path.display());
+            }
+            defaults
+        });
+        Self {
+            path,
+            settings: Mutex::new(settings),
+        }
+    }
+
+    pub fn get(&self) -> Settings {
+        lock(&self.settings).clone()
+    }
+
+    /// Change settings with `f`, save them and tell every window.
+    pub fn update(&self, app: &AppHandle, f: impl FnOnce(&mut Settings)) -> Result<Settings> {
+        self.modify(app, |current| {
+            let mut next = current.clone();
+            f(&mut next);
+            Ok(next)
+        })
+    }
+
+    /// Derive new settings from the current ones and save them. The event
+    /// goes out after the lock is released, since Rust listeners such as
+    /// the vault watcher read the settings again.
+    fn modify(
+        &self,
+        app: &AppHandle,
+        f: impl FnOnce(&Settings) -> Result<Settings>,
+    ) -> Result<Settings> {
+        let changed = {
+            let mut current = lock(&self.settings);
+            let mut next = f(&current)?;
+            next.normalize();
+            if next == *current {
+                None
+            } else {
+                save(&self.path, &next)?;
+                *current = next.clone();
+                Some(next)
+            }
+        };
+        match changed {
+            Some(settings) => {
+                crate::log_err("emit settings change", app.emit(CHANGED_EVENT, &settings));
+                Ok(settings)
+            }
+            None => Ok(self.get()),
+        }
+    }
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_settings(store: State<'_, SettingsStore>) -> Settings {
+    store.get()
+}
+
+/// Merge `patch` (camelCase keys, e.g. `{"theme": "dark"}`) into the
+/// settings and return the result.
+#[tauri::command]
+pub fn set_settings(
+    app: AppHandle,
+    store: State<'_, SettingsStore>,
+    patch: Map<String, Value>,
+) -> Result<Settings> {
+    store.modify(&app, |current| apply_patch(current, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+    use serde_json::json;
+
+    fn patch(value: Value) -> Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn unversioned_files_are_migrated_and_filled_in() {
+        let settings = parse(r#"{"theme":"dark","exportDir":""}"#).unwrap();
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.theme, Theme::Dark);
+        assert_eq!(settings.export_dir, None);
+        assert_eq!(settings.shortcuts.record, ShortcutConfig::default().record);
+    }
+
+    #[test]
+    fn newer_files_are_kept_but_not_saved() {
+        let settings = parse(r#"{"version":99,"theme":"light","futureField":1}"#).unwrap();
+        assert_eq!(settings.version, 99);
+        assert_eq!(settings.theme, Theme::Light);
+        let dir = TestDir::new("settings");
+        let path = dir.join("settings.json");
+        assert!(save(&path, &settings).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn patches_merge_known_fields_only() {
+        let current = Settings::default();
+        let next = apply_patch(
+            &current,
+            patch(json!({"exportEnabled": true, "exportDir": "/notes", "theme": "dark"})),
+        )
+        .unwrap();
+        assert!(next.export_enabled);
+        assert_eq!(next.export_dir.as_deref(), Some("/notes"));
+        assert_eq!(next.theme, Theme::Dark);
+
+        assert!(apply_patch(&current, patch(json!({"colour": "red"}))).is_err());
+        assert!(apply_patch(&current, patch(json!({"version": 2}))).is_err());
+        assert!(apply_patch(&current, patch(json!({"shortcuts": {}}))).is_err());
+        assert!(apply_patch(&current, patch(json!({"theme": "sepia"}))).is_err());
+    }
+}
@@ -0,0 +1,299 @@
+//! Global shortcuts registered from Rust during setup, so the capture,
+//! record and clip hotkeys work before the main webview has loaded, or if
+//! it never does.
+//!
+//! A shortcut that cannot be registered (usually because another app holds
+//! it) is kept in the settings and reported through `get_shortcuts`, so
+//! settings can show the conflict instead of the hotkey silently not working.
+
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+use crate::error::{Error, Result};
+use crate::settings::SettingsStore;
+
+const DEFAULT_CAPTURE: &str = "Alt+I";
+const DEFAULT_RECORD: &str = "Alt+R";
+const DEFAULT_CLIP: &str = "Alt+C";
+/// Emitted to every window after a shortcut changes.
+const CHANGED_EVENT: &str = "shortcuts-changed";
+/// Key repeat can deliver several presses; recording toggles need a pause.
+const RECORD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Capture,
+    Record,
+    Clip,
+}
+
+impl Action {
+    const ALL: [Action; 3] = [Action::Capture, Action::Record, Action::Clip];
+
+    fn label(self) -> &'static str {
+        match self {
+            Action::Capture => "Quick Capture",
+            Action::Record => "Quick Record",
+            Action::Clip => "Capture Clipboard",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShortcutConfig {
+    pub capture: String,
+    pub record: String,
+    pub clip: String,
+}
+
+impl Default for ShortcutConfig {
+    fn default() -> Self {
+        Self {
+            capture: DEFAULT_CAPTURE.into(),
+            record: DEFAULT_RECORD.into(),
+            clip: DEFAULT_CLIP.into(),
+        }
+    }
+}
+
+impl ShortcutConfig {
+    fn get(&self, action: Action) -> &str {
+        match action {
+            Action::Capture => &self.capture,
+            Action::Record => &self.record,
+            Action::Clip => &self.clip,
+        }
+    }
+
+    pub(crate) fn set(&mut self, action: Action, shortcut: String) {
+        match action {
+            Action::Capture => self.capture = shortcut,
+            Action::Record => self.record = shortcut,
+            Action::Clip => self.clip = shortcut,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutBinding {
+    pub shortcut: String,
+    pub registered: bool,
+    /// Why registration failed, e.g. another app already owns the keys.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutsStatus {
+    pub capture: ShortcutBinding,
+    pub record: ShortcutBinding,
+    pub clip: ShortcutBinding,
+}
+
+fn parse(shortcut: &str) -> Result<Shortcut> {
+    shortcut
+        .parse()
+        .map_err(|e| Error::Invalid(format!("invalid shortcut {shortcut}: {e}")))
+}
+
+/// Parse `candidate` for `action` and reject it if another action already
+/// uses the same keys.
+fn check(config: &ShortcutConfig, action: Action, candidate: &str) -> Result<Shortcut> {
+    let shortcut = parse(candidate)?;
+    let taken_by = Action::ALL.into_iter().find(|&other| {
+        other != action && parse(config.get(other)).is_ok_and(|taken| taken == shortcut)
+    });
+    if let Some(other) = taken_by {
+        return Err(Error::Invalid(format!(
+            "{candidate} is already used for {}",
+            other.label()
+        )));
+    }
+    Ok(shortcut)
+}
+
+/// Registration failures; the keys themselves live in [`SettingsStore`].
+#[derive(Default)]
+struct Inner {
+    capture_error: Option<String>,
+    record_error: Option<String>,
+    clip_error: Option<String>,
+}
+
+impl Inner {
+    fn error_mut(&mut self, action: Action) -> &mut Option<String> {
+        match action {
+            Action::Capture => &mut self.capture_error,
+            Action::Record => &mut self.record_error,
+            Action::Clip => &mut self.clip_error,
+        }
+    }
+
+    fn binding(&self, config: &ShortcutConfig, action: Action) -> ShortcutBinding {
+        let error = match action {
+            Action::Capture => &self.capture_error,
+            Action::Record => &self.record_error,
+            Action::Clip => &self.clip_error,
+        };
+        ShortcutBinding {
+            shortcut: config.get(action).to_owned(),
+            registered: error.is_none(),
+            error: error.clone(),
+        }
+    }
+
+    fn status(&self, config: &ShortcutConfig) -> ShortcutsStatus {
+        ShortcutsStatus {
+            capture: self.binding(config, Action::Capture),
+            record: self.binding(config, Action::Record),
+            clip: self.binding(config, Action::Clip),
+        }
+    }
+}
+
+/// Managed state: whether each saved shortcut is live.
+#[derive(Default)]
+pub struct Shortcuts {
+    inner: Mutex<Inner>,
+    last_record: Mutex<Option<Instant>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl Shortcuts {
+    /// Register every saved shortcut. Called from setup; failures are
+    /// logged and kept for settings to show.
+    pub fn register_all(&self, app: &AppHandle) {
+        let config = app.state::<SettingsStore>().get().shortcuts;
+        let mut inner = lock(&self.inner);
+        for action in Action::ALL {
+            let result = check(&config, action, config.get(action))
+                .and_then(|shortcut| register(app, action, shortcut));
+            if let Err(e) = &result {
+                log::warn!("{} shortcut not registered: {e}", action.label());
+            }
+            *inner.error_mut(action) = result.err().map(|e| e.to_string());
+        }
+    }
+
+    fn debounce_record(&self) -> bool {
+        let mut last = lock(&self.last_record);
+        let now = Instant::now();
+        if last.is_some_and(|at| now.duration_since(at) < RECORD_DEBOUNCE) {
+            return false;
+        }
+        *last = Some(now);
+        true
+    }
+}
+
+/// Bind `shortcut` to `action`. The handler captures the action itself, so
+/// it never needs this module's locks while the plugin is registering.
+fn register(app: &AppHandle, action: Action, shortcut: Shortcut) -> Result<()> {
+    app.global_shortcut()
+        .on_shortcut(shortcut, move |app, _, event| {
+            if event.state == ShortcutState::Pressed {
+                run(app, action);
+            }
+        })
+        .map_err(|e| Error::Invalid(format!("could not register {shortcut}: {e}")))
+}
+
+fn run(app: &AppHandle, action: Action) {
+    match action {
+        Action::Capture => crate::toggle_capture_window(app),
+        Action::Record => {
+            if app.state::<Shortcuts>().debounce_record() {
+                crate::toggle_indicator_recording(app);
+            }
+        }
+        Action::Clip => crate::clip::capture(app),
+    }
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+#[tauri::command]
+pub fn get_shortcuts(
+    shortcuts: State<'_, Shortcuts>,
+    settings: State<'_, SettingsStore>,
+) -> ShortcutsStatus {
+    lock(&shortcuts.inner).status(&settings.get().shortcuts)
+}
+
+/// Rebind `action`. If the new keys cannot be registered the previous ones
+/// are restored and the error is returned for settings to show.
+#[tauri::command]
+pub fn set_shortcut(
+    app: AppHandle,
+    shortcuts: State<'_, Shortcuts>,
+    settings: State<'_, SettingsStore>,
+    action: Action,
+    shortcut: String,
+) -> Result<ShortcutsStatus> {
+    let mut inner = lock(&shortcuts.inner);
+    let config = settings.get().shortcuts;
+    let next = check(&config, action, &shortcut)?;
+    let previous = parse(config.get(action)).ok();
+    if previous == Some(next) && inner.error_mut(action).is_none() {
+        return Ok(inner.status(&config));
+    }
+
+    let global = app.global_shortcut();
+    if let Some(previous) = previous.filter(|previous| global.is_registered(*previous)) {
+        crate::log_err("unregister shortcut", global.unregister(previous));
+    }
+    if let Err(e) = register(&app, action, next) {
+        if let Some(previous) = previous {
+            *inner.error_mut(action) = register(&app, action, previous)
+                .err()
+                .map(|e| e.to_string());
+        }
+        return Err(e);
+    }
+
+    *inner.error_mut(action) = None;
+    let saved = settings.update(&app, |settings| settings.shortcuts.set(action, shortcut))?;
+    let status = inner.status(&saved.shortcuts);
+    crate::log_err("emit shortcuts change", app.emit(CHANGED_EVENT, &status));
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_keys_bound_to_the_other_action() {
+        let config = ShortcutConfig::default();
+        assert!(check(&config, Action::Capture, "Alt+Shift+I").is_ok());
+        assert!(check(&config, Action::Capture, "Alt+I").is_ok());
+
+        let err = check(&config, Action::Capture, "alt+r").unwrap_err();
+        assert!(err.to_string().contains("Quick Record"), "{err}");
+        assert!(check(&config, Action::Record, "Alt+").is_err());
+
+        let err = check(&config, Action::Clip, "Alt+I").unwrap_err();
+        assert!(err.to_string().contains("Quick Capture"), "{err}");
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        let config: ShortcutConfig =
+            serde_json::from_str(r#"{"capture":"Control+Space"}"#).unwrap();
+        assert_eq!(config.capture, "Control+Space");
+        assert_eq!(config.record, DEFAULT_RECORD);
+        assert_eq!(config.clip, DEFAULT_CLIP);
+    }
+}
@@ -0,0 +1,521 @@
+//! Tags: `#hashtags` found in an idea's text, plus tags added by hand.
+//!
+//! Links taken from the text are marked `auto` and replaced every time the
+//! text changes; links added by hand stay until they are removed. A tag
+//! whose last extracted link goes away is deleted with it. Names are stored
+//! lowercase without the `#`.
+
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::db::Db;
+use crate::embeddings;
+use crate::error::{Error, Result};
+use crate::ideas::{self, Idea, IdeaUpdate, IDEA_COLUMNS};
+use crate::vault;
+use crate::vector_index::VectorIndex;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagCount {
+    pub name: String,
+    pub count: i64,
+}
+
+/// Ideas touched by renaming or deleting a tag.
+#[derive(Debug, Default)]
+pub struct Retagged {
+    /// Their text changed, so their embeddings are stale.
+    pub rewritten: Vec<String>,
+    /// Every idea whose tags changed, rewritten ones included.
+    pub ideas: Vec<String>,
+}
+
+fn is_tag_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '/')
+}
+
+/// Byte ranges of hashtag names (without the `#`) in `text`. Headings,
+/// URL fragments, `issue#12` and anything inside code are not tags, and a
+/// name needs at least one letter.
+fn hashtag_spans(text: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    let mut fenced = false;
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fenced = !fenced;
+            continue;
+        }
+        if fenced {
+            continue;
+        }
+
+        let mut code = false;
+        let mut prev: Option<char> = None;
+        let mut chars = line.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            let boundary = prev.map_or(true, |p| p.is_whitespace() || "([{\"'*,;".contains(p));
+            if c == '`' {
+                code = !code;
+            } else if c == '#' && !code && boundary {
+                let mut end = i + 1;
+                while let Some(&(j, next)) = chars.peek() {
+                    if !is_tag_char(next) {
+                        break;
+                    }
+                    end = j + next.len_utf8();
+                    chars.next();
+                }
+                let name = line[i + 1..end].trim_end_matches(['-', '/']);
+                if name.chars().any(char::is_alphabetic) {
+                    spans.push(start + i + 1..start + i + 1 + name.len());
+                }
+                prev = line[..end].chars().next_back();
+                continue;
+            }
+            prev = Some(c);
+        }
+    }
+    spans
+}
+
+/// The distinct hashtags in `text`, lowercase and sorted.
+pub fn extract(text: &str) -> Vec<String> {
+    hashtag_spans(text)
+        .into_iter()
+        .map(|span| text[span].to_lowercase())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+/// Check a tag name typed by hand, with or without its `#`.
+pub fn normalize(name: &str) -> Result<String> {
+    let name = name.trim();
+    let name = name.strip_prefix('#').unwrap_or(name).to_lowercase();
+    if name.is_empty() || !name.chars().all(is_tag_char) || !name.chars().any(char::is_alphabetic) {
+        return Err(Error::Invalid(format!(
+            "\"{name}\" is not a valid tag; use letters, digits, _, - or /"
+        )));
+    }
+    Ok(name)
+}
+
+/// Replace every `#from` in `text` with `#to`, or drop the `#` when `to`
+/// is `None` so the word stays.
+fn rewrite(text: &str, from: &str, to: Option<&str>) -> String {
+    let mut out = text.to_owned();
+    for span in hashtag_spans(text).into_iter().rev() {
+        if text[span.clone()].to_lowercase() != from {
+            continue;
+        }
+        match to {
+            Some(to) => out.replace_range(span, to),
+            None => out.replace_range(span.start - 1..span.start, ""),
+        }
+    }
+    out
+}
+
+// ── Repository ───────────────────────────────────────────
+
+fn find(conn: &Connection, name: &str) -> rusqlite::Result<Option<i64>> {
+    conn.query_row("SELECT id FROM tags WHERE name = ?1", [name], |row| {
+        row.get(0)
+    })
+    .optional()
+}
+
+fn ensure(conn: &Connection, name: &str) -> rusqlite::Result<i64> {
+    conn.execute("INSERT OR IGNORE INTO tags (name) VALUES (?1)", [name])?;
+    conn.query_row("SELECT id FROM tags WHERE name = ?1", [name], |row| {
+        row.get(0)
+    })
+}
+
+fn existing(conn: &Connection, name: &str) -> Result<i64> {
+    find(conn, name)?.ok_or_else(|| Error::Invalid(format!("there is no tag #{name}")))
+}
+
+/// Delete those of `ids` that no idea uses any more.
+fn prune(conn: &Connection, ids: &[i64]) -> rusqlite::Result<()> {
+    for id in ids {
+        conn.execute(
+            "DELETE FROM tags WHERE id = ?1
+               AND NOT EXISTS (SELECT 1 FROM idea_tags WHERE tag_id = ?1)",
+            [id],
+        )?;
+    }
+    Ok(())
+}
+
+fn linked_ideas(conn: &Connection, tag_id: i64, auto_only: bool) -> rusqlite::Result<Vec<String>> {
+    let mut stmt =
+        conn.prepare("SELECT idea_id FROM idea_tags WHERE tag_id = ?1 AND (auto = 1 OR NOT ?2)")?;
+    let rows = stmt.query_map(params![tag_id, auto_only], |row| row.get(0))?;
+    rows.collect()
+}
+
+/// Replace the idea's extracted tags with the hashtags now in `text`.
+pub(crate) fn sync(conn: &Connection, idea_id: &str, text: &str) -> rusqlite::Result<()> {
+    let stale: Vec<i64> = {
+        let mut stmt =
+            conn.prepare("SELECT tag_id FROM idea_tags WHERE idea_id = ?1 AND auto = 1")?;
+        let rows = stmt.query_map([idea_id], |row| row.get(0))?;
+        rows.collect::<rusqlite::Result<_>>()?
+    };
+    conn.execute(
+        "DELETE FROM idea_tags WHERE idea_id = ?1 AND auto = 1",
+        [idea_id],
+    )?;
+    for name in extract(text) {
+        let tag_id = ensure(conn, &name)?;
+        // A tag also added by hand keeps its manual link.
+        conn.execute(
+            "INSERT OR IGNORE INTO idea_tags (idea_id, tag_id, auto) VALUES (?1, ?2, 1)",
+            params![idea_id, tag_id],
+        )?;
+    }
+    prune(conn, &stale)
+}
+
+/// Add hand-made links for `names`, skipping invalid names and tags the
+/// idea already has.
+pub(crate) fn link(conn: &Connection, idea_id: &str, names: &[String]) -> rusqlite::Result<()> {
+    for name in names.iter().filter_map(|name| normalize(name).ok()) {
+        let tag_id = ensure(conn, &name)?;
+        conn.execute(
+            "INSERT OR IGNORE INTO idea_tags (idea_id, tag_id, auto) VALUES (?1, ?2, 0)",
+            params![idea_id, tag_id],
+        )?;
+    }
+    Ok(())
+}
+
+/// Extract tags for every idea; run once when the tables are created.
+pub(crate) fn backfill(conn: &Connection) -> rusqlite::Result<()> {
+    let ideas: Vec<(String, String)> = {
+        let mut stmt = conn.prepare("SELECT id, text FROM ideas")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<rusqlite::Result<_>>()?
+    };
+    for (id, text) in ideas {
+        sync(conn, &id, &text)?;
+    }
+    Ok(())
+}
+
+/// Every tag with the number of ideas carrying it, optionally counting
+/// only archived or unarchived ones.
+pub fn list(conn: &Connection, archived: Option<bool>) -> Result<Vec<TagCount>> {
+    let mut stmt = conn.prepare(
+        "SELECT tags.name, COUNT(ideas.id) FROM tags
+         LEFT JOIN idea_tags ON idea_tags.tag_id = tags.id
+         LEFT JOIN ideas ON ideas.id = idea_tags.idea_id
+           AND ideas.deleted_at IS NULL
+           AND (?1 IS NULL OR ideas.archived = ?1)
+         GROUP BY tags.id
+         ORDER BY tags.name",
+    )?;
+    let rows = stmt.query_map(params![archived.map(i64::from)], |row| {
+        Ok(TagCount {
+            name: row.get(0)?,
+            count: row.get(1)?,
+        })
+    })?;
+    Ok(rows.collect::<rusqlite::Result<_>>()?)
+}
+
+pub fn ideas_with(
+    conn: &Connection,
+    tag: &str,
+    archived: Option<bool>,
+    source_app: Option<&str>,
+) -> Result<Vec<Idea>> {
+    ideas::query_ideas(
+        conn,
+        &format!(
+            "SELECT {IDEA_COLUMNS} FROM ideas
+             JOIN idea_tags ON idea_tags.idea_id = ideas.id
+             JOIN tags ON tags.id = idea_tags.tag_id
+             WHERE tags.name = ?1 AND ideas.deleted_at IS NULL
+               AND (?2 IS NULL OR ideas.archived = ?2)
+               AND (?3 IS NULL OR ideas.source_app = ?3)
+             ORDER BY ideas.created_at DESC"
+        ),
+        params![normalize(tag)?, archived.map(i64::from), source_app],
+    )
+}
+
+/// Create an empty tag. Creating one that exists is not an error.
+pub fn create(conn: &Connection, name: &str) -> Result<String> {
+    let name = normalize(name)?;
+    ensure(conn, &name)?;
+    Ok(name)
+}
+
+pub fn add(conn: &Connection, idea_id: &str, name: &str) -> Result<()> {
+    if !ideas::exists(conn, idea_id)? {
+        return Err(Error::NotFound(idea_id.to_owned()));
+    }
+    link(conn, idea_id, &[normalize(name)?])?;
+    Ok(())
+}
+
+/// Remove a hand-made link. A hashtag in the text can only go by editing it.
+pub fn remove(conn: &Connection, idea_id: &str, name: &str) -> Result<()> {
+    let name = normalize(name)?;
+    let idea = ideas::get(conn, idea_id)?.ok_or_else(|| Error::NotFound(idea_id.to_owned()))?;
+    if extract(&idea.text).contains(&name) {
+        return Err(Error::Invalid(format!(
+            "#{name} is in the idea's text; edit the text to remove it"
+        )));
+    }
+    let tag_id = existing(conn, &name)?;
+    conn.execute(
+        "DELETE FROM idea_tags WHERE idea_id = ?1 AND tag_id = ?2",
+        params![idea_id, tag_id],
+    )?;
+    prune(conn, &[tag_id])?;
+    Ok(())
+}
+
+/// Rewrite the hashtag in every idea that has it, then deal with the
+/// remaining hand-made links through `relink`.
+fn retag(
+    conn: &Connection,
+    name: &str,
+    to: Option<&str>,
+    relink: impl FnOnce(i64) -> rusqlite::Result<()>,
+) -> Result<Retagged> {
+    let tx = conn.unchecked_transaction()?;
+    let tag_id = existing(&tx, name)?;
+    let mut retagged = Retagged {
+        rewritten: Vec::new(),
+        ideas: linked_ideas(&tx, tag_id, false)?,
+    };
+    // Ideas in the trash can't be edited and keep their text.
+    for id in linked_ideas(&tx, tag_id, true)? {
+        let Some(idea) = ideas::get(&tx, &id)?.filter(|idea| idea.deleted_at.is_none()) else {
+            continue;
+        };
+        let update = IdeaUpdate {
+            text: Some(rewrite(&idea.text, name, to)),
+            title: None,
+        };
+        ideas::update(&tx, &id, &update)?;
+        retagged.rewritten.push(id);
+    }
+    relink(tag_id)?;
+    tx.execute("DELETE FROM tags WHERE id = ?1", [tag_id])?;
+    tx.commit()?;
+    retagged.ideas.sort();
+    Ok(retagged)
+}
+
+/// Rename a tag, merging it into `to` if that exists. Hashtags in the
+/// text are rewritten so they don't bring the old name back.
+pub fn rename(conn: &Connection, name: &str, to: &str) -> Result<(String, Retagged)> {
+    let (name, to) = (normalize(name)?, normalize(to)?);
+    if name == to {
+        return Ok((to, Retagged::default()));
+    }
+    let retagged = retag(conn, &name, Some(&to), |tag_id| {
+        let to_id = ensure(conn, &to)?;
+        conn.execute(
+            "INSERT OR IGNORE INTO idea_tags (idea_id, tag_id, auto)
+             SELECT idea_id, ?2, 0 FROM idea_tags WHERE tag_id = ?1",
+            params![tag_id, to_id],
+        )?;
+        Ok(())
+    })?;
+    Ok((to, retagged))
+}
+
+/// Delete a tag everywhere. Its hashtags lose their `#` but keep the word.
+pub fn delete(conn: &Connection, name: &str) -> Result<Retagged> {
+    retag(conn, &normalize(name)?, None, |_| Ok(()))
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+/// Drop stale embeddings, refresh exported files and tell every window.
+fn announce(app: &AppHandle, conn: &Connection, index: &VectorIndex, retagged: &Retagged) {
+    for id in &retagged.rewritten {
+        crate::log_err(
+            "drop stale embedding",
+            embeddings::delete_for_idea(conn, id),
+        );
+        index.remove(id);
+    }
+    vault::reexport(app, conn, &retagged.ideas);
+    if !retagged.ideas.is_empty() {
+        crate::log_err(
+            "emit retagged ideas",
+            app.emit(ideas::CHANGED_EVENT, &retagged.ideas),
+        );
+    }
+}
+
+#[tauri::command]
+pub fn list_tags(db: State<'_, Db>, archived: Option<bool>) -> Result<Vec<TagCount>> {
+    list(&db.conn(), archived)
+}
+
+#[tauri::command]
+pub fn get_ideas_by_tag(
+    db: State<'_, Db>,
+    tag: String,
+    archived: Option<bool>,
+    source_app: Option<String>,
+) -> Result<Vec<Idea>> {
+    ideas_with(&db.conn(), &tag, archived, source_app.as_deref())
+}
+
+#[tauri::command]
+pub fn create_tag(db: State<'_, Db>, name: String) -> Result<String> {
+    create(&db.conn(), &name)
+}
+
+#[tauri::command]
+pub fn rename_tag(
+    app: AppHandle,
+    db: State<'_, Db>,
+    index: State<'_, VectorIndex>,
+    name: String,
+    new_name: String,
+) -> Result<String> {
+    let conn = db.conn();
+    let (renamed, retagged) = rename(&conn, &name, &new_name)?;
+    announce(&app, &conn, &index, &retagged);
+    Ok(renamed)
+}
+
+#[tauri::command]
+pub fn delete_tag(
+    app: AppHandle,
+    db: State<'_, Db>,
+    index: State<'_, VectorIndex>,
+    name: String,
+) -> Result<()> {
+    let conn = db.conn();
+    let retagged = delete(&conn, &name)?;
+    announce(&app, &conn, &index, &retagged);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn add_idea_tag(app: AppHandle, db: State<'_, Db>, id: String, tag: String) -> Result<()> {
+    let conn = db.conn();
+    add(&conn, &id, &tag)?;
+    vault::reexport(&app, &conn, &[id]);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn remove_idea_tag(app: AppHandle, db: State<'_, Db>, id: String, tag: String) -> Result<()> {
+    let conn = db.conn();
+    remove(&conn, &id, &tag)?;
+    vault::reexport(&app, &conn, &[id]);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conn() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        crate::migrations::run(&mut conn, None).unwrap();
+        conn
+    }
+
+    #[test]
+    fn extracts_hashtags_but_not_headings_links_or_code() {
+        let text = "# Heading\nPlan the #Garden and #garden-2024, (#seeds).\n\
+                    See https://example.com/#anchor, issue#12, #42 and `#code`.\n\
+                    ```\n#fenced\n```\n#work/client-";
+        assert_eq!(
+            extract(text),
+            ["garden", "garden-2024", "seeds", "work/client"]
+        );
+        assert_eq!(
+            rewrite("#Garden and #gardening", "garden", Some("yard")),
+            "#yard and #gardening"
+        );
+        assert_eq!(rewrite("buy #seeds", "seeds", None), "buy seeds");
+        assert!(normalize("#Ok_tag").is_ok());
+        assert!(matches!(normalize("no spaces"), Err(Error::Invalid(_))));
+    }
+
+    #[test]
+    fn follows_edits_and_keeps_hand_made_links() {
+        let conn = conn();
+        let idea = ideas::create(&conn, "water the #garden", None).unwrap();
+        assert_eq!(idea.tags, ["garden"]);
+        add(&conn, &idea.id, "#Home").unwrap();
+        assert!(matches!(
+            remove(&conn, &idea.id, "garden"),
+            Err(Error::Invalid(_))
+        ));
+
+        let edit = IdeaUpdate {
+            text: Some("water the #plants".into()),
+            title: None,
+        };
+        ideas::update(&conn, &idea.id, &edit).unwrap();
+        let stored = ideas::get(&conn, &idea.id).unwrap().unwrap();
+        assert_eq!(stored.tags, ["home", "plants"]);
+        let names: Vec<String> = list(&conn, None)
+            .unwrap()
+            .into_iter()
+            .map(|tag| tag.name)
+            .collect();
+        assert_eq!(names, ["home", "plants"], "#garden went with its last idea");
+
+        ideas::set_archived(&conn, &idea.id, true).unwrap();
+        assert!(ideas_with(&conn, "plants", Some(false), None)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            ideas_with(&conn, "#plants", Some(true), None)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn rename_rewrites_text_and_merges() {
+        let conn = conn();
+        let a = ideas::create(&conn, "#todo call Sam", None).unwrap();
+        let b = ideas::create(&conn, "#tasks file taxes", None).unwrap();
+        let c = ideas::create(&conn, "no hashtag", None).unwrap();
+        add(&conn, &c.id, "todo").unwrap();
+
+        let (name, retagged) = rename(&conn, "todo", "#Tasks").unwrap();
+        assert_eq!(name, "tasks");
+        assert_eq!(retagged.rewritten, [a.id.as_str()]);
+        assert_eq!(
+            ideas::get(&conn, &a.id).unwrap().unwrap().text,
+            "#tasks call Sam"
+        );
+        assert_eq!(ideas_with(&conn, "tasks", None, None).unwrap().len(), 3);
+        assert!(find(&conn, "todo").unwrap().is_none());
+
+        delete(&conn, "tasks").unwrap();
+        assert_eq!(
+            ideas::get(&conn, &b.id).unwrap().unwrap().text,
+            "tasks file taxes"
+        );
+        assert!(list(&conn, None).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,369 @@
+//! Speech-to-text on the CPU with whisper.cpp, for machines where the
+//! webview has no WebGPU. Models are ggml files in the app data directory.
+//!
+//! whisper.cpp and libopus need cmake and a C++ toolchain to build, so the
+//! engine sits behind the `native-stt` feature. Without it the status
+//! command reports it unavailable and the frontend keeps its WebGPU worker.
+
+use std::path::PathBuf;
+#[cfg(feature = "native-stt")]
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::ipc::{InvokeBody, Request};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::audio;
+use crate::error::{Error, Result};
+use crate::settings::SettingsStore;
+
+/// Emitted to the indicator window while a recording is transcribed.
+const PROGRESS_EVENT: &str = "transcription-progress";
+/// Emitted to every window while a model downloads.
+const DOWNLOAD_EVENT: &str = "whisper-model-progress";
+
+#[cfg(feature = "native-stt")]
+const MODEL_BASE_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Segment {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transcript {
+    pub text: String,
+    pub segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(not(feature = "native-stt"), allow(dead_code))]
+pub enum Stage {
+    Decoding,
+    Loading,
+    Transcribing,
+    Done,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Progress {
+    stage: Stage,
+    percent: u8,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DownloadProgress<'a> {
+    model: &'a str,
+    percent: u8,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeSttStatus {
+    pub available: bool,
+    pub models_dir: String,
+    /// ggml model names present on disk, e.g. `"base"`.
+    pub installed: Vec<String>,
+}
+
+/// Map a frontend model id (`"Xenova/whisper-base"`) to its ggml name
+/// (`"base"`). Plain names such as `"small.en"` pass through.
+pub fn ggml_name(model: &str) -> Result<String> {
+    let name = model.rsplit('/').next().unwrap_or(model);
+    let name = name.strip_prefix("whisper-").unwrap_or(name);
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+    if valid {
+        Ok(name.to_owned())
+    } else {
+        Err(Error::Invalid(format!("unknown Whisper model: {model}")))
+    }
+}
+
+#[cfg(not(feature = "native-stt"))]
+fn unavailable() -> Error {
+    Error::Invalid("this build has no native transcription (native-stt feature)".into())
+}
+
+/// Managed state: where models live and, in `native-stt` builds, the most
+/// recently used model kept loaded between recordings.
+pub struct Transcriber {
+    models_dir: PathBuf,
+    #[cfg(feature = "native-stt")]
+    loaded: Mutex<Option<(String, whisper_rs::WhisperContext)>>,
+}
+
+impl Transcriber {
+    pub fn new(models_dir: PathBuf) -> Self {
+        #[cfg(feature = "native-stt")]
+        whisper_rs::install_logging_hooks();
+        Self {
+            models_dir,
+            #[cfg(feature = "native-stt")]
+            loaded: Mutex::new(None),
+        }
+    }
+
+    #[cfg_attr(not(feature = "native-stt"), allow(dead_code))]
+    fn model_path(&self, name: &str) -> PathBuf {
+        self.models_dir.join(format!("ggml-{name}.bin"))
+    }
+
+    pub fn installed(&self) -> Vec<String> {
+        let Ok(files) = std::fs::read_dir(&self.models_dir) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = files
+            .flatten()
+            .filter_map(|file| {
+                let file_name = file.file_name().into_string().ok()?;
+                let name = file_name.strip_prefix("ggml-")?.strip_suffix(".bin")?;
+                Some(name.to_owned())
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    pub fn status(&self) -> NativeSttStatus {
+        NativeSttStatus {
+            available: cfg!(feature = "native-stt"),
+            models_dir: self.models_dir.display().to_string(),
+            installed: self.installed(),
+        }
+    }
+
+    /// Run Whisper over 16 kHz mono samples. `language` of `None` lets the
+    /// model detect it.
+    #[cfg(feature = "native-stt")]
+    pub fn transcribe(
+        &self,
+        samples: &[f32],
+        name: &str,
+        language: Option<&str>,
+        mut on_progress: impl FnMut(Stage, u8) + Send + 'static,
+    ) -> Result<Transcript> {
+        use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+        let whisper_err = |e: whisper_rs::WhisperError| Error::Invalid(format!("whisper: {e}"));
+
+        let mut loaded = self.loaded.lock().unwrap_or_else(|e| e.into_inner());
+        if loaded.as_ref().map_or(true, |(current, _)| current != name) {
+            let path = self.model_path(name);
+            if !path.exists() {
+                return Err(Error::Invalid(format!(
+                    "Whisper model {name} is not downloaded"
+                )));
+            }
+            on_progress(Stage::Loading, 0);
+            *loaded = None;
+            let ctx = WhisperContext::new_with_params(
+                &path.to_string_lossy(),
+                WhisperContextParameters::default(),
+            )
+            .map_err(whisper_err)?;
+            *loaded = Some((name.to_owned(), ctx));
+        }
+        let (_, ctx) = loaded.as_ref().expect("loaded above");
+        let mut state = ctx.create_state().map_err(whisper_err)?;
+
+        let threads = std::thread::available_parallelism().map_or(4, |n| n.get().min(8));
+        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+        params.set_n_threads(threads as i32);
+        params.set_language(Some(language.unwrap_or("auto")));
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+        params.set_progress_callback_safe(move |percent: i32| {
+            on_progress(Stage::Transcribing, percent.clamp(0, 100) as u8)
+        });
+        state.full(params, samples).map_err(whisper_err)?;
+
+        let count = state.full_n_segments().map_err(whisper_err)?;
+        let mut segments = Vec::with_capacity(count.max(0) as usize);
+        for i in 0..count {
+            // whisper.cpp timestamps are in centiseconds.
+            segments.push(Segment {
+                start_ms: state.full_get_segment_t0(i).map_err(whisper_err)? * 10,
+                end_ms: state.full_get_segment_t1(i).map_err(whisper_err)? * 10,
+                text: state
+                    .full_get_segment_text_lossy(i)
+                    .map_err(whisper_err)?
+                    .trim()
+                    .to_owned(),
+            });
+        }
+        let text = segments
+            .iter()
+            .map(|segment| segment.text.as_str())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Ok(Transcript { text, segments })
+    }
+
+    #[cfg(not(feature = "native-stt"))]
+    pub fn transcribe(
+        &self,
+        _samples: &[f32],
+        _name: &str,
+        _language: Option<&str>,
+        _on_progress: impl FnMut(Stage, u8) + Send + 'static,
+    ) -> Result<Transcript> {
+        Err(unavailable())
+    }
+
+    /// Fetch a ggml model unless it is already on disk. Writes to a `.part`
+    /// file first so an interrupted download is never mistaken for a model.
+    #[cfg(feature = "native-stt")]
+    pub fn download(&self, name: &str, mut on_progress: impl FnMut(u8)) -> Result<()> {
+        use std::io::{Read, Write};
+
+        let path = self.model_path(name);
+        if path.exists() {
+            return Ok(());
+        }
+        std::fs::create_dir_all(&self.models_dir)?;
+        let url = format!("{MODEL_BASE_URL}/ggml-{name}.bin");
+        let response = ureq::get(&url)
+            .call()
+            .map_err(|e| Error::Invalid(format!("could not download {name}: {e}")))?;
+        let total: u64 = response
+            .header("Content-Length")
+            .and_then(|len| len.parse().ok())
+            .unwrap_or(0);
+
+        let part = path.with_extension("bin.part");
+        let mut out = std::io::BufWriter::new(std::fs::File::create(&part)?);
+        let mut reader = response.into_reader();
+        let mut buf = vec![0u8; 64 * 1024];
+        let (mut written, mut reported) = (0u64, 0u8);
+        loop {
+            let n = reader.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            out.write_all(&buf[..n])?;
+            written += n as u64;
+            // Servers that omit Content-Length get no progress, just the result.
+            if let Some(percent) = (written * 100).checked_div(total) {
+                let percent = percent.min(100) as u8;
+                if percent != reported {
+                    reported = percent;
+                    on_progress(percent);
+                }
+            }
+        }
+        out.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        std::fs::rename(&part, &path)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "native-stt"))]
+    pub fn download(&self, _name: &str, _on_progress: impl FnMut(u8)) -> Result<()> {
+        Err(unavailable())
+    }
+}
+
+fn emit_progress(app: &AppHandle, stage: Stage, percent: u8) {
+    crate::log_err(
+        "emit transcription progress",
+        app.emit_to("indicator", PROGRESS_EVENT, Progress { stage, percent }),
+    );
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+#[tauri::command]
+pub fn native_transcription_status(transcriber: State<'_, Transcriber>) -> NativeSttStatus {
+    transcriber.status()
+}
+
+#[tauri::command]
+pub async fn download_whisper_model(app: AppHandle, model: String) -> Result<()> {
+    let name = ggml_name(&model)?;
+    tauri::async_runtime::spawn_blocking(move || {
+        let transcriber = app.state::<Transcriber>();
+        transcriber.download(&name, |percent| {
+            crate::log_err(
+                "emit model download progress",
+                app.emit(
+                    DOWNLOAD_EVENT,
+                    DownloadProgress {
+                        model: &model,
+                        percent,
+                    },
+                ),
+            );
+        })
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("model download failed: {e}")))?
+}
+
+/// Transcribe a recording sent as the raw request body, so the webview can
+/// pass its Blob bytes without a JSON round trip. The model id comes in the
+/// `x-model` header, falling back to the `sttModel` setting, and an optional
+/// language code in `x-language`.
+#[tauri::command]
+pub async fn transcribe_audio(app: AppHandle, request: Request<'_>) -> Result<Transcript> {
+    let InvokeBody::Raw(audio) = request.body() else {
+        return Err(Error::Invalid("expected raw audio bytes".into()));
+    };
+    let header = |key: &str| {
+        request
+            .headers()
+            .get(key)
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| !value.is_empty())
+            .map(str::to_owned)
+    };
+    let model = header("x-model")
+        .or_else(|| app.state::<SettingsStore>().get().stt_model)
+        .unwrap_or_else(|| "base".into());
+    let name = ggml_name(&model)?;
+    let language = header("x-language");
+    let audio = audio.clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        emit_progress(&app, Stage::Decoding, 0);
+        let samples = audio::decode_to_whisper(audio)?;
+        if samples.is_empty() {
+            return Err(Error::Invalid("recording is empty".into()));
+        }
+        let progress_app = app.clone();
+        let transcript = app.state::<Transcriber>().transcribe(
+            &samples,
+            &name,
+            language.as_deref(),
+            move |stage, percent| emit_progress(&progress_app, stage, percent),
+        )?;
+        emit_progress(&app, Stage::Done, 100);
+        Ok(transcript)
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("transcription failed: {e}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_frontend_ids_to_ggml_names() {
+        assert_eq!(ggml_name("Xenova/whisper-tiny").unwrap(), "tiny");
+        assert_eq!(ggml_name("whisper-small").unwrap(), "small");
+        assert_eq!(ggml_name("base.en").unwrap(), "base.en");
+        assert!(ggml_name("..\\models\\evil").is_err());
+        assert!(ggml_name("Xenova/").is_err());
+    }
+}
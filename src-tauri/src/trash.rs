@@ -0,0 +1,298 @@
+//! Deleted ideas wait in the trash before they are gone for good.
+//!
+//! Deleting an idea only sets its `deleted_at`. It drops out of every list
+//! and search but keeps its embeddings, attachments and recording, so
+//! restoring it brings all of that back. A background thread purges ideas
+//! that have been in the trash longer than `trashDays`; emptying the trash,
+//! from the dashboard or the tray menu, purges the rest.
+//!
+//! A trashed idea's Markdown export is removed from the vault, and written
+//! again when the idea is restored.
+
+use std::time::Duration;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind};
+
+use crate::app_lock::AppLock;
+use crate::attachments::Blobs;
+use crate::db::{now_millis, Db};
+use crate::error::{Error, Result};
+use crate::ideas::{self, Idea, IDEA_COLUMNS};
+use crate::recordings::Recordings;
+use crate::settings::SettingsStore;
+use crate::vault;
+use crate::vector_index::VectorIndex;
+
+/// How often the purge thread looks for expired ideas.
+const CHECK_EVERY: Duration = Duration::from_secs(60 * 60);
+const DAY_MILLIS: i64 = 24 * 60 * 60 * 1000;
+
+/// Move the idea to the trash, and its exported file out of the vault.
+/// Trashing it again keeps the first date.
+pub fn trash(conn: &Connection, id: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE ideas SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+        params![now_millis(), id],
+    )?;
+    let idea = ideas::get(conn, id)?.ok_or_else(|| Error::NotFound(id.to_owned()))?;
+    if idea.markdown_path.is_some() {
+        // If the file stays, the purge has another go at it.
+        match vault::remove_file(idea.markdown_path.as_deref()) {
+            Ok(()) => ideas::set_markdown_path(conn, id, None)?,
+            Err(e) => log::warn!("Could not remove the vault file of idea {id}: {e}"),
+        }
+    }
+    Ok(())
+}
+
+/// Take the idea back out of the trash, into the list it was deleted from.
+pub fn restore(conn: &Connection, id: &str) -> Result<()> {
+    let changed = conn.execute(
+        "UPDATE ideas SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+        params![id],
+    )?;
+    if changed == 0 {
+        return Err(if ideas::exists(conn, id)? {
+            Error::Invalid(format!("idea {id} is not in the trash"))
+        } else {
+            Error::NotFound(id.to_owned())
+        });
+    }
+    Ok(())
+}
+
+/// Ideas in the trash, most recently deleted first.
+pub fn list(conn: &Connection) -> Result<Vec<Idea>> {
+    ideas::query_ideas(
+        conn,
+        &format!(
+            "SELECT {IDEA_COLUMNS} FROM ideas
+             WHERE deleted_at IS NOT NULL
+             ORDER BY deleted_at DESC"
+        ),
+        [],
+    )
+}
+
+/// An idea deleted for good, with the vault file it may have left behind.
+pub(crate) struct Purged {
+    id: String,
+    markdown_path: Option<String>,
+}
+
+fn purged_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Purged> {
+    Ok(Purged {
+        id: row.get(0)?,
+        markdown_path: row.get(1)?,
+    })
+}
+
+/// Delete for good the ideas trashed before `before` (ms), or every idea in
+/// the trash when it is `None`.
+pub(crate) fn purge(conn: &Connection, before: Option<i64>) -> Result<Vec<Purged>> {
+    let mut stmt = conn.prepare(
+        "DELETE FROM ideas
+         WHERE deleted_at IS NOT NULL AND (?1 IS NULL OR deleted_at < ?1)
+         RETURNING id, markdown_path",
+    )?;
+    let purged = stmt.query_map(params![before], purged_from_row)?;
+    Ok(purged.collect::<rusqlite::Result<_>>()?)
+}
+
+/// Delete one idea in the trash for good.
+fn purge_one(conn: &Connection, id: &str) -> Result<Purged> {
+    conn.query_row(
+        "DELETE FROM ideas WHERE id = ?1 AND deleted_at IS NOT NULL
+         RETURNING id, markdown_path",
+        params![id],
+        purged_from_row,
+    )
+    .optional()?
+    .ok_or_else(|| Error::Invalid(format!("idea {id} is not in the trash")))
+}
+
+/// After a purge: the embeddings, attachment and recording rows went with
+/// the ideas via `ON DELETE CASCADE`, which leaves the index entries, vault
+/// files and stored files.
+fn clean_up(app: &AppHandle, conn: &Connection, purged: Vec<Purged>) {
+    if purged.is_empty() {
+        return;
+    }
+    let index = app.state::<VectorIndex>();
+    for idea in &purged {
+        index.remove(&idea.id);
+        crate::log_err(
+            "remove vault file",
+            vault::remove_file(idea.markdown_path.as_deref()),
+        );
+    }
+    let ids: Vec<String> = purged.into_iter().map(|idea| idea.id).collect();
+    crate::log_err("prune attachments", app.state::<Blobs>().prune(conn));
+    crate::log_err("prune recordings", app.state::<Recordings>().prune(conn));
+    crate::log_err("emit ideas change", app.emit(ideas::CHANGED_EVENT, ids));
+}
+
+/// Purge ideas older than the `trashDays` setting. Waits while the database
+/// is locked.
+fn purge_expired(app: &AppHandle) -> Result<()> {
+    let Some(db) = app.try_state::<Db>() else {
+        return Ok(());
+    };
+    let days = app.state::<SettingsStore>().get().trash_days;
+    if days == 0 {
+        return Ok(());
+    }
+    let conn = db.conn();
+    let purged = purge(&conn, Some(now_millis() - i64::from(days) * DAY_MILLIS))?;
+    if !purged.is_empty() {
+        log::info!("Purged {} ideas from the trash", purged.len());
+    }
+    clean_up(app, &conn, purged);
+    Ok(())
+}
+
+/// Start the purge thread.
+pub fn start(app: &AppHandle) {
+    let app = app.clone();
+    let spawned = std::thread::Builder::new()
+        .name("glimt-trash".into())
+        .spawn(move || loop {
+            crate::log_err("purge trash", purge_expired(&app));
+            std::thread::sleep(CHECK_EVERY);
+        });
+    crate::log_err("start trash purge", spawned);
+}
+
+/// The tray's "Empty Trash": asks first, since nothing can be restored
+/// afterwards. Does nothing while the app is locked.
+pub fn confirm_empty(app: &AppHandle) {
+    if app.state::<AppLock>().is_locked() {
+        log::info!("Not emptying the trash while Glimt is locked");
+        return;
+    }
+    let handle = app.clone();
+    app.dialog()
+        .message("Ideas in the trash will be deleted permanently.")
+        .title("Empty Trash")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Empty Trash".into(),
+            "Cancel".into(),
+        ))
+        .show(move |confirmed| {
+            if !confirmed {
+                return;
+            }
+            let Some(db) = handle.try_state::<Db>() else {
+                return;
+            };
+            let conn = db.conn();
+            match purge(&conn, None) {
+                Ok(purged) => clean_up(&handle, &conn, purged),
+                Err(e) => log::error!("Could not empty the trash: {e}"),
+            }
+        });
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+#[tauri::command]
+pub fn list_trash(db: State<'_, Db>) -> Result<Vec<Idea>> {
+    list(&db.conn())
+}
+
+#[tauri::command]
+pub fn restore_idea(app: AppHandle, db: State<'_, Db>, id: String) -> Result<()> {
+    let conn = db.conn();
+    restore(&conn, &id)?;
+    vault::reexport(&app, &conn, &[id]);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn purge_idea(app: AppHandle, db: State<'_, Db>, id: String) -> Result<()> {
+    let conn = db.conn();
+    let purged = purge_one(&conn, &id)?;
+    clean_up(&app, &conn, vec![purged]);
+    Ok(())
+}
+
+/// Delete every idea in the trash for good. Returns how many went.
+#[tauri::command]
+pub fn empty_trash(app: AppHandle, db: State<'_, Db>) -> Result<usize> {
+    let conn = db.conn();
+    let purged = purge(&conn, None)?;
+    let count = purged.len();
+    clean_up(&app, &conn, purged);
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trashed_ideas_leave_lists_until_restored_or_purged() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "foreign_keys", true).unwrap();
+        crate::migrations::run(&mut conn, None).unwrap();
+        let old = ideas::create(&conn, "old #draft", None).unwrap();
+        let recent = ideas::create(&conn, "recent #draft", None).unwrap();
+        let kept = ideas::create(&conn, "kept", None).unwrap();
+        trash(&conn, &old.id).unwrap();
+        trash(&conn, &recent.id).unwrap();
+        conn.execute(
+            "UPDATE ideas SET deleted_at = deleted_at - 10 * ?1 WHERE id = ?2",
+            params![DAY_MILLIS, old.id],
+        )
+        .unwrap();
+
+        let listed: Vec<String> = ideas::list(&conn, false, None)
+            .unwrap()
+            .into_iter()
+            .map(|idea| idea.id)
+            .collect();
+        assert_eq!(listed, [kept.id.as_str()]);
+        assert!(ideas::search_fts(&conn, "draft").unwrap().is_empty());
+        assert_eq!(crate::tags::list(&conn, None).unwrap()[0].count, 0);
+        let trashed: Vec<String> = list(&conn).unwrap().into_iter().map(|i| i.id).collect();
+        assert_eq!(trashed, [recent.id.as_str(), old.id.as_str()]);
+        assert!(matches!(restore(&conn, &kept.id), Err(Error::Invalid(_))));
+        let edit = ideas::IdeaUpdate {
+            text: Some("edited in the trash".into()),
+            title: None,
+        };
+        assert!(matches!(
+            ideas::update(&conn, &recent.id, &edit),
+            Err(Error::NotFound(_))
+        ));
+        assert!(crate::revisions::list(&conn, &recent.id)
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            ideas::set_archived(&conn, &recent.id, true),
+            Err(Error::NotFound(_))
+        ));
+        assert!(matches!(
+            crate::reminders::set(&conn, &recent.id, Some(1)),
+            Err(Error::NotFound(_))
+        ));
+
+        let week_ago = now_millis() - 7 * DAY_MILLIS;
+        let purged: Vec<String> = purge(&conn, Some(week_ago))
+            .unwrap()
+            .into_iter()
+            .map(|idea| idea.id)
+            .collect();
+        assert_eq!(purged, [old.id.as_str()]);
+        assert!(!ideas::exists(&conn, &old.id).unwrap());
+
+        restore(&conn, &recent.id).unwrap();
+        assert_eq!(ideas::search_fts(&conn, "draft").unwrap()[0].id, recent.id);
+        assert!(purge(&conn, None).unwrap().is_empty());
+        assert!(matches!(purge_one(&conn, &kept.id), Err(Error::Invalid(_))));
+        assert!(matches!(trash(&conn, "missing"), Err(Error::NotFound(_))));
+    }
+}
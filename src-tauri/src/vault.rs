@@ -0,0 +1,507 @@
+//! Two-way sync between ideas and the Markdown export folder, so notes
+//! edited, renamed or deleted in Obsidian or Logseq flow back into Glimt.
+//!
+//! Every exported file records its path in `ideas.markdown_path`. A watcher
+//! on the folder parses the frontmatter written by
+//! [`export::generate_markdown`] and applies edits to the idea with the
+//! matching `id`. If the idea changed in Glimt after the file was written
+//! (its `updated_at` is newer than the file's `updated`), the edit is not
+//! applied: the file is saved aside as a conflict copy and rewritten from
+//! the database. Deleting a file archives its idea rather than deleting it.
+//!
+//! Ideas in the trash are left out of sync. Trashing an idea removes its
+//! file, restoring it writes the file again and purging it removes whatever
+//! is left.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use chrono::Local;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use rusqlite::Connection;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Listener, Manager, State};
+
+use crate::attachments::{self, Blobs};
+use crate::db::Db;
+use crate::encryption;
+use crate::error::{Error, Result};
+use crate::export;
+use crate::ideas::{self, Idea, IdeaUpdate};
+use crate::settings::{self, SettingsStore};
+use crate::vector_index::VectorIndex;
+
+/// Editors save in bursts (temp file, rename, metadata); wait for quiet.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+/// Emitted to every window when an outside edit lost to a newer one.
+const CONFLICT_EVENT: &str = "vault-conflict";
+/// Subfolder of the vault that exported attachments are copied into.
+const ATTACHMENT_DIR: &str = "attachments";
+
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    /// Not a Glimt note, or one for an idea that no longer exists or is in
+    /// the trash.
+    Ignored,
+    Unchanged,
+    /// Only the file name changed.
+    Moved,
+    Updated(String),
+    Conflict {
+        id: String,
+        copy: PathBuf,
+    },
+}
+
+fn path_key(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
+
+/// The file contents for `idea`, with links to its exported attachments.
+fn render(conn: &Connection, idea: &Idea) -> Result<String> {
+    let links: Vec<_> = attachments::for_idea(conn, &idea.id)?
+        .iter()
+        .map(|stored| {
+            let path = format!("{ATTACHMENT_DIR}/{}", stored.export_name());
+            (stored.attachment.name.clone(), path, stored.is_image())
+        })
+        .collect();
+    Ok(export::with_attachments(
+        export::generate_markdown(idea),
+        &links,
+    ))
+}
+
+/// Write `idea` into `dir` and remember the file. An idea that was already
+/// exported keeps its file, even if it was renamed in the vault since.
+/// Attachments are copied into `dir/attachments` if they aren't there yet.
+fn export_idea(conn: &Connection, blobs: &Blobs, dir: &Path, idea: &Idea) -> Result<PathBuf> {
+    let path = idea
+        .markdown_path
+        .as_deref()
+        .map(PathBuf::from)
+        .filter(|path| path.parent() == Some(dir) && path.is_file())
+        .unwrap_or_else(|| dir.join(export::generate_filename(idea)));
+    std::fs::create_dir_all(dir)?;
+    for stored in attachments::for_idea(conn, &idea.id)? {
+        let target = dir.join(ATTACHMENT_DIR).join(stored.export_name());
+        if !target.is_file() {
+            std::fs::create_dir_all(dir.join(ATTACHMENT_DIR))?;
+            std::fs::copy(blobs.path(&stored.hash), target)?;
+        }
+    }
+    std::fs::write(&path, render(conn, idea)?)?;
+    ideas::set_markdown_path(conn, &idea.id, Some(&path_key(&path)))?;
+    Ok(path)
+}
+
+/// Keep an edit that lost a conflict next to the original. Its `id` key is
+/// renamed so sync does not pick the copy up as the same idea.
+fn write_conflict_copy(path: &Path, content: &str) -> Result<PathBuf> {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let stamp = Local::now().format("%Y-%m-%d %H%M%S");
+    let copy = path.with_file_name(format!("{stem} (conflict {stamp}).md"));
+    std::fs::write(&copy, content.replacen("\nid:", "\nconflict_of:", 1))?;
+    Ok(copy)
+}
+
+/// Apply one file that exists in the vault to the database.
+fn sync_file(conn: &Connection, path: &Path) -> Result<Outcome> {
+    let content = std::fs::read_to_string(path)?;
+    let note = export::parse_markdown(&content);
+    let Some(id) = note.id else {
+        return Ok(Outcome::Ignored);
+    };
+    let Some(idea) = ideas::get(conn, &id)?.filter(|idea| idea.deleted_at.is_none()) else {
+        return Ok(Outcome::Ignored);
+    };
+
+    let key = path_key(path);
+    let moved = idea.markdown_path.as_deref() != Some(key.as_str());
+    if moved {
+        ideas::set_markdown_path(conn, &idea.id, Some(&key))?;
+    }
+    if note.text == idea.text.trim_end_matches('\n') && note.title == idea.title {
+        return Ok(if moved {
+            Outcome::Moved
+        } else {
+            Outcome::Unchanged
+        });
+    }
+
+    if note.updated_at.is_some_and(|at| idea.updated_at > at) {
+        let copy = write_conflict_copy(path, &content)?;
+        std::fs::write(path, render(conn, &idea)?)?;
+        return Ok(Outcome::Conflict { id: idea.id, copy });
+    }
+
+    let update = IdeaUpdate {
+        text: Some(note.text),
+        title: Some(note.title),
+    };
+    ideas::update(conn, &idea.id, &update)?;
+    // Rewrite the frontmatter so `updated` matches the database again.
+    if let Some(updated) = ideas::get(conn, &idea.id)? {
+        std::fs::write(path, render(conn, &updated)?)?;
+    }
+    Ok(Outcome::Updated(idea.id))
+}
+
+/// A file is gone from the vault: archive its idea and forget the path.
+/// Returns the idea's id if one was affected. The files of trashed ideas
+/// are removed on purpose, so their ideas are left as they are.
+fn sync_removed(conn: &Connection, path: &Path) -> Result<Option<String>> {
+    let Some(idea) = ideas::find_by_markdown_path(conn, &path_key(path))?
+        .filter(|idea| idea.deleted_at.is_none())
+    else {
+        return Ok(None);
+    };
+    ideas::set_markdown_path(conn, &idea.id, None)?;
+    if !idea.archived {
+        ideas::set_archived(conn, &idea.id, true)?;
+    }
+    Ok(Some(idea.id))
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultConflict {
+    pub id: String,
+    /// Where the edit that was not applied was saved.
+    pub path: String,
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "md")
+}
+
+/// Sync a batch of changed paths. Existing files go first so a rename,
+/// reported as a removal plus a creation, moves the idea instead of
+/// archiving it.
+fn sync_paths(app: &AppHandle, paths: &BTreeSet<PathBuf>) {
+    let Some(db) = app.try_state::<Db>() else {
+        return;
+    };
+    let mut changed = Vec::new();
+    let mut conflicts = Vec::new();
+    {
+        let conn = db.conn();
+        let (present, missing): (Vec<_>, Vec<_>) = paths.iter().partition(|path| path.is_file());
+        for path in present {
+            match sync_file(&conn, path) {
+                Ok(Outcome::Updated(id)) => {
+                    crate::log_err(
+                        "drop stale embedding",
+                        crate::embeddings::delete_for_idea(&conn, &id),
+                    );
+                    changed.push(id);
+                }
+                Ok(Outcome::Conflict { id, copy }) => {
+                    log::warn!("Vault edit to {} conflicts with Glimt", path.display());
+                    conflicts.push(VaultConflict {
+                        id,
+                        path: path_key(&copy),
+                    });
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Could not sync {}: {e}", path.display()),
+            }
+        }
+        for path in missing {
+            match sync_removed(&conn, path) {
+                Ok(Some(id)) => changed.push(id),
+                Ok(None) => {}
+                Err(e) => log::warn!("Could not sync removal of {}: {e}", path.display()),
+            }
+        }
+    }
+
+    if let Some(index) = app.try_state::<VectorIndex>() {
+        for id in &changed {
+            index.remove(id);
+        }
+    }
+    if !changed.is_empty() {
+        crate::log_err(
+            "emit vault changes",
+            app.emit(ideas::CHANGED_EVENT, &changed),
+        );
+    }
+    for conflict in conflicts {
+        crate::log_err("emit vault conflict", app.emit(CONFLICT_EVENT, &conflict));
+    }
+}
+
+fn markdown_files(dir: &Path) -> BTreeSet<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return BTreeSet::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| is_markdown(path))
+        .collect()
+}
+
+/// Body of the watcher thread. Picks up edits made while Glimt was closed,
+/// then syncs each burst of events until the watcher is dropped.
+fn run(app: &AppHandle, dir: &Path, events: &mpsc::Receiver<notify::Result<notify::Event>>) {
+    // Only existing files: a folder that is missing or unmounted at startup
+    // must not archive everything.
+    sync_paths(app, &markdown_files(dir));
+
+    let mut pending = BTreeSet::new();
+    loop {
+        let next = if pending.is_empty() {
+            events.recv().map_err(|_| RecvTimeoutError::Disconnected)
+        } else {
+            events.recv_timeout(DEBOUNCE)
+        };
+        match next {
+            Ok(Ok(event)) => {
+                if !matches!(event.kind, EventKind::Access(_)) {
+                    pending.extend(event.paths.into_iter().filter(|path| is_markdown(path)));
+                }
+            }
+            Ok(Err(e)) => log::warn!("Vault watcher error: {e}"),
+            Err(RecvTimeoutError::Timeout) => sync_paths(app, &std::mem::take(&mut pending)),
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+    }
+}
+
+fn watch(app: &AppHandle, dir: &Path) -> Result<RecommendedWatcher> {
+    let watch_err =
+        |e: notify::Error| Error::Invalid(format!("could not watch {}: {e}", dir.display()));
+    std::fs::create_dir_all(dir)?;
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).map_err(watch_err)?;
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .map_err(watch_err)?;
+
+    let app = app.clone();
+    let dir = dir.to_owned();
+    std::thread::Builder::new()
+        .name("glimt-vault".into())
+        .spawn(move || run(&app, &dir, &rx))?;
+    Ok(watcher)
+}
+
+/// Managed state: the folder being watched, if export is on.
+#[derive(Default)]
+pub struct Vault {
+    watching: Mutex<Option<(PathBuf, RecommendedWatcher)>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn export_dir(app: &AppHandle) -> Option<PathBuf> {
+    let settings = app.state::<SettingsStore>().get();
+    settings
+        .export_enabled
+        .then_some(settings.export_dir)
+        .flatten()
+        .map(PathBuf::from)
+}
+
+impl Vault {
+    /// Watch the export folder from settings, and follow later changes to it.
+    pub fn start(app: &AppHandle) {
+        app.state::<Vault>().configure(app);
+        for event in [settings::CHANGED_EVENT, encryption::UNLOCKED_EVENT] {
+            let handle = app.clone();
+            app.listen_any(event, move |_| {
+                handle.state::<Vault>().configure(&handle);
+            });
+        }
+    }
+
+    /// Start, move or stop the watcher to match the export settings. Edits
+    /// made while the database is locked are picked up by the scan once it
+    /// is unlocked.
+    fn configure(&self, app: &AppHandle) {
+        let dir = export_dir(app).filter(|_| app.try_state::<Db>().is_some());
+        let mut watching = lock(&self.watching);
+        if watching.as_ref().map(|(current, _)| current) == dir.as_ref() {
+            return;
+        }
+        // Dropping the old watcher closes its channel and ends its thread.
+        *watching = None;
+        let Some(dir) = dir else {
+            return;
+        };
+        match watch(app, &dir) {
+            Ok(watcher) => *watching = Some((dir, watcher)),
+            Err(e) => log::warn!("Vault sync disabled: {e}"),
+        }
+    }
+}
+
+/// Rewrite the files of ideas changed on the Rust side, such as a renamed
+/// tag or a restored idea, when export is on. Ideas in the trash are skipped.
+pub(crate) fn reexport(app: &AppHandle, conn: &Connection, ids: &[String]) {
+    let Some(dir) = export_dir(app) else {
+        return;
+    };
+    let blobs = app.state::<Blobs>();
+    for id in ids {
+        match ideas::get(conn, id) {
+            Ok(Some(idea)) if idea.deleted_at.is_none() => {
+                crate::log_err("re-export idea", export_idea(conn, &blobs, &dir, &idea))
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("re-export idea: {e}"),
+        }
+    }
+}
+
+/// Delete an idea's exported file, if it is still there.
+pub(crate) fn remove_file(markdown_path: Option<&str>) -> Result<()> {
+    match markdown_path.map(Path::new) {
+        Some(path) if path.is_file() => Ok(std::fs::remove_file(path)?),
+        _ => Ok(()),
+    }
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+/// Write the idea to the export folder if export is on. Returns the file's
+/// path, or `None` when export is off or the idea is in the trash.
+#[tauri::command]
+pub fn export_idea_markdown(
+    app: AppHandle,
+    db: State<'_, Db>,
+    blobs: State<'_, Blobs>,
+    id: String,
+) -> Result<Option<String>> {
+    let Some(dir) = export_dir(&app) else {
+        return Ok(None);
+    };
+    let conn = db.conn();
+    let idea = ideas::get(&conn, &id)?.ok_or_else(|| Error::NotFound(id.clone()))?;
+    if idea.deleted_at.is_some() {
+        return Ok(None);
+    }
+    export_idea(&conn, &blobs, &dir, &idea).map(|path| Some(path_key(&path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_dir::TestDir;
+
+    fn conn() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        crate::migrations::run(&mut conn, None).unwrap();
+        conn
+    }
+
+    /// Tests export ideas without attachments, so the store stays empty.
+    fn blobs(dir: &Path) -> Blobs {
+        Blobs::new(dir.join(".blobs"))
+    }
+
+    #[test]
+    fn applies_edits_and_tracks_renames() {
+        let conn = conn();
+        let dir = TestDir::new("vault-edit");
+        let idea = ideas::create(&conn, "original", None).unwrap();
+        let path = export_idea(&conn, &blobs(&dir), &dir, &idea).unwrap();
+        assert_eq!(sync_file(&conn, &path).unwrap(), Outcome::Unchanged);
+
+        let renamed = dir.join("Renamed.md");
+        std::fs::rename(&path, &renamed).unwrap();
+        assert_eq!(sync_file(&conn, &renamed).unwrap(), Outcome::Moved);
+        assert!(sync_removed(&conn, &path).unwrap().is_none());
+
+        let content = std::fs::read_to_string(&renamed).unwrap();
+        std::fs::write(&renamed, content.replace("original", "edited in the vault")).unwrap();
+        assert_eq!(
+            sync_file(&conn, &renamed).unwrap(),
+            Outcome::Updated(idea.id.clone())
+        );
+        let stored = ideas::get(&conn, &idea.id).unwrap().unwrap();
+        assert_eq!(stored.text, "edited in the vault");
+        assert_eq!(stored.markdown_path, Some(path_key(&renamed)));
+        // The rewritten file is in sync again.
+        assert_eq!(sync_file(&conn, &renamed).unwrap(), Outcome::Unchanged);
+
+        std::fs::remove_file(&renamed).unwrap();
+        assert_eq!(
+            sync_removed(&conn, &renamed).unwrap(),
+            Some(idea.id.clone())
+        );
+        assert!(ideas::get(&conn, &idea.id).unwrap().unwrap().archived);
+    }
+
+    #[test]
+    fn newer_database_edits_win_and_keep_a_conflict_copy() {
+        let conn = conn();
+        let dir = TestDir::new("vault-conflict");
+        let idea = ideas::create(&conn, "first", None).unwrap();
+        let path = export_idea(&conn, &blobs(&dir), &dir, &idea).unwrap();
+        let exported = std::fs::read_to_string(&path).unwrap();
+
+        std::thread::sleep(Duration::from_millis(5));
+        let edit = IdeaUpdate {
+            text: Some("changed in Glimt".into()),
+            title: None,
+        };
+        ideas::update(&conn, &idea.id, &edit).unwrap();
+        std::fs::write(&path, exported.replace("first", "changed in the vault")).unwrap();
+
+        let Outcome::Conflict { copy, .. } = sync_file(&conn, &path).unwrap() else {
+            panic!("expected a conflict");
+        };
+        assert_eq!(
+            ideas::get(&conn, &idea.id).unwrap().unwrap().text,
+            "changed in Glimt"
+        );
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("changed in Glimt"));
+        assert!(std::fs::read_to_string(&copy)
+            .unwrap()
+            .contains("changed in the vault"));
+        assert_eq!(sync_file(&conn, &copy).unwrap(), Outcome::Ignored);
+    }
+
+    #[test]
+    fn trashed_ideas_leave_the_vault() {
+        let conn = conn();
+        let dir = TestDir::new("vault-trash");
+        let idea = ideas::create(&conn, "draft", None).unwrap();
+        let path = export_idea(&conn, &blobs(&dir), &dir, &idea).unwrap();
+        let exported = std::fs::read_to_string(&path).unwrap();
+
+        crate::trash::trash(&conn, &idea.id).unwrap();
+        assert!(!path.exists());
+        assert!(sync_removed(&conn, &path).unwrap().is_none());
+
+        // A copy that comes back, say from a vault backup, is left alone.
+        std::fs::write(&path, exported.replace("draft", "edited")).unwrap();
+        assert_eq!(sync_file(&conn, &path).unwrap(), Outcome::Ignored);
+        std::fs::remove_file(&path).unwrap();
+        assert!(sync_removed(&conn, &path).unwrap().is_none());
+        let trashed = ideas::get(&conn, &idea.id).unwrap().unwrap();
+        assert_eq!((trashed.text.as_str(), trashed.archived), ("draft", false));
+
+        crate::trash::restore(&conn, &idea.id).unwrap();
+        let restored = ideas::get(&conn, &idea.id).unwrap().unwrap();
+        assert_eq!(
+            export_idea(&conn, &blobs(&dir), &dir, &restored).unwrap(),
+            path
+        );
+        assert_eq!(sync_file(&conn, &path).unwrap(), Outcome::Unchanged);
+    }
+}
@@ -0,0 +1,321 @@
+//! Per-model ANN indexes over the `embeddings` table, kept in memory and
+//! persisted to the app cache directory so startup does not rebuild them.
+//!
+//! An index is built (or loaded) the first time its model is searched and
+//! then follows `store_embedding` / `delete_*` incrementally. A persisted
+//! index is only trusted when its fingerprint still matches the table.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::db::Db;
+use crate::embeddings;
+use crate::error::{Error, Result};
+use crate::hnsw::Hnsw;
+
+/// Row count and newest `created_at` for a model. Any store or delete moves
+/// at least one of them, so a mismatch means the file on disk is stale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Fingerprint {
+    count: i64,
+    newest: i64,
+}
+
+impl Fingerprint {
+    fn read(conn: &Connection, model: &str) -> Result<Self> {
+        Ok(conn.query_row(
+            "SELECT COUNT(*), COALESCE(MAX(created_at), 0) FROM embeddings WHERE model = ?1",
+            params![model],
+            |row| {
+                Ok(Self {
+                    count: row.get(0)?,
+                    newest: row.get(1)?,
+                })
+            },
+        )?)
+    }
+}
+
+struct Entry {
+    index: Hnsw,
+    /// Whether the in-memory index has changes the file on disk lacks.
+    dirty: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Neighbour {
+    pub idea_id: String,
+    pub score: f32,
+}
+
+/// Managed state: loaded indexes keyed by embedding model.
+pub struct VectorIndex {
+    dir: PathBuf,
+    models: Mutex<HashMap<String, Entry>>,
+}
+
+impl VectorIndex {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            models: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Entry>> {
+        self.models
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn path(&self, model: &str) -> PathBuf {
+        let safe: String = model
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        self.dir.join(format!("{safe}.hnsw"))
+    }
+
+    /// Top-`k` ideas for `query` under `model`, loading or building the index
+    /// on first use.
+    pub fn search(
+        &self,
+        conn: &Connection,
+        model: &str,
+        query: &[f32],
+        k: usize,
+    ) -> Result<Vec<Neighbour>> {
+        let mut models = self.lock();
+        if !models.contains_key(model) {
+            let entry = self.open(conn, model)?;
+            models.insert(model.to_owned(), entry);
+        }
+        let entry = models.get(model).expect("inserted above");
+        if entry.index.len() > 0 && query.len() != entry.index.dims() {
+            return Err(Error::Invalid(format!(
+                "query has {} dimensions, index has {}",
+                query.len(),
+                entry.index.dims()
+            )));
+        }
+        Ok(entry
+            .index
+            .search(query, k)
+            .into_iter()
+            .map(|(idea_id, score)| Neighbour { idea_id, score })
+            .collect())
+    }
+
+    /// Mirror a stored embedding. Models whose index is not loaded yet pick
+    /// the row up from the table when they are.
+    pub fn upsert(&self, model: &str, idea_id: &str, vector: &[f32]) {
+        let mut models = self.lock();
+        let Some(entry) = models.get_mut(model) else {
+            return;
+        };
+        if entry.index.dims() != vector.len() {
+            // The model's output size changed; rebuild from the table next time.
+            models.remove(model);
+            return;
+        }
+        entry.index.insert(idea_id, vector);
+        entry.dirty = true;
+        if entry.index.needs_compaction() {
+            entry.index = compact(&entry.index);
+        }
+    }
+
+    /// Drop an idea from every model, e.g. when the idea itself is deleted.
+    pub fn remove(&self, idea_id: &str) {
+        for entry in self.lock().values_mut() {
+            if entry.index.remove(idea_id) {
+                entry.dirty = true;
+            }
+        }
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+        if let Ok(files) = std::fs::read_dir(&self.dir) {
+            for file in files.flatten() {
+                if file.path().extension().is_some_and(|ext| ext == "hnsw") {
+                    crate::log_err("remove vector index", std::fs::remove_file(file.path()));
+                }
+            }
+        }
+    }
+
+    /// Write indexes with unsaved changes. Called on exit; a crash just means
+    /// the next launch rebuilds from the table.
+    pub fn persist(&self, conn: &Connection) {
+        for (model, entry) in self.lock().iter_mut() {
+            if !entry.dirty {
+                continue;
+            }
+            match self.save(conn, model, &entry.index) {
+                Ok(()) => entry.dirty = false,
+                Err(e) => log::warn!("Could not save vector index for {model}: {e}"),
+            }
+        }
+    }
+
+    fn open(&self, conn: &Connection, model: &str) -> Result<Entry> {
+        let fingerprint = Fingerprint::read(conn, model)?;
+        let path = self.path(model);
+        match load(&path) {
+            Ok((saved, index)) if saved == fingerprint => {
+                return Ok(Entry {
+                    index,
+                    dirty: false,
+                })
+            }
+            Ok(_) => log::info!("Vector index for {model} is stale, rebuilding"),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => log::warn!("Ignoring unreadable {}: {e}", path.display()),
+        }
+
+        let rows = embeddings::load_all(conn, model)?;
+        let dims = rows.first().map_or(0, |row| row.vector.len());
+        let mut index = Hnsw::new(dims);
+        for row in rows.iter().filter(|row| row.vector.len() == dims) {
+            index.insert(&row.idea_id, &row.vector);
+        }
+        log::info!("Built vector index for {model} ({} entries)", index.len());
+        if let Err(e) = self.save(conn, model, &index) {
+            log::warn!("Could not save vector index for {model}: {e}");
+        }
+        Ok(Entry {
+            index,
+            dirty: false,
+        })
+    }
+
+    fn save(&self, conn: &Connection, model: &str, index: &Hnsw) -> Result<()> {
+        let fingerprint = Fingerprint::read(conn, model)?;
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.path(model);
+        let tmp = path.with_extension("hnsw.tmp");
+        let mut out = BufWriter::new(File::create(&tmp)?);
+        out.write_all(&fingerprint.count.to_le_bytes())?;
+        out.write_all(&fingerprint.newest.to_le_bytes())?;
+        index.write_to(&mut out)?;
+        out.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+}
+
+fn load(path: &Path) -> std::io::Result<(Fingerprint, Hnsw)> {
+    let file = File::open(path)?;
+    // Past the two fingerprint fields.
+    let len = file.metadata()?.len().saturating_sub(16);
+    let mut input = BufReader::new(file);
+    let mut buf = [0u8; 8];
+    input.read_exact(&mut buf)?;
+    let count = i64::from_le_bytes(buf);
+    input.read_exact(&mut buf)?;
+    let newest = i64::from_le_bytes(buf);
+    let index = Hnsw::read_from(&mut input, len)?;
+    Ok((Fingerprint { count, newest }, index))
+}
+
+fn compact(index: &Hnsw) -> Hnsw {
+    let mut fresh = Hnsw::new(index.dims());
+    for (id, vector) in index.entries() {
+        fresh.insert(id, vector);
+    }
+    fresh
+}
+
+// ── Commands ─────────────────────────────────────────────
+
+/// Approximate nearest neighbours for an already-embedded query. Runs off the
+/// main thread because the first call per model may build the index.
+#[tauri::command]
+pub async fn search_similar(
+    app: AppHandle,
+    model: String,
+    vector: Vec<f32>,
+    top_k: Option<usize>,
+) -> Result<Vec<Neighbour>> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let db = app.state::<Db>();
+        let index = app.state::<VectorIndex>();
+        let conn = db.conn();
+        index.search(&conn, &model, &vector, top_k.unwrap_or(20))
+    })
+    .await
+    .map_err(|e| Error::Invalid(format!("vector search failed: {e}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ideas;
+
+    fn setup() -> (Connection, VectorIndex, PathBuf) {
+        let mut conn = Connection::open_in_memory().unwrap();
+        crate::migrations::run(&mut conn, None).unwrap();
+        let dir = std::env::temp_dir().join(format!(
+            "glimt-index-{}-{}",
+            std::process::id(),
+            uuid::Uuid::new_v4()
+        ));
+        (conn, VectorIndex::new(dir.clone()), dir)
+    }
+
+    #[test]
+    fn builds_from_table_and_follows_changes() {
+        let (conn, index, dir) = setup();
+        let a = ideas::create(&conn, "a", None).unwrap();
+        let b = ideas::create(&conn, "b", None).unwrap();
+        embeddings::store(&conn, &a.id, "m", &[1.0, 0.0]).unwrap();
+
+        let hits = index.search(&conn, "m", &[1.0, 0.1], 5).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].idea_id, a.id);
+
+        embeddings::store(&conn, &b.id, "m", &[0.0, 1.0]).unwrap();
+        index.upsert("m", &b.id, &[0.0, 1.0]);
+        assert_eq!(
+            index.search(&conn, "m", &[0.1, 1.0], 1).unwrap()[0].idea_id,
+            b.id
+        );
+
+        index.remove(&b.id);
+        assert_eq!(index.search(&conn, "m", &[0.1, 1.0], 5).unwrap().len(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reloads_persisted_index_only_while_fresh() {
+        let (conn, index, dir) = setup();
+        let a = ideas::create(&conn, "a", None).unwrap();
+        embeddings::store(&conn, &a.id, "m", &[1.0, 0.0]).unwrap();
+        index.search(&conn, "m", &[1.0, 0.0], 1).unwrap();
+        assert!(index.path("m").exists());
+
+        let (saved, _) = load(&index.path("m")).unwrap();
+        assert_eq!(saved, Fingerprint::read(&conn, "m").unwrap());
+
+        // A row written while the app was closed makes the file stale.
+        let b = ideas::create(&conn, "b", None).unwrap();
+        embeddings::store(&conn, &b.id, "m", &[0.0, 1.0]).unwrap();
+        let reopened = VectorIndex::new(dir.clone());
+        assert_eq!(
+            reopened.search(&conn, "m", &[0.0, 1.0], 5).unwrap().len(),
+            2
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -14,25 +14,29 @@ import {
   CommandShortcut,
 } from '@/components/ui/command'
 import { Toaster } from '@/components/ui/sonner'
+import { AppLockScreen } from '@/components/app-lock-screen'
 import { ErrorBoundary } from '@/components/error-boundary'
+import { UnlockScreen } from '@/components/unlock-screen'
 import { TooltipProvider } from '@/components/ui/tooltip'
 import { Dashboard } from '@/features/dashboard/dashboard'
 import { Settings } from '@/features/settings/settings'
 import { AppProvider, type AppContextValue } from '@/lib/app-context'
+import { useAppLock } from '@/lib/hooks/use-app-lock'
 import { useAutoUpdate } from '@/lib/hooks/use-auto-update'
 import { useDatabase } from '@/lib/hooks/use-database'
 import { useIdeaActions } from '@/lib/hooks/use-idea-actions'
 import { useModelNotifications } from '@/lib/hooks/use-model-notifications'
 import { useTheme } from '@/lib/hooks/use-theme'
+import { migrateLegacySettings } from '@/lib/settings'
 import {
-  changeCaptureShortcut,
-  changeRecordShortcut,
-  getSavedCaptureShortcut,
-  getSavedRecordShortcut,
+  DEFAULT_CAPTURE_SHORTCUT,
+  DEFAULT_CLIP_SHORTCUT,
+  DEFAULT_RECORD_SHORTCUT,
+  getShortcuts,
   parseShortcutKeys,
-  registerCaptureShortcut,
-  registerRecordShortcut,
+  setShortcut,
 } from '@/lib/shortcut'
+import type { ShortcutAction, ShortcutsStatus } from '@/lib/types'
 import {
   RiAddLine,
   RiArchiveLine,
@@ -49,27 +53,50 @@ type View = 'dashboard' | 'settings'
 export function App() {
   const [view, setView] = useState<View>('dashboard')
   const [commandOpen, setCommandOpen] = useState(false)
-  const [captureShortcut, setCaptureShortcut] = useState(getSavedCaptureShortcut)
-  const [recordShortcut, setRecordShortcut] = useState(getSavedRecordShortcut)
+  const [shortcuts, setShortcuts] = useState<ShortcutsStatus | null>(null)
 
-  const { dbReady, dbError } = useDatabase()
+  const { dbReady, dbError, locked } = useDatabase()
+  const { locked: appLocked } = useAppLock({ trackActivity: true })
   const { theme, onThemeChange } = useTheme()
   useModelNotifications()
   const autoUpdate = useAutoUpdate()
   const ideaActions = useIdeaActions()
 
-  // Load ideas once DB is ready
+  // Load ideas once DB is ready, and again on unlock since reads are refused while locked
   useEffect(() => {
-    if (dbReady) {
+    if (dbReady && !appLocked) {
       ideaActions.loadIdeas()
     }
-    // eslint-disable-next-line react-hooks/exhaustive-deps -- loadIdeas is stable, only run when DB becomes ready
-  }, [dbReady])
+    // eslint-disable-next-line react-hooks/exhaustive-deps -- loadIdeas is stable, only run when DB becomes ready or the app unlocks
+  }, [dbReady, appLocked])
 
-  // Register global shortcuts
+  // Global shortcuts are registered by Rust; load their state and conflicts
   useEffect(() => {
-    registerCaptureShortcut().catch(console.error)
-    registerRecordShortcut().catch(console.error)
+    let unlisten: (() => void) | undefined
+
+    migrateLegacySettings()
+      .catch((error: unknown) => console.error('Settings migration failed:', error))
+      .then(getShortcuts)
+      .then((status) => {
+        setShortcuts(status)
+        for (const binding of [status.capture, status.record]) {
+          if (binding.error) toast.error(`Shortcut unavailable: ${binding.error}`)
+        }
+      })
+      .catch(console.error)
+
+    import('@tauri-apps/api/event')
+      .then(({ listen }) =>
+        listen<ShortcutsStatus>('shortcuts-changed', (event) => setShortcuts(event.payload)),
+      )
+      .then((fn) => {
+        unlisten = fn
+      })
+      .catch(() => {
+        // Expected when not running in Tauri context (e.g. Vite dev server)
+      })
+
+    return () => unlisten?.()
   }, [])
 
   // Command palette keyboard shortcut
@@ -84,29 +111,36 @@ export function App() {
     return () => document.removeEventListener('keydown', handleKeyDown)
   }, [])
 
-  const handleCaptureShortcutChange = useCallback(async (newShortcut: string) => {
+  const changeShortcut = useCallback(async (action: ShortcutAction, newShortcut: string) => {
     try {
-      await changeCaptureShortcut(newShortcut)
-      setCaptureShortcut(newShortcut)
+      setShortcuts(await setShortcut(action, newShortcut))
       const keys = parseShortcutKeys(newShortcut).join('+')
-      toast.success(`Capture shortcut changed to ${keys}`)
+      const label = { capture: 'Capture', record: 'Record', clip: 'Clipboard' }[action]
+      toast.success(`${label} shortcut changed to ${keys}`)
     } catch (error: unknown) {
       const message = error instanceof Error ? error.message : String(error)
       toast.error(`Failed to set shortcut: ${message}`)
     }
   }, [])
 
-  const handleRecordShortcutChange = useCallback(async (newShortcut: string) => {
-    try {
-      await changeRecordShortcut(newShortcut)
-      setRecordShortcut(newShortcut)
-      const keys = parseShortcutKeys(newShortcut).join('+')
-      toast.success(`Record shortcut changed to ${keys}`)
-    } catch (error: unknown) {
-      const message = error instanceof Error ? error.message : String(error)
-      toast.error(`Failed to set shortcut: ${message}`)
-    }
-  }, [])
+  const handleCaptureShortcutChange = useCallback(
+    (newShortcut: string) => changeShortcut('capture', newShortcut),
+    [changeShortcut],
+  )
+
+  const handleRecordShortcutChange = useCallback(
+    (newShortcut: string) => changeShortcut('record', newShortcut),
+    [changeShortcut],
+  )
+
+  const handleClipShortcutChange = useCallback(
+    (newShortcut: string) => changeShortcut('clip', newShortcut),
+    [changeShortcut],
+  )
+
+  const captureShortcut = shortcuts?.capture.shortcut ?? DEFAULT_CAPTURE_SHORTCUT
+  const recordShortcut = shortcuts?.record.shortcut ?? DEFAULT_RECORD_SHORTCUT
+  const clipShortcut = shortcuts?.clip.shortcut ?? DEFAULT_CLIP_SHORTCUT
 
   const contextValue = useMemo<AppContextValue>(
     () => ({
@@ -115,9 +149,14 @@ export function App() {
       ...ideaActions,
       ...autoUpdate,
       captureShortcut,
+      captureShortcutError: shortcuts?.capture.error ?? null,
       onCaptureShortcutChange: handleCaptureShortcutChange,
       recordShortcut,
+      recordShortcutError: shortcuts?.record.error ?? null,
       onRecordShortcutChange: handleRecordShortcutChange,
+      clipShortcut,
+      clipShortcutError: shortcuts?.clip.error ?? null,
+      onClipShortcutChange: handleClipShortcutChange,
     }),
     [
       theme,
@@ -128,9 +167,20 @@ export function App() {
       handleCaptureShortcutChange,
       recordShortcut,
       handleRecordShortcutChange,
+      clipShortcut,
+      handleClipShortcutChange,
+      shortcuts,
     ],
   )
 
+  if (locked) {
+    return <UnlockScreen />
+  }
+
+  if (appLocked) {
+    return <AppLockScreen />
+  }
+
   if (dbError) {
     return (
       <div className="flex h-screen items-center justify-center bg-background p-8">
@@ -1,24 +1,36 @@
 import { ErrorBoundary } from '@/components/error-boundary'
+import { UnlockScreen } from '@/components/unlock-screen'
 import { CompactCaptureWindow } from '@/features/capture/compact-capture-window'
 import { embedForStorage, preloadEmbeddingModel } from '@/lib/ai/embeddings'
 import { generateTitle } from '@/lib/ai/title-generation'
 import { preloadWhisperModel } from '@/lib/ai/whisper'
-import { createIdea, getIdea, initDb, storeEmbedding, updateIdea } from '@/lib/db'
+import { CLIP_CAPTURED_EVENT, takeClip } from '@/lib/clip'
+import { createIdea, storeEmbedding, updateIdea } from '@/lib/db'
 import { exportIdea } from '@/lib/export-service'
+import { useAppLock } from '@/lib/hooks/use-app-lock'
+import { useDbReady } from '@/lib/hooks/use-db-ready'
+import { useTheme } from '@/lib/hooks/use-theme'
+import { getSettings } from '@/lib/settings'
 import { STORAGE_KEYS } from '@/lib/storage-keys'
+import type { Clip } from '@/lib/types'
 import { useCallback, useEffect, useRef, useState } from 'react'
 
 export type SaveState = 'idle' | 'saved'
 
 export function CaptureApp() {
-  const [dbReady, setDbReady] = useState(false)
+  const { dbReady, locked } = useDbReady()
+  const { locked: appLocked } = useAppLock()
   const [editorKey, setEditorKey] = useState(0)
   const [saveState, setSaveState] = useState<SaveState>('idle')
   const [autoRecord, setAutoRecord] = useState(false)
+  const [clip, setClip] = useState<Clip | null>(null)
+  const clipRef = useRef(clip)
+  clipRef.current = clip
   const isRecordingRef = useRef(false)
   const isSavingRef = useRef(false)
   const isDraggingRef = useRef(false)
   const blurTimeoutRef = useRef<ReturnType<typeof setTimeout> | null>(null)
+  useTheme()
 
   // Make html+body transparent so rounded window corners show through
   useEffect(() => {
@@ -34,20 +46,28 @@ export function CaptureApp() {
     return () => document.documentElement.classList.remove('capture-transparent')
   }, [])
 
+  // Pre-fill the editor with text left by the clip shortcut, if any
+  const loadClip = useCallback(async () => {
+    try {
+      const taken = await takeClip()
+      if (taken) {
+        setClip(taken)
+        setEditorKey((k) => k + 1)
+      }
+    } catch {
+      // Expected when not running in Tauri context (e.g. Vite dev server)
+    }
+  }, [])
+
   useEffect(() => {
-    initDb()
-      .then(() => {
-        setDbReady(true)
-        preloadEmbeddingModel()
-        const savedSttModel = localStorage.getItem(STORAGE_KEYS.STT_MODEL)
-        if (savedSttModel) {
-          preloadWhisperModel(savedSttModel)
-        }
-      })
-      .catch((error: unknown) => {
-        console.error('Capture DB init failed:', error)
+    if (!dbReady) return
+    preloadEmbeddingModel()
+    getSettings()
+      .then(({ sttModel }) => {
+        if (sttModel) preloadWhisperModel(sttModel)
       })
-  }, [])
+      .catch(console.error)
+  }, [dbReady])
 
   // Handle window focus/blur
   useEffect(() => {
@@ -71,7 +91,9 @@ export function CaptureApp() {
           }
           // Reset editor on focus so it's clean for a new idea
           setAutoRecord(false)
+          setClip(null)
           setEditorKey((k) => k + 1)
+          void loadClip()
         } else if (!isRecordingRef.current && !isSavingRef.current && !isDraggingRef.current) {
           // Debounce hide — resize/move events cancel this if the window is still active
           blurTimeoutRef.current = setTimeout(() => {
@@ -99,7 +121,7 @@ export function CaptureApp() {
     return () => {
       cleanup.then((unlistenAll) => unlistenAll())
     }
-  }, [])
+  }, [loadClip])
 
   // Listen for start-recording event from quick record shortcut
   useEffect(() => {
@@ -122,6 +144,22 @@ export function CaptureApp() {
     }
   }, [])
 
+  // A clip that arrives while the window already has focus
+  useEffect(() => {
+    let unlisten: (() => void) | undefined
+    import('@tauri-apps/api/event')
+      .then(({ listen }) => listen(CLIP_CAPTURED_EVENT, () => void loadClip()))
+      .then((fn) => {
+        unlisten = fn
+      })
+      .catch(() => {
+        // Expected when not running in Tauri context (e.g. Vite dev server)
+      })
+    return () => {
+      unlisten?.()
+    }
+  }, [loadClip])
+
   const finishSave = useCallback(async (ideaId: string, text: string) => {
     // Increment capture count for conditional hints
     const count = parseInt(localStorage.getItem(STORAGE_KEYS.CAPTURE_COUNT) ?? '0', 10)
@@ -158,22 +196,15 @@ export function CaptureApp() {
     await emit('idea-saved')
 
     // Background export (fire-and-forget)
-    const exportEnabled = localStorage.getItem(STORAGE_KEYS.EXPORT_ENABLED) === 'true'
-    const exportDir = localStorage.getItem(STORAGE_KEYS.EXPORT_DIR)
-    if (exportEnabled && exportDir) {
-      getIdea(ideaId)
-        .then((idea) => {
-          if (idea) return exportIdea(idea, exportDir)
-        })
-        .catch(console.error)
-    }
+    exportIdea(ideaId).catch(console.error)
 
     // Background title generation (fire-and-forget)
-    const autoTitleEnabled = localStorage.getItem(STORAGE_KEYS.AUTO_TITLE_ENABLED) === 'true'
+    const { autoTitleEnabled } = await getSettings()
     if (autoTitleEnabled) {
       generateTitle(text)
         .then(async (title) => {
           await updateIdea(ideaId, { title })
+          await exportIdea(ideaId)
           const { emit: emitEvent } = await import('@tauri-apps/api/event')
           await emitEvent('title-generated')
         })
@@ -184,7 +215,7 @@ export function CaptureApp() {
   const handleSave = useCallback(
     async (text: string) => {
       try {
-        const idea = await createIdea(text)
+        const idea = await createIdea(text, { sourceUrl: clipRef.current?.sourceUrl })
         await finishSave(idea.id, text)
       } catch (error) {
         console.error('Failed to save idea:', error)
@@ -218,6 +249,10 @@ export function CaptureApp() {
     isDraggingRef.current = true
   }, [])
 
+  if (locked) {
+    return <UnlockScreen compact />
+  }
+
   if (!dbReady) {
     return null
   }
@@ -233,6 +268,8 @@ export function CaptureApp() {
         onDragStart={handleDragStart}
         saveState={saveState}
         autoRecord={autoRecord}
+        initialText={clip?.text}
+        canEditLast={!appLocked}
       />
     </ErrorBoundary>
   )
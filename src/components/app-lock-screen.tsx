@@ -0,0 +1,60 @@
+import { useCallback, useState, type FormEvent } from 'react'
+import { Button } from '@/components/ui/button'
+import { Input } from '@/components/ui/input'
+import { unlockApp } from '@/lib/app-lock'
+import { RiLockLine } from '@remixicon/react'
+
+/**
+ * Covers the main window while the app is locked. Quick capture keeps
+ * working from the shortcut in the meantime.
+ */
+export function AppLockScreen() {
+  const [pin, setPin] = useState('')
+  const [unlocking, setUnlocking] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  const handleSubmit = useCallback(
+    async (event: FormEvent) => {
+      event.preventDefault()
+      if (!pin) return
+      setUnlocking(true)
+      setError(null)
+      try {
+        await unlockApp(pin)
+      } catch (err) {
+        setError(String(err))
+        setPin('')
+        setUnlocking(false)
+      }
+    },
+    [pin],
+  )
+
+  return (
+    <div className="flex h-screen items-center justify-center bg-background p-8">
+      <form onSubmit={handleSubmit} className="w-full max-w-xs space-y-4">
+        <div className="flex items-center gap-2">
+          <RiLockLine className="size-5 text-primary" />
+          <h1 className="text-xl font-semibold text-foreground">Glimt is locked</h1>
+        </div>
+        <p className="text-sm text-muted-foreground">
+          Enter your PIN to see your ideas. Quick capture still works while locked.
+        </p>
+        <Input
+          type="password"
+          autoFocus
+          placeholder="PIN"
+          value={pin}
+          onChange={(e) => setPin(e.target.value)}
+          disabled={unlocking}
+        />
+        {error && <p className="text-sm text-destructive">{error}</p>}
+        <div className="flex justify-end">
+          <Button type="submit" size="sm" disabled={unlocking || !pin}>
+            {unlocking ? 'Unlocking…' : 'Unlock'}
+          </Button>
+        </div>
+      </form>
+    </div>
+  )
+}
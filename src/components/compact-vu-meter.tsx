@@ -1,10 +1,20 @@
 import { useEffect, useRef } from 'react'
 
-export function CompactVuMeter({ stream }: { stream: MediaStream }) {
+// Either analyse a live stream, or show a level measured elsewhere (the
+// native recorder reports peaks in 0..1).
+type CompactVuMeterProps = { stream: MediaStream; level?: never } | { level: number; stream?: never }
+
+export function CompactVuMeter({ stream, level }: CompactVuMeterProps) {
   const canvasRef = useRef<HTMLDivElement>(null)
   const levelRef = useRef(0)
 
   useEffect(() => {
+    if (level === undefined || !canvasRef.current) return
+    canvasRef.current.style.width = `${Math.max(4, Math.min(1, level) * 64)}px`
+  }, [level])
+
+  useEffect(() => {
+    if (!stream) return
     let audioCtx: AudioContext | null = null
     let animId: number | null = null
 
@@ -0,0 +1,103 @@
+import { readAttachment, removeAttachment, saveAttachment } from '@/lib/attachments'
+import type { Attachment } from '@/lib/types'
+import { save } from '@tauri-apps/plugin-dialog'
+import { RiCloseLine, RiFileLine } from '@remixicon/react'
+import { useEffect, useState } from 'react'
+import { toast } from 'sonner'
+
+function formatSize(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`
+  if (bytes < 1024 * 1024) return `${Math.round(bytes / 1024)} KB`
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`
+}
+
+/** Loads the thumbnail into a blob URL, revoked when the attachment goes away. */
+function useThumbnail({ id, mime, thumbnail }: Attachment): string | null {
+  const [url, setUrl] = useState<string | null>(null)
+
+  // Keyed on the fields rather than the object, which every list reload replaces.
+  useEffect(() => {
+    if (!thumbnail) return
+    let objectUrl: string | null = null
+    let cancelled = false
+    readAttachment({ id, mime, thumbnail }, { thumbnail: true })
+      .then((blob) => {
+        if (cancelled) return
+        objectUrl = URL.createObjectURL(blob)
+        setUrl(objectUrl)
+      })
+      .catch((err) => console.error('Failed to load thumbnail:', err))
+    return () => {
+      cancelled = true
+      if (objectUrl) URL.revokeObjectURL(objectUrl)
+    }
+  }, [id, mime, thumbnail])
+
+  return url
+}
+
+function AttachmentItem({ attachment }: { attachment: Attachment }) {
+  const thumbnail = useThumbnail(attachment)
+  const label = `${attachment.name} (${formatSize(attachment.size)})`
+
+  async function handleSave() {
+    try {
+      const path = await save({ defaultPath: attachment.name })
+      if (!path) return
+      await saveAttachment(attachment.id, path)
+      toast.success(`Saved ${attachment.name}`)
+    } catch (error) {
+      toast.error(`Could not save attachment: ${String(error)}`)
+    }
+  }
+
+  async function handleRemove() {
+    try {
+      await removeAttachment(attachment.id)
+    } catch (error) {
+      toast.error(`Could not remove attachment: ${String(error)}`)
+    }
+  }
+
+  return (
+    <div className="group/attachment relative">
+      <button
+        type="button"
+        title={`Save ${label}`}
+        onClick={handleSave}
+        className="flex items-center gap-1.5 overflow-hidden rounded-md border border-border text-xs text-muted-foreground hover:text-foreground"
+      >
+        {thumbnail ? (
+          <img src={thumbnail} alt={attachment.name} className="h-16 max-w-32 object-cover" />
+        ) : (
+          <span className="flex max-w-48 items-center gap-1.5 px-2 py-1">
+            <RiFileLine className="size-3.5 shrink-0" />
+            <span className="truncate">{attachment.name}</span>
+            <span className="shrink-0 opacity-60">{formatSize(attachment.size)}</span>
+          </span>
+        )}
+      </button>
+      <button
+        type="button"
+        aria-label={`Remove ${attachment.name}`}
+        title="Remove attachment"
+        onClick={handleRemove}
+        className="absolute -right-1.5 -top-1.5 hidden rounded-full border border-border bg-background p-0.5 text-muted-foreground hover:text-destructive group-hover/attachment:block"
+      >
+        <RiCloseLine className="size-3" />
+      </button>
+    </div>
+  )
+}
+
+/** An idea's attachments: image thumbnails and file chips. Click to save a copy. */
+export function IdeaAttachments({ attachments }: { attachments: Attachment[] }) {
+  if (attachments.length === 0) return null
+  return (
+    <div className="flex flex-wrap items-center gap-2" onClick={(e) => e.stopPropagation()}>
+      {attachments.map((attachment) => (
+        <AttachmentItem key={attachment.id} attachment={attachment} />
+      ))}
+    </div>
+  )
+}
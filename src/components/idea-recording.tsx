@@ -0,0 +1,125 @@
+import { Button } from '@/components/ui/button'
+import { STT_MODELS } from '@/lib/ai/models'
+import { ggmlName } from '@/lib/ai/whisper'
+import { getNativeSttStatus } from '@/lib/native-transcription'
+import { deleteRecording, readRecording, retranscribeRecording } from '@/lib/recordings'
+import type { Recording } from '@/lib/types'
+import { ask } from '@tauri-apps/plugin-dialog'
+import { RiCloseLine, RiPlayLine } from '@remixicon/react'
+import { useEffect, useState } from 'react'
+import { toast } from 'sonner'
+
+function formatDuration(ms: number): string {
+  const seconds = Math.round(ms / 1000)
+  return `${Math.floor(seconds / 60)}:${String(seconds % 60).padStart(2, '0')}`
+}
+
+/** Whisper models downloaded for native transcription; empty without it. */
+function useInstalledModels(): { id: string; name: string }[] {
+  const [models, setModels] = useState<{ id: string; name: string }[]>([])
+
+  useEffect(() => {
+    getNativeSttStatus()
+      .then((status) => {
+        if (!status?.available) return
+        setModels(STT_MODELS.filter((model) => status.installed.includes(ggmlName(model.id))))
+      })
+      .catch(console.error)
+  }, [])
+
+  return models
+}
+
+interface IdeaRecordingProps {
+  ideaId: string
+  recording: Recording
+  onReplaceText: (id: string, text: string) => void
+}
+
+/** Playback and re-transcription for an idea's kept recording. */
+export function IdeaRecording({ ideaId, recording, onReplaceText }: IdeaRecordingProps) {
+  const [src, setSrc] = useState<string | null>(null)
+  const [transcribing, setTranscribing] = useState(false)
+  const models = useInstalledModels()
+
+  useEffect(() => {
+    return () => {
+      if (src) URL.revokeObjectURL(src)
+    }
+  }, [src])
+
+  async function handlePlay() {
+    try {
+      setSrc(URL.createObjectURL(await readRecording(ideaId)))
+    } catch (error) {
+      toast.error(`Could not play recording: ${String(error)}`)
+    }
+  }
+
+  async function handleRetranscribe(model: string) {
+    setTranscribing(true)
+    try {
+      const { text } = await retranscribeRecording(ideaId, model)
+      const transcript = text.trim()
+      if (!transcript) {
+        toast.info('Nothing detected')
+        return
+      }
+      const replace = await ask(`Replace the idea with the new transcript?\n\n${transcript}`, {
+        title: 'Transcribed again',
+        okLabel: 'Replace',
+      })
+      if (replace) onReplaceText(ideaId, transcript)
+    } catch (error) {
+      toast.error(`Transcription failed: ${String(error)}`)
+    } finally {
+      setTranscribing(false)
+    }
+  }
+
+  async function handleDelete() {
+    try {
+      await deleteRecording(ideaId)
+    } catch (error) {
+      toast.error(`Could not delete recording: ${String(error)}`)
+    }
+  }
+
+  return (
+    <div className="flex flex-wrap items-center gap-2" onClick={(e) => e.stopPropagation()}>
+      {src ? (
+        <audio src={src} controls autoPlay className="h-8 max-w-full" />
+      ) : (
+        <Button size="xs" variant="outline" onClick={handlePlay}>
+          <RiPlayLine className="size-3.5" />
+          {formatDuration(recording.durationMs)}
+        </Button>
+      )}
+      {models.length > 0 && (
+        <select
+          aria-label="Transcribe again"
+          value=""
+          disabled={transcribing}
+          onChange={(e) => handleRetranscribe(e.target.value)}
+          className="h-7 rounded-md border border-input bg-transparent px-2 text-xs text-muted-foreground"
+        >
+          <option value="">{transcribing ? 'Transcribing...' : 'Transcribe again'}</option>
+          {models.map((model) => (
+            <option key={model.id} value={model.id}>
+              with {model.name}
+            </option>
+          ))}
+        </select>
+      )}
+      <Button
+        size="icon"
+        variant="ghost"
+        className="size-6 text-muted-foreground hover:text-destructive"
+        title="Delete recording"
+        onClick={handleDelete}
+      >
+        <RiCloseLine className="size-3.5" />
+      </Button>
+    </div>
+  )
+}
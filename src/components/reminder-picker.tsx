@@ -0,0 +1,102 @@
+import { Button } from '@/components/ui/button'
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog'
+import { Input } from '@/components/ui/input'
+import { useEffect, useState } from 'react'
+
+const HOUR_MS = 60 * 60 * 1000
+
+/** `YYYY-MM-DDTHH:mm` in local time, as `datetime-local` inputs take it. */
+function toInputValue(ms: number): string {
+  const date = new Date(ms)
+  const pad = (n: number) => String(n).padStart(2, '0')
+  const day = `${date.getFullYear()}-${pad(date.getMonth() + 1)}-${pad(date.getDate())}`
+  return `${day}T${pad(date.getHours())}:${pad(date.getMinutes())}`
+}
+
+/** 9:00 in the morning, `days` from today. */
+function morningIn(days: number): number {
+  const date = new Date()
+  date.setDate(date.getDate() + days)
+  date.setHours(9, 0, 0, 0)
+  return date.getTime()
+}
+
+const PRESETS: Array<{ label: string; at: () => number }> = [
+  { label: 'In an hour', at: () => Date.now() + HOUR_MS },
+  { label: 'Tomorrow morning', at: () => morningIn(1) },
+  { label: 'Next week', at: () => morningIn(7) },
+]
+
+interface ReminderPickerProps {
+  /** The idea to remind about; null closes the dialog. */
+  ideaId: string | null
+  /** The idea's current reminder, if it has one. */
+  remindAt: number | null
+  onClose: () => void
+  onSet: (id: string, remindAt: number | null) => Promise<void>
+}
+
+/** Pick when to be reminded of an idea, or remove its reminder. */
+export function ReminderPicker({ ideaId, remindAt, onClose, onSet }: ReminderPickerProps) {
+  const [custom, setCustom] = useState('')
+
+  useEffect(() => {
+    if (ideaId) setCustom(toInputValue(remindAt ?? Date.now() + HOUR_MS))
+  }, [ideaId, remindAt])
+
+  async function choose(at: number | null) {
+    if (!ideaId) return
+    await onSet(ideaId, at)
+    onClose()
+  }
+
+  const customAt = custom ? new Date(custom).getTime() : Number.NaN
+  const customValid = Number.isFinite(customAt) && customAt > Date.now()
+
+  return (
+    <Dialog open={ideaId !== null} onOpenChange={(open) => !open && onClose()}>
+      <DialogContent className="sm:max-w-sm">
+        <DialogHeader>
+          <DialogTitle>Remind me</DialogTitle>
+          <DialogDescription>
+            {remindAt
+              ? `Currently set for ${new Date(remindAt).toLocaleString()}.`
+              : 'Glimt shows a notification with this idea at the chosen time.'}
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="grid gap-2">
+          {PRESETS.map((preset) => (
+            <Button key={preset.label} variant="outline" onClick={() => choose(preset.at())}>
+              {preset.label}
+            </Button>
+          ))}
+        </div>
+
+        <div className="flex items-center gap-2">
+          <Input
+            type="datetime-local"
+            aria-label="Reminder time"
+            value={custom}
+            onChange={(e) => setCustom(e.target.value)}
+          />
+          <Button disabled={!customValid} onClick={() => choose(customAt)}>
+            Set
+          </Button>
+        </div>
+
+        {remindAt && (
+          <Button variant="ghost" className="text-destructive" onClick={() => choose(null)}>
+            Remove reminder
+          </Button>
+        )}
+      </DialogContent>
+    </Dialog>
+  )
+}
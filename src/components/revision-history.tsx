@@ -0,0 +1,153 @@
+import { Button } from '@/components/ui/button'
+import {
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogHeader,
+  DialogTitle,
+} from '@/components/ui/dialog'
+import { ScrollArea } from '@/components/ui/scroll-area'
+import { diffRevisions, listRevisions } from '@/lib/revisions'
+import type { DiffPart, Revision } from '@/lib/types'
+import { cn } from '@/lib/utils'
+import { useEffect, useState } from 'react'
+
+function DiffView({ parts }: { parts: DiffPart[] }) {
+  return (
+    <p className="whitespace-pre-wrap text-sm">
+      {parts.map((part, i) =>
+        part.change === 'insert' ? (
+          <ins
+            key={i}
+            className="rounded-sm bg-green-500/15 text-green-700 no-underline dark:text-green-400"
+          >
+            {part.text}
+          </ins>
+        ) : part.change === 'delete' ? (
+          <del key={i} className="rounded-sm bg-destructive/15 text-destructive">
+            {part.text}
+          </del>
+        ) : (
+          <span key={i}>{part.text}</span>
+        ),
+      )}
+    </p>
+  )
+}
+
+interface RevisionHistoryProps {
+  /** The idea whose history is shown; null closes the dialog. */
+  ideaId: string | null
+  onClose: () => void
+  onRestore: (id: string, revision: number) => Promise<void>
+}
+
+/** Earlier versions of an idea, each diffed against the current text. */
+export function RevisionHistory({ ideaId, onClose, onRestore }: RevisionHistoryProps) {
+  const [revisions, setRevisions] = useState<Revision[] | null>(null)
+  const [selected, setSelected] = useState<Revision | null>(null)
+  const [diff, setDiff] = useState<DiffPart[]>([])
+  const [restoring, setRestoring] = useState(false)
+
+  useEffect(() => {
+    setRevisions(null)
+    setSelected(null)
+    if (!ideaId) return
+    let cancelled = false
+    listRevisions(ideaId)
+      .then((list) => {
+        if (cancelled) return
+        setRevisions(list)
+        setSelected(list[0] ?? null)
+      })
+      .catch((error) => console.error('Failed to list revisions:', error))
+    return () => {
+      cancelled = true
+    }
+  }, [ideaId])
+
+  useEffect(() => {
+    setDiff([])
+    if (!ideaId || !selected) return
+    let cancelled = false
+    diffRevisions(ideaId, selected.id)
+      .then((parts) => {
+        if (!cancelled) setDiff(parts)
+      })
+      .catch((error) => console.error('Failed to diff revisions:', error))
+    return () => {
+      cancelled = true
+    }
+  }, [ideaId, selected])
+
+  async function handleRestore() {
+    if (!ideaId || !selected) return
+    setRestoring(true)
+    try {
+      await onRestore(ideaId, selected.id)
+      onClose()
+    } finally {
+      setRestoring(false)
+    }
+  }
+
+  return (
+    <Dialog open={ideaId !== null} onOpenChange={(open) => !open && onClose()}>
+      <DialogContent className="sm:max-w-2xl">
+        <DialogHeader>
+          <DialogTitle>History</DialogTitle>
+          <DialogDescription>
+            Earlier versions of this idea. Changes are shown from the selected version to the
+            current text.
+          </DialogDescription>
+        </DialogHeader>
+
+        {revisions && revisions.length === 0 && (
+          <p className="py-6 text-center text-sm text-muted-foreground">
+            This idea has not been edited yet.
+          </p>
+        )}
+
+        {revisions && revisions.length > 0 && (
+          <div className="grid gap-4 sm:grid-cols-[12rem_1fr]">
+            <ScrollArea className="max-h-80 sm:border-r sm:pr-2">
+              <ul className="space-y-1">
+                {revisions.map((revision) => (
+                  <li key={revision.id}>
+                    <button
+                      type="button"
+                      onClick={() => setSelected(revision)}
+                      className={cn(
+                        'w-full rounded-md px-2 py-1.5 text-left text-sm transition-colors',
+                        selected?.id === revision.id
+                          ? 'bg-primary/10 text-primary'
+                          : 'text-muted-foreground hover:text-foreground',
+                      )}
+                    >
+                      <span className="block">
+                        {new Date(revision.createdAt).toLocaleString()}
+                      </span>
+                      <span className="block truncate text-xs opacity-80">
+                        {revision.title ?? revision.text}
+                      </span>
+                    </button>
+                  </li>
+                ))}
+              </ul>
+            </ScrollArea>
+            <div className="space-y-3">
+              <ScrollArea className="max-h-72 rounded-md border p-3">
+                <DiffView parts={diff} />
+              </ScrollArea>
+              <div className="flex justify-end">
+                <Button size="sm" onClick={handleRestore} disabled={!selected || restoring}>
+                  Restore this version
+                </Button>
+              </div>
+            </div>
+          </div>
+        )}
+      </DialogContent>
+    </Dialog>
+  )
+}
@@ -0,0 +1,83 @@
+import { useCallback, useState, type FormEvent } from 'react'
+import { Button } from '@/components/ui/button'
+import { Input } from '@/components/ui/input'
+import { Label } from '@/components/ui/label'
+import { Switch } from '@/components/ui/switch'
+import { unlockDatabase } from '@/lib/encryption'
+import { cn } from '@/lib/utils'
+import { RiLockLine } from '@remixicon/react'
+
+interface UnlockScreenProps {
+  /** Fit the small capture window instead of filling the main one. */
+  compact?: boolean
+}
+
+/**
+ * Asks for the database passphrase. Every window waiting on the database
+ * continues on its own once it is unlocked, whichever window it came from.
+ */
+export function UnlockScreen({ compact = false }: UnlockScreenProps) {
+  const [passphrase, setPassphrase] = useState('')
+  const [remember, setRemember] = useState(false)
+  const [unlocking, setUnlocking] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  const handleSubmit = useCallback(
+    async (event: FormEvent) => {
+      event.preventDefault()
+      if (!passphrase) return
+      setUnlocking(true)
+      setError(null)
+      try {
+        await unlockDatabase(passphrase, remember)
+      } catch (err) {
+        setError(String(err))
+        setUnlocking(false)
+      }
+    },
+    [passphrase, remember],
+  )
+
+  return (
+    <div
+      className={cn(
+        'flex h-screen items-center justify-center bg-background',
+        compact ? 'rounded-2xl p-4' : 'p-8',
+      )}
+    >
+      <form onSubmit={handleSubmit} className="w-full max-w-sm space-y-4">
+        <div className="flex items-center gap-2">
+          <RiLockLine className="size-5 text-primary" />
+          <h1 className={cn('font-semibold text-foreground', compact ? 'text-base' : 'text-xl')}>
+            Glimt is locked
+          </h1>
+        </div>
+        {!compact && (
+          <p className="text-sm text-muted-foreground">
+            Your ideas are encrypted. Enter the passphrase to open them.
+          </p>
+        )}
+        <Input
+          type="password"
+          autoFocus
+          placeholder="Passphrase"
+          value={passphrase}
+          onChange={(e) => setPassphrase(e.target.value)}
+          disabled={unlocking}
+        />
+        {error && <p className="text-sm text-destructive">{error}</p>}
+        <div className="flex items-center justify-between gap-3">
+          <div className="flex items-center gap-2">
+            <Switch id="unlock-remember" checked={remember} onCheckedChange={setRemember} />
+            <Label htmlFor="unlock-remember" className="text-sm">
+              Remember on this device
+            </Label>
+          </div>
+          <Button type="submit" size="sm" disabled={unlocking || !passphrase}>
+            {unlocking ? 'Unlocking…' : 'Unlock'}
+          </Button>
+        </div>
+      </form>
+    </div>
+  )
+}
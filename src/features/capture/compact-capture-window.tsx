@@ -44,6 +44,10 @@ interface CompactCaptureWindowProps {
   onDragStart: () => void
   saveState: SaveState
   autoRecord?: boolean
+  /** Text to start from, such as a clipboard capture. */
+  initialText?: string
+  /** Off while the app is locked, since saved ideas can't be read back then. */
+  canEditLast?: boolean
 }
 
 export function CompactCaptureWindow({
@@ -54,9 +58,13 @@ export function CompactCaptureWindow({
   onDragStart,
   saveState,
   autoRecord,
+  initialText,
+  canEditLast = true,
 }: CompactCaptureWindowProps) {
-  const [wordCount, setWordCount] = useState(0)
-  const [hasContent, setHasContent] = useState(false)
+  const [wordCount, setWordCount] = useState(
+    () => initialText?.trim().split(/\s+/).filter(Boolean).length ?? 0,
+  )
+  const [hasContent, setHasContent] = useState(Boolean(initialText))
   const [mdHint, setMdHint] = useState<string | null>(null)
   const [editingId, setEditingId] = useState<string | null>(null)
   const [lastIdea, setLastIdea] = useState<{ id: string; preview: string } | null>(null)
@@ -238,6 +246,7 @@ export function CompactCaptureWindow({
 
       {!hasContent &&
         !editingId &&
+        canEditLast &&
         lastIdea &&
         recordingState === 'idle' &&
         saveState === 'idle' && (
@@ -257,6 +266,7 @@ export function CompactCaptureWindow({
         <div className="flex-1 min-w-0">
           <MarkdownEditor
             ref={editorRef}
+            initialContent={initialText}
             placeholder={placeholder}
             className="glass-editor"
             compact
@@ -264,7 +274,7 @@ export function CompactCaptureWindow({
             onSave={handleSave}
             onCancel={onClose}
             onUpdate={handleEditorUpdate}
-            onArrowUpEmpty={lastIdea && !editingId ? () => void handleEditLast() : undefined}
+            onArrowUpEmpty={canEditLast && lastIdea && !editingId ? () => void handleEditLast() : undefined}
           />
         </div>
       </div>
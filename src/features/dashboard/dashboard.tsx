@@ -1,5 +1,9 @@
+import { IdeaAttachments } from '@/components/idea-attachments'
+import { IdeaRecording } from '@/components/idea-recording'
 import { MarkdownEditor, type MarkdownEditorHandle } from '@/components/markdown-editor'
 import { MarkdownRenderer } from '@/components/markdown-renderer'
+import { ReminderPicker } from '@/components/reminder-picker'
+import { RevisionHistory } from '@/components/revision-history'
 import { Badge } from '@/components/ui/badge'
 import { Button } from '@/components/ui/button'
 import { Card, CardContent } from '@/components/ui/card'
@@ -7,13 +11,22 @@ import { Input } from '@/components/ui/input'
 import { ScrollArea } from '@/components/ui/scroll-area'
 import { Tooltip, TooltipContent, TooltipTrigger } from '@/components/ui/tooltip'
 import { useAppContext } from '@/lib/app-context'
-import type { Idea } from '@/lib/types'
+import { attachData, attachFile } from '@/lib/attachments'
+import { getIdea } from '@/lib/db'
+import type { Idea, SnippetPart } from '@/lib/types'
 import { cn } from '@/lib/utils'
+import { TrashList } from './trash-list'
 import {
   RiAddLine,
+  RiAlarmLine,
+  RiAppsLine,
   RiArchiveLine,
+  RiAttachment2,
   RiCloseLine,
+  RiDeleteBin2Line,
   RiDeleteBinLine,
+  RiHashtag,
+  RiHistoryLine,
   RiInboxLine,
   RiInboxUnarchiveLine,
   RiLightbulbFlashLine,
@@ -21,7 +34,9 @@ import {
   RiSearchLine,
   RiSettings3Line,
 } from '@remixicon/react'
+import { open } from '@tauri-apps/plugin-dialog'
 import { useCallback, useEffect, useMemo, useRef, useState } from 'react'
+import { toast } from 'sonner'
 
 const SEARCH_EXAMPLES = [
   'What was that idea about meetings?',
@@ -84,6 +99,22 @@ function useAnimatedPlaceholder(sentences: string[], active: boolean): string {
   return active ? display : ''
 }
 
+function SearchSnippet({ parts }: { parts: SnippetPart[] }) {
+  return (
+    <p className="line-clamp-3 text-sm text-foreground/90">
+      {parts.map((part, i) =>
+        part.highlight ? (
+          <mark key={i} className="rounded-sm bg-primary/20 px-0.5 text-foreground">
+            {part.text}
+          </mark>
+        ) : (
+          <span key={i}>{part.text}</span>
+        ),
+      )}
+    </p>
+  )
+}
+
 interface DashboardProps {
   onSettings: () => void
 }
@@ -98,6 +129,15 @@ function formatDate(timestamp: number): string {
   })
 }
 
+/** Short label for a source link; the full URL goes in its tooltip. */
+function hostname(url: string): string {
+  try {
+    return new URL(url).hostname.replace(/^www\./, '')
+  } catch {
+    return url
+  }
+}
+
 function groupByDay(ideas: Idea[]): Map<string, Idea[]> {
   const groups = new Map<string, Idea[]>()
   for (const idea of ideas) {
@@ -116,18 +156,48 @@ function groupByDay(ideas: Idea[]): Map<string, Idea[]> {
   return groups
 }
 
+/** Groups by source app, busiest first; ideas with no recorded app go last. */
+function groupBySource(ideas: Idea[]): Map<string, Idea[]> {
+  const bySource = new Map<string | null, Idea[]>()
+  for (const idea of ideas) {
+    const existing = bySource.get(idea.sourceApp)
+    if (existing) {
+      existing.push(idea)
+    } else {
+      bySource.set(idea.sourceApp, [idea])
+    }
+  }
+  const sorted = Array.from(bySource.entries()).sort(
+    ([a, aIdeas], [b, bIdeas]) =>
+      Number(a === null) - Number(b === null) || bIdeas.length - aIdeas.length,
+  )
+  return new Map(sorted.map(([source, group]) => [source ?? 'Unknown app', group]))
+}
+
 export function Dashboard({ onSettings }: DashboardProps) {
   const {
     ideas,
     showArchive,
     archiveCount,
+    trashCount,
+    searchTotal,
+    searchSnippets,
+    tags,
+    activeTag,
+    sourceApps,
+    activeSourceApp,
     onToggleArchive,
+    onTagFilter,
+    onSourceAppFilter,
     onSearch,
+    onLoadMoreResults,
     onUpdate,
     onDelete,
     onArchive,
     onCapture,
     onRegenerateTitle,
+    onRestoreRevision,
+    onSetReminder,
   } = useAppContext()
 
   const [searchQuery, setSearchQuery] = useState('')
@@ -136,10 +206,16 @@ export function Dashboard({ onSettings }: DashboardProps) {
   const [editText, setEditText] = useState('')
   const [deleteConfirmId, setDeleteConfirmId] = useState<string | null>(null)
   const [regeneratingId, setRegeneratingId] = useState<string | null>(null)
+  const [groupBy, setGroupBy] = useState<'day' | 'app'>('day')
+  const [historyId, setHistoryId] = useState<string | null>(null)
+  const [showTrash, setShowTrash] = useState(false)
+  const [reminderId, setReminderId] = useState<string | null>(null)
+  const [focusId, setFocusId] = useState<string | null>(null)
+  const [highlightId, setHighlightId] = useState<string | null>(null)
   const debounceRef = useRef<ReturnType<typeof setTimeout>>(undefined)
   const editEditorRef = useRef<MarkdownEditorHandle>(null)
 
-  const placeholderActive = !searchQuery && !isSearchFocused && !showArchive
+  const placeholderActive = !searchQuery && !isSearchFocused && !showArchive && !showTrash
   const animatedText = useAnimatedPlaceholder(SEARCH_EXAMPLES, placeholderActive)
 
   const handleSearchChange = useCallback(
@@ -156,13 +232,23 @@ export function Dashboard({ onSettings }: DashboardProps) {
   )
 
   function switchTab(archived: boolean) {
-    if (archived === showArchive) return
+    const wasTrash = showTrash
+    setShowTrash(false)
+    if (archived === showArchive && !wasTrash) return
     setSearchQuery('')
     setEditingId(null)
     setDeleteConfirmId(null)
     onToggleArchive(archived)
   }
 
+  function openTrash() {
+    if (showTrash) return
+    setShowTrash(true)
+    setSearchQuery('')
+    setEditingId(null)
+    setDeleteConfirmId(null)
+  }
+
   useEffect(() => {
     return () => {
       if (debounceRef.current) {
@@ -171,6 +257,52 @@ export function Dashboard({ onSettings }: DashboardProps) {
     }
   }, [])
 
+  /** Bring an idea into the list, clearing filters that hide it. */
+  async function focusIdea(id: string) {
+    const idea = await getIdea(id)
+    if (!idea || idea.deletedAt) return
+    if (searchQuery) handleSearchChange('')
+    if (activeTag && !idea.tags.includes(activeTag)) await onTagFilter(null)
+    if (activeSourceApp && activeSourceApp !== idea.sourceApp) await onSourceAppFilter(null)
+    switchTab(idea.archived)
+    setFocusId(id)
+  }
+  const focusIdeaRef = useRef(focusIdea)
+  focusIdeaRef.current = focusIdea
+
+  // Clicking a reminder or resurfaced-idea notification asks for the idea
+  useEffect(() => {
+    let unlisten: (() => void) | undefined
+    import('@tauri-apps/api/event')
+      .then(({ listen }) =>
+        listen<string>('focus-idea', (event) => {
+          focusIdeaRef.current(event.payload).catch(console.error)
+        }).then((fn) => {
+          unlisten = fn
+        }),
+      )
+      .catch(() => {
+        // Not running in Tauri context
+      })
+    return () => unlisten?.()
+  }, [])
+
+  // Scroll once the focused idea's card is rendered
+  useEffect(() => {
+    if (!focusId) return
+    const card = document.getElementById(`idea-${focusId}`)
+    if (!card) return
+    card.scrollIntoView({ behavior: 'smooth', block: 'center' })
+    setHighlightId(focusId)
+    setFocusId(null)
+  }, [focusId, ideas])
+
+  useEffect(() => {
+    if (!highlightId) return
+    const timeout = setTimeout(() => setHighlightId(null), 2000)
+    return () => clearTimeout(timeout)
+  }, [highlightId])
+
   const startEdit = useCallback((idea: Idea) => {
     setEditingId(idea.id)
     setEditText(idea.text)
@@ -188,6 +320,34 @@ export function Dashboard({ onSettings }: DashboardProps) {
     [editText, onUpdate],
   )
 
+  const attachFiles = useCallback(async (id: string) => {
+    const selected = await open({ multiple: true, title: 'Attach files' })
+    if (!selected) return
+    for (const path of selected) {
+      try {
+        await attachFile(id, path)
+      } catch (error) {
+        toast.error(`Could not attach file: ${String(error)}`)
+      }
+    }
+  }, [])
+
+  /** Pasted screenshots and files are attached instead of going into the text. */
+  const pasteAttachments = useCallback(async (id: string, e: React.ClipboardEvent) => {
+    const files = Array.from(e.clipboardData.files)
+    if (files.length === 0) return
+    e.preventDefault()
+    e.stopPropagation()
+    for (const file of files) {
+      const name = file.name || `pasted-${Date.now()}.${file.type.split('/')[1] ?? 'bin'}`
+      try {
+        await attachData(id, name, file)
+      } catch (error) {
+        toast.error(`Could not attach ${name}: ${String(error)}`)
+      }
+    }
+  }, [])
+
   const confirmDelete = useCallback((id: string) => {
     setDeleteConfirmId(id)
   }, [])
@@ -199,7 +359,14 @@ export function Dashboard({ onSettings }: DashboardProps) {
     }
   }, [deleteConfirmId, onDelete])
 
-  const grouped = useMemo(() => groupByDay(ideas), [ideas])
+  const grouped = useMemo(
+    () => (groupBy === 'app' ? groupBySource(ideas) : groupByDay(ideas)),
+    [ideas, groupBy],
+  )
+  const visibleTags = useMemo(
+    () => tags.filter((tag) => tag.count > 0 || tag.name === activeTag),
+    [tags, activeTag],
+  )
 
   return (
     <div className="flex h-screen flex-col bg-background">
@@ -220,6 +387,7 @@ export function Dashboard({ onSettings }: DashboardProps) {
               onChange={(e) => handleSearchChange(e.target.value)}
               onFocus={() => setIsSearchFocused(true)}
               onBlur={() => setIsSearchFocused(false)}
+              disabled={showTrash}
               placeholder={
                 showArchive ? 'Search archived ideas...' : isSearchFocused ? 'Search...' : undefined
               }
@@ -260,8 +428,8 @@ export function Dashboard({ onSettings }: DashboardProps) {
           <div className="archive-tabs" role="tablist">
             <button
               role="tab"
-              aria-selected={!showArchive}
-              className={`archive-tab${!showArchive ? ' archive-tab-active' : ''}`}
+              aria-selected={!showArchive && !showTrash}
+              className={`archive-tab${!showArchive && !showTrash ? ' archive-tab-active' : ''}`}
               onClick={() => switchTab(false)}
             >
               <RiLightbulbFlashLine className="size-4" />
@@ -269,15 +437,76 @@ export function Dashboard({ onSettings }: DashboardProps) {
             </button>
             <button
               role="tab"
-              aria-selected={showArchive}
-              className={`archive-tab${showArchive ? ' archive-tab-active' : ''}`}
+              aria-selected={showArchive && !showTrash}
+              className={`archive-tab${showArchive && !showTrash ? ' archive-tab-active' : ''}`}
               onClick={() => switchTab(true)}
             >
               <RiArchiveLine className="size-4" />
               Archive
               {archiveCount > 0 && <span className="archive-tab-count">{archiveCount}</span>}
             </button>
+            <button
+              role="tab"
+              aria-selected={showTrash}
+              className={`archive-tab${showTrash ? ' archive-tab-active' : ''}`}
+              onClick={openTrash}
+            >
+              <RiDeleteBin2Line className="size-4" />
+              Trash
+              {trashCount > 0 && <span className="archive-tab-count">{trashCount}</span>}
+            </button>
           </div>
+
+          {!showTrash && visibleTags.length > 0 && (
+            <div className="flex min-w-0 flex-1 items-center gap-1.5 overflow-x-auto py-1">
+              <RiHashtag className="size-4 shrink-0 text-muted-foreground" />
+              {visibleTags.map((tag) => (
+                <button
+                  key={tag.name}
+                  type="button"
+                  aria-pressed={activeTag === tag.name}
+                  onClick={() => onTagFilter(activeTag === tag.name ? null : tag.name)}
+                  className={cn(
+                    'shrink-0 rounded-full border px-2 py-0.5 text-xs transition-colors',
+                    activeTag === tag.name
+                      ? 'border-primary bg-primary/10 text-primary'
+                      : 'border-border text-muted-foreground hover:text-foreground',
+                  )}
+                >
+                  {tag.name}
+                  <span className="ml-1 opacity-60">{tag.count}</span>
+                </button>
+              ))}
+            </div>
+          )}
+
+          {!showTrash && sourceApps.length > 0 && (
+            <div className="ml-auto flex shrink-0 items-center gap-1.5">
+              <RiAppsLine className="size-4 text-muted-foreground" />
+              <select
+                aria-label="Filter by app"
+                value={activeSourceApp ?? ''}
+                onChange={(e) => onSourceAppFilter(e.target.value || null)}
+                className="h-8 rounded-md border border-input bg-transparent px-2 text-xs"
+              >
+                <option value="">All apps</option>
+                {sourceApps.map((app) => (
+                  <option key={app.sourceApp} value={app.sourceApp}>
+                    {app.sourceApp} ({app.count})
+                  </option>
+                ))}
+              </select>
+              <select
+                aria-label="Group by"
+                value={groupBy}
+                onChange={(e) => setGroupBy(e.target.value as 'day' | 'app')}
+                className="h-8 rounded-md border border-input bg-transparent px-2 text-xs"
+              >
+                <option value="day">By day</option>
+                <option value="app">By app</option>
+              </select>
+            </div>
+          )}
         </div>
       </div>
 
@@ -286,12 +515,12 @@ export function Dashboard({ onSettings }: DashboardProps) {
         <div className="px-4 pt-3 sm:px-6">
           <div className="search-active mx-auto flex max-w-3xl items-center gap-2 text-sm">
             <span className="text-muted-foreground">
-              Found <strong className="text-foreground">{ideas.length}</strong> idea
-              {ideas.length !== 1 ? 's' : ''}
+              Found <strong className="text-foreground">{searchTotal}</strong> idea
+              {searchTotal !== 1 ? 's' : ''}
               {showArchive && ' in archive'}
             </span>
             <Badge variant="secondary" className="text-xs">
-              Semantic Search
+              Keyword + Semantic
             </Badge>
           </div>
         </div>
@@ -300,12 +529,22 @@ export function Dashboard({ onSettings }: DashboardProps) {
       {/* Idea list */}
       <ScrollArea className="flex-1">
         <div className="mx-auto max-w-3xl space-y-6 p-4 sm:p-6">
-          {ideas.length === 0 && (
+          {showTrash && <TrashList />}
+
+          {!showTrash && ideas.length === 0 && (
             <div className="py-16 text-center">
               {searchQuery ? (
                 <p className="text-lg text-muted-foreground">
                   No {showArchive ? 'archived ' : ''}ideas match your search.
                 </p>
+              ) : activeTag ? (
+                <p className="text-lg text-muted-foreground">
+                  No {showArchive ? 'archived ' : ''}ideas tagged #{activeTag}.
+                </p>
+              ) : activeSourceApp ? (
+                <p className="text-lg text-muted-foreground">
+                  No {showArchive ? 'archived ' : ''}ideas captured from {activeSourceApp}.
+                </p>
               ) : showArchive ? (
                 <div className="archive-empty flex flex-col items-center gap-4">
                   <div className="archive-empty-icon flex size-16 items-center justify-center rounded-2xl">
@@ -341,171 +580,302 @@ export function Dashboard({ onSettings }: DashboardProps) {
             </div>
           )}
 
-          {Array.from(grouped.entries()).map(([day, dayIdeas]) => (
-            <div key={day} className="space-y-3">
-              <h2 className="day-header text-[13px] font-semibold uppercase tracking-wide text-muted-foreground">
-                {day}
-              </h2>
-              {dayIdeas.map((idea) => (
-                <Card
-                  key={idea.id}
-                  className={`idea-card group${showArchive ? ' idea-card-archived' : ''}${editingId === idea.id ? ' idea-card-editing' : ''}`}
-                >
-                  <CardContent className="space-y-2 p-4">
-                    {editingId === idea.id ? (
-                      <div className="space-y-2">
-                        <MarkdownEditor
-                          key={editingId}
-                          ref={editEditorRef}
-                          initialContent={editText}
-                          compact
-                          placeholder="Edit your idea..."
-                          onSave={() => saveEdit(idea.id)}
-                          onCancel={() => setEditingId(null)}
-                          className="min-h-[80px]"
-                        />
-                        <div className="flex items-center gap-2">
-                          <Button size="sm" onClick={() => saveEdit(idea.id)}>
-                            Save
-                          </Button>
-                          <Button size="sm" variant="ghost" onClick={() => setEditingId(null)}>
-                            Cancel
-                          </Button>
-                          <span className="text-xs text-muted-foreground">
-                            Ctrl+Enter to save / Esc to cancel
-                          </span>
-                        </div>
-                      </div>
-                    ) : (
-                      <>
+          {!showTrash &&
+            Array.from(grouped.entries()).map(([group, groupIdeas]) => (
+              <div key={group} className="space-y-3">
+                <h2 className="day-header text-[13px] font-semibold uppercase tracking-wide text-muted-foreground">
+                  {group}
+                </h2>
+                {groupIdeas.map((idea) => (
+                  <Card
+                    key={idea.id}
+                    id={`idea-${idea.id}`}
+                    className={cn(
+                      'idea-card group',
+                      showArchive && 'idea-card-archived',
+                      editingId === idea.id && 'idea-card-editing',
+                      highlightId === idea.id && 'idea-card-focused',
+                    )}
+                  >
+                    <CardContent className="space-y-2 p-4">
+                      {editingId === idea.id ? (
                         <div
-                          className="cursor-pointer"
-                          onClick={() => startEdit(idea)}
-                          role="button"
-                          tabIndex={0}
-                          onKeyDown={(e) => {
-                            if (e.key === 'Enter') startEdit(idea)
-                          }}
+                          className="space-y-2"
+                          onPasteCapture={(e) => void pasteAttachments(idea.id, e)}
                         >
-                          <div className="group/title flex items-center gap-1.5">
-                            {idea.title ? (
-                              <h3 className="mb-1 font-semibold text-foreground">{idea.title}</h3>
+                          <MarkdownEditor
+                            key={editingId}
+                            ref={editEditorRef}
+                            initialContent={editText}
+                            compact
+                            placeholder="Edit your idea..."
+                            onSave={() => saveEdit(idea.id)}
+                            onCancel={() => setEditingId(null)}
+                            className="min-h-[80px]"
+                          />
+                          <IdeaAttachments attachments={idea.attachments} />
+                          <div className="flex items-center gap-2">
+                            <Button size="sm" onClick={() => saveEdit(idea.id)}>
+                              Save
+                            </Button>
+                            <Button size="sm" variant="ghost" onClick={() => setEditingId(null)}>
+                              Cancel
+                            </Button>
+                            <span className="text-xs text-muted-foreground">
+                              Ctrl+Enter to save / Esc to cancel
+                            </span>
+                          </div>
+                        </div>
+                      ) : (
+                        <>
+                          <div
+                            className="cursor-pointer"
+                            onClick={() => startEdit(idea)}
+                            role="button"
+                            tabIndex={0}
+                            onKeyDown={(e) => {
+                              if (e.key === 'Enter') startEdit(idea)
+                            }}
+                          >
+                            <div className="group/title flex items-center gap-1.5">
+                              {idea.title ? (
+                                <h3 className="mb-1 font-semibold text-foreground">{idea.title}</h3>
+                              ) : (
+                                <span className="mb-1 text-sm italic text-muted-foreground">
+                                  Untitled
+                                </span>
+                              )}
+                              <Tooltip>
+                                <TooltipTrigger asChild>
+                                  <button
+                                    className={cn(
+                                      'mb-1 inline-flex size-5 shrink-0 items-center justify-center rounded-sm text-muted-foreground transition-all hover:text-foreground',
+                                      regeneratingId === idea.id
+                                        ? 'opacity-100'
+                                        : idea.title
+                                          ? 'opacity-0 group-hover/title:opacity-100'
+                                          : 'opacity-0 group-hover:opacity-100',
+                                    )}
+                                    disabled={regeneratingId === idea.id}
+                                    onClick={async (e) => {
+                                      e.stopPropagation()
+                                      setRegeneratingId(idea.id)
+                                      try {
+                                        await onRegenerateTitle(idea.id)
+                                      } finally {
+                                        setRegeneratingId(null)
+                                      }
+                                    }}
+                                  >
+                                    <RiLoopLeftLine
+                                      className={cn(
+                                        'size-3.5',
+                                        regeneratingId === idea.id && 'animate-spin',
+                                      )}
+                                    />
+                                  </button>
+                                </TooltipTrigger>
+                                <TooltipContent>
+                                  {regeneratingId === idea.id
+                                    ? 'Generating...'
+                                    : idea.title
+                                      ? 'Regenerate title'
+                                      : 'Generate title'}
+                                </TooltipContent>
+                              </Tooltip>
+                            </div>
+                            {searchQuery && searchSnippets[idea.id] ? (
+                              <SearchSnippet parts={searchSnippets[idea.id]!} />
                             ) : (
-                              <span className="mb-1 text-sm italic text-muted-foreground">
-                                Untitled
-                              </span>
+                              <MarkdownRenderer content={idea.text} className="line-clamp-3" />
                             )}
-                            <Tooltip>
-                              <TooltipTrigger asChild>
+                          </div>
+                          {idea.recording && (
+                            <IdeaRecording
+                              ideaId={idea.id}
+                              recording={idea.recording}
+                              onReplaceText={onUpdate}
+                            />
+                          )}
+                          <IdeaAttachments attachments={idea.attachments} />
+                          <div className="flex items-center justify-between gap-2">
+                            <div className="flex min-w-0 flex-wrap items-center gap-x-2 gap-y-1">
+                              <span className="text-[13px] text-muted-foreground">
+                                {formatDate(idea.createdAt)}
+                              </span>
+                              {idea.sourceApp && groupBy !== 'app' && (
                                 <button
-                                  className={cn(
-                                    'mb-1 inline-flex size-5 shrink-0 items-center justify-center rounded-sm text-muted-foreground transition-all hover:text-foreground',
-                                    regeneratingId === idea.id
-                                      ? 'opacity-100'
-                                      : idea.title
-                                        ? 'opacity-0 group-hover/title:opacity-100'
-                                        : 'opacity-0 group-hover:opacity-100',
-                                  )}
-                                  disabled={regeneratingId === idea.id}
-                                  onClick={async (e) => {
+                                  type="button"
+                                  title={`Show ideas captured from ${idea.sourceApp}`}
+                                  onClick={(e) => {
                                     e.stopPropagation()
-                                    setRegeneratingId(idea.id)
-                                    try {
-                                      await onRegenerateTitle(idea.id)
-                                    } finally {
-                                      setRegeneratingId(null)
-                                    }
+                                    onSourceAppFilter(idea.sourceApp)
                                   }}
+                                  className="text-xs text-muted-foreground hover:text-foreground"
                                 >
-                                  <RiLoopLeftLine
-                                    className={cn(
-                                      'size-3.5',
-                                      regeneratingId === idea.id && 'animate-spin',
-                                    )}
-                                  />
+                                  {idea.sourceApp}
                                 </button>
-                              </TooltipTrigger>
-                              <TooltipContent>
-                                {regeneratingId === idea.id
-                                  ? 'Generating...'
-                                  : idea.title
-                                    ? 'Regenerate title'
-                                    : 'Generate title'}
-                              </TooltipContent>
-                            </Tooltip>
-                          </div>
-                          <MarkdownRenderer content={idea.text} className="line-clamp-3" />
-                        </div>
-                        <div className="flex items-center justify-between gap-2">
-                          <span className="text-[13px] text-muted-foreground">
-                            {formatDate(idea.createdAt)}
-                          </span>
-                          <div className="flex items-center gap-0.5 opacity-0 transition-opacity group-hover:opacity-100 focus-within:opacity-100">
-                            <Button
-                              size="icon"
-                              variant="ghost"
-                              className="size-7 text-muted-foreground hover:text-foreground"
-                              title={showArchive ? 'Restore to Ideas' : 'Move to Archive'}
-                              onClick={(e) => {
-                                e.stopPropagation()
-                                onArchive(idea.id)
-                              }}
-                            >
-                              {showArchive ? (
-                                <RiInboxUnarchiveLine className="size-4" />
-                              ) : (
-                                <RiArchiveLine className="size-4" />
                               )}
-                            </Button>
-                            {deleteConfirmId === idea.id ? (
-                              <div className="flex gap-1">
-                                <Button
-                                  size="xs"
-                                  variant="destructive"
-                                  autoFocus
+                              {idea.remindAt && (
+                                <button
+                                  type="button"
+                                  title="Change reminder"
                                   onClick={(e) => {
                                     e.stopPropagation()
-                                    executeDelete()
+                                    setReminderId(idea.id)
                                   }}
+                                  className="inline-flex items-center gap-1 text-xs text-primary/80 hover:text-primary"
                                 >
-                                  Confirm
-                                </Button>
-                                <Button
-                                  size="xs"
-                                  variant="ghost"
+                                  <RiAlarmLine className="size-3.5" />
+                                  {formatDate(idea.remindAt)}
+                                </button>
+                              )}
+                              {idea.sourceUrl && (
+                                <span
+                                  title={idea.sourceUrl}
+                                  className="max-w-48 truncate text-xs text-muted-foreground"
+                                >
+                                  {hostname(idea.sourceUrl)}
+                                </span>
+                              )}
+                              {idea.tags.map((tag) => (
+                                <button
+                                  key={tag}
+                                  type="button"
+                                  title={`Show ideas tagged #${tag}`}
                                   onClick={(e) => {
                                     e.stopPropagation()
-                                    setDeleteConfirmId(null)
+                                    onTagFilter(tag)
                                   }}
+                                  className="text-xs text-primary/80 hover:text-primary"
                                 >
-                                  Cancel
-                                </Button>
-                              </div>
-                            ) : (
+                                  #{tag}
+                                </button>
+                              ))}
+                            </div>
+                            <div className="flex items-center gap-0.5 opacity-0 transition-opacity group-hover:opacity-100 focus-within:opacity-100">
                               <Button
                                 size="icon"
                                 variant="ghost"
-                                className="size-7 text-muted-foreground hover:text-destructive"
-                                title="Delete"
+                                className="size-7 text-muted-foreground hover:text-foreground"
+                                title="Attach files"
                                 onClick={(e) => {
                                   e.stopPropagation()
-                                  confirmDelete(idea.id)
+                                  void attachFiles(idea.id)
                                 }}
                               >
-                                <RiDeleteBinLine className="size-4" />
+                                <RiAttachment2 className="size-4" />
                               </Button>
-                            )}
+                              <Button
+                                size="icon"
+                                variant="ghost"
+                                className="size-7 text-muted-foreground hover:text-foreground"
+                                title="Remind me"
+                                onClick={(e) => {
+                                  e.stopPropagation()
+                                  setReminderId(idea.id)
+                                }}
+                              >
+                                <RiAlarmLine className="size-4" />
+                              </Button>
+                              <Button
+                                size="icon"
+                                variant="ghost"
+                                className="size-7 text-muted-foreground hover:text-foreground"
+                                title="History"
+                                onClick={(e) => {
+                                  e.stopPropagation()
+                                  setHistoryId(idea.id)
+                                }}
+                              >
+                                <RiHistoryLine className="size-4" />
+                              </Button>
+                              <Button
+                                size="icon"
+                                variant="ghost"
+                                className="size-7 text-muted-foreground hover:text-foreground"
+                                title={showArchive ? 'Restore to Ideas' : 'Move to Archive'}
+                                onClick={(e) => {
+                                  e.stopPropagation()
+                                  onArchive(idea.id)
+                                }}
+                              >
+                                {showArchive ? (
+                                  <RiInboxUnarchiveLine className="size-4" />
+                                ) : (
+                                  <RiArchiveLine className="size-4" />
+                                )}
+                              </Button>
+                              {deleteConfirmId === idea.id ? (
+                                <div className="flex gap-1">
+                                  <Button
+                                    size="xs"
+                                    variant="destructive"
+                                    autoFocus
+                                    onClick={(e) => {
+                                      e.stopPropagation()
+                                      executeDelete()
+                                    }}
+                                  >
+                                    Confirm
+                                  </Button>
+                                  <Button
+                                    size="xs"
+                                    variant="ghost"
+                                    onClick={(e) => {
+                                      e.stopPropagation()
+                                      setDeleteConfirmId(null)
+                                    }}
+                                  >
+                                    Cancel
+                                  </Button>
+                                </div>
+                              ) : (
+                                <Button
+                                  size="icon"
+                                  variant="ghost"
+                                  className="size-7 text-muted-foreground hover:text-destructive"
+                                  title="Move to Trash"
+                                  onClick={(e) => {
+                                    e.stopPropagation()
+                                    confirmDelete(idea.id)
+                                  }}
+                                >
+                                  <RiDeleteBinLine className="size-4" />
+                                </Button>
+                              )}
+                            </div>
                           </div>
-                        </div>
-                      </>
-                    )}
-                  </CardContent>
-                </Card>
-              ))}
+                        </>
+                      )}
+                    </CardContent>
+                  </Card>
+                ))}
+              </div>
+            ))}
+
+          {!showTrash && searchQuery && ideas.length < searchTotal && (
+            <div className="flex justify-center">
+              <Button variant="outline" size="sm" onClick={() => onLoadMoreResults()}>
+                Show more results
+              </Button>
             </div>
-          ))}
+          )}
         </div>
       </ScrollArea>
+
+      <ReminderPicker
+        ideaId={reminderId}
+        remindAt={ideas.find((idea) => idea.id === reminderId)?.remindAt ?? null}
+        onClose={() => setReminderId(null)}
+        onSet={onSetReminder}
+      />
+
+      <RevisionHistory
+        ideaId={historyId}
+        onClose={() => setHistoryId(null)}
+        onRestore={onRestoreRevision}
+      />
     </div>
   )
 }
@@ -0,0 +1,174 @@
+import { MarkdownRenderer } from '@/components/markdown-renderer'
+import { Button } from '@/components/ui/button'
+import { Card, CardContent } from '@/components/ui/card'
+import { useAppContext } from '@/lib/app-context'
+import { useSettings } from '@/lib/hooks/use-settings'
+import { emptyTrash, listTrash, purgeIdea, restoreIdea } from '@/lib/trash'
+import type { Idea } from '@/lib/types'
+import { RiArrowGoBackLine, RiDeleteBin2Line } from '@remixicon/react'
+import { ask } from '@tauri-apps/plugin-dialog'
+import { useCallback, useEffect, useState } from 'react'
+import { toast } from 'sonner'
+
+const DAY_MS = 24 * 60 * 60 * 1000
+const KEEP_OPTIONS = [7, 30, 90]
+
+/** "Deleted today", "Deleted 3 days ago", plus when it will be purged. */
+function describeDeletion(deletedAt: number, trashDays: number): string {
+  const days = Math.floor((Date.now() - deletedAt) / DAY_MS)
+  const deleted = days === 0 ? 'Deleted today' : `Deleted ${days} day${days === 1 ? '' : 's'} ago`
+  if (trashDays === 0) return deleted
+  const left = Math.max(0, trashDays - days)
+  const gone = left === 0 ? 'within a day' : `in ${left} day${left === 1 ? '' : 's'}`
+  return `${deleted} · gone ${gone}`
+}
+
+/** Deleted ideas, with restore and delete-forever actions. */
+export function TrashList() {
+  const { loadIdeas } = useAppContext()
+  const { settings, updateSettings } = useSettings()
+  const [trashed, setTrashed] = useState<Idea[]>([])
+  const trashDays = settings?.trashDays ?? 30
+
+  const refresh = useCallback(() => {
+    listTrash()
+      .then(setTrashed)
+      .catch((error) => console.error('Failed to load trash:', error))
+  }, [])
+
+  useEffect(refresh, [refresh])
+
+  // The purge job and the tray's Empty Trash change the list from Rust
+  useEffect(() => {
+    let unlisten: (() => void) | undefined
+    import('@tauri-apps/api/event')
+      .then(({ listen }) =>
+        listen('ideas-changed', refresh).then((fn) => {
+          unlisten = fn
+        }),
+      )
+      .catch(() => {
+        // Not running in Tauri context
+      })
+    return () => unlisten?.()
+  }, [refresh])
+
+  const afterChange = useCallback(async () => {
+    refresh()
+    await loadIdeas()
+  }, [refresh, loadIdeas])
+
+  async function handleRestore(id: string) {
+    try {
+      await restoreIdea(id)
+      await afterChange()
+      toast.success('Idea restored')
+    } catch (error) {
+      toast.error(`Could not restore idea: ${String(error)}`)
+    }
+  }
+
+  async function handlePurge(id: string) {
+    const confirmed = await ask('This idea will be deleted permanently.', {
+      title: 'Delete forever',
+      kind: 'warning',
+      okLabel: 'Delete',
+    })
+    if (!confirmed) return
+    try {
+      await purgeIdea(id)
+      await afterChange()
+    } catch (error) {
+      toast.error(`Could not delete idea: ${String(error)}`)
+    }
+  }
+
+  async function handleEmpty() {
+    const confirmed = await ask(
+      `${trashed.length} idea${trashed.length === 1 ? '' : 's'} will be deleted permanently.`,
+      { title: 'Empty Trash', kind: 'warning', okLabel: 'Empty Trash' },
+    )
+    if (!confirmed) return
+    try {
+      const count = await emptyTrash()
+      await afterChange()
+      toast.success(`Deleted ${count} idea${count === 1 ? '' : 's'}`)
+    } catch (error) {
+      toast.error(`Could not empty the trash: ${String(error)}`)
+    }
+  }
+
+  async function handleKeepChange(days: number) {
+    try {
+      await updateSettings({ trashDays: days })
+    } catch (error) {
+      toast.error(String(error))
+    }
+  }
+
+  return (
+    <div className="space-y-3">
+      <div className="flex items-center gap-2 text-sm text-muted-foreground">
+        <span>Keep deleted ideas</span>
+        <select
+          aria-label="Keep deleted ideas"
+          value={trashDays}
+          onChange={(e) => handleKeepChange(Number(e.target.value))}
+          className="h-8 rounded-md border border-input bg-transparent px-2 text-xs"
+        >
+          {!KEEP_OPTIONS.includes(trashDays) && trashDays !== 0 && (
+            <option value={trashDays}>for {trashDays} days</option>
+          )}
+          {KEEP_OPTIONS.map((days) => (
+            <option key={days} value={days}>
+              for {days} days
+            </option>
+          ))}
+          <option value={0}>until emptied</option>
+        </select>
+        <Button
+          size="sm"
+          variant="outline"
+          className="ml-auto"
+          disabled={trashed.length === 0}
+          onClick={handleEmpty}
+        >
+          <RiDeleteBin2Line className="size-4" />
+          Empty Trash
+        </Button>
+      </div>
+
+      {trashed.length === 0 ? (
+        <p className="py-16 text-center text-lg text-muted-foreground">Trash is empty.</p>
+      ) : (
+        trashed.map((idea) => (
+          <Card key={idea.id} className="idea-card idea-card-archived group">
+            <CardContent className="space-y-2 p-4">
+              {idea.title && <h3 className="font-semibold text-foreground">{idea.title}</h3>}
+              <MarkdownRenderer content={idea.text} className="line-clamp-3" />
+              <div className="flex items-center justify-between gap-2">
+                <span className="text-[13px] text-muted-foreground">
+                  {describeDeletion(idea.deletedAt ?? Date.now(), trashDays)}
+                </span>
+                <div className="flex items-center gap-1">
+                  <Button size="xs" variant="ghost" onClick={() => handleRestore(idea.id)}>
+                    <RiArrowGoBackLine className="size-3.5" />
+                    Restore
+                  </Button>
+                  <Button
+                    size="xs"
+                    variant="ghost"
+                    className="text-muted-foreground hover:text-destructive"
+                    onClick={() => handlePurge(idea.id)}
+                  >
+                    Delete forever
+                  </Button>
+                </div>
+              </div>
+            </CardContent>
+          </Card>
+        ))
+      )}
+    </div>
+  )
+}
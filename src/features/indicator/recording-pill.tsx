@@ -6,17 +6,21 @@ type PillState = 'recording' | 'transcribing' | 'saved' | 'error'
 
 interface RecordingPillProps {
   state: PillState
-  stream: MediaStream | null
+  /** Input peak level in 0..1 from the native recorder. */
+  level: number
   elapsedSeconds: number
   error: string | null
+  /** Transcription percent, when the engine reports it. */
+  progress?: number | null
   shortcutLabel: string
 }
 
 export function RecordingPill({
   state,
-  stream,
+  level,
   elapsedSeconds,
   error,
+  progress = null,
   shortcutLabel,
 }: RecordingPillProps) {
   return (
@@ -28,7 +32,7 @@ export function RecordingPill({
         {state === 'recording' && (
           <>
             <span className="glass-recording-dot" />
-            {stream && <CompactVuMeter stream={stream} />}
+            <CompactVuMeter level={level} />
             <span className="text-xs tabular-nums text-foreground/70">
               {formatTime(elapsedSeconds)}
             </span>
@@ -42,6 +46,11 @@ export function RecordingPill({
           <>
             <RiLoader2Line className="size-3.5 animate-spin text-muted-foreground" />
             <span className="text-xs text-muted-foreground">Transcribing...</span>
+            {progress !== null && (
+              <span className="ml-auto text-xs tabular-nums text-muted-foreground/70">
+                {progress}%
+              </span>
+            )}
           </>
         )}
 
@@ -0,0 +1,168 @@
+import { useCallback, useEffect, useState } from 'react'
+import { toast } from 'sonner'
+import { Button } from '@/components/ui/button'
+import { Input } from '@/components/ui/input'
+import { Label } from '@/components/ui/label'
+import { getAppLockStatus, lockApp, setAppLockPin } from '@/lib/app-lock'
+import { useSettings } from '@/lib/hooks/use-settings'
+import type { AppLockStatus } from '@/lib/types'
+import { RiShieldKeyholeLine } from '@remixicon/react'
+
+const AUTO_LOCK_OPTIONS = [0, 1, 5, 15, 30, 60]
+
+export function AppLockSettings() {
+  const { settings, updateSettings } = useSettings()
+  const [status, setStatus] = useState<AppLockStatus | null>(null)
+  const [current, setCurrent] = useState('')
+  const [pin, setPin] = useState('')
+  const [confirm, setConfirm] = useState('')
+  const [busy, setBusy] = useState(false)
+
+  useEffect(() => {
+    getAppLockStatus()
+      .then(setStatus)
+      .catch((err) => console.error('[Settings] Failed to read app lock status:', err))
+  }, [])
+
+  const applyPin = useCallback(
+    async (next: string) => {
+      if (!status) return
+      setBusy(true)
+      try {
+        setStatus(await setAppLockPin(status.pinSet ? current : null, next))
+        toast.success(!next ? 'PIN removed' : status.pinSet ? 'PIN changed' : 'PIN set')
+        setCurrent('')
+        setPin('')
+        setConfirm('')
+      } catch (error) {
+        toast.error(String(error))
+      } finally {
+        setBusy(false)
+      }
+    },
+    [status, current],
+  )
+
+  const handleAutoLockChange = useCallback(
+    async (minutes: number) => {
+      try {
+        await updateSettings({ autoLockMinutes: minutes })
+      } catch (error) {
+        toast.error(String(error))
+      }
+    },
+    [updateSettings],
+  )
+
+  const handleLockNow = useCallback(async () => {
+    try {
+      await lockApp()
+    } catch (error) {
+      toast.error(String(error))
+    }
+  }, [])
+
+  if (!settings || !status) return null
+
+  const mismatch = confirm.length > 0 && confirm !== pin
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center gap-2">
+        <RiShieldKeyholeLine className="size-5 text-primary" />
+        <h3 className="text-lg font-semibold">App Lock</h3>
+      </div>
+
+      <p className="text-sm text-muted-foreground">
+        Hide your ideas behind a PIN when you step away. Quick capture keeps working while Glimt is
+        locked, but can't show or edit earlier ideas.
+      </p>
+
+      <div className="space-y-3 pl-1">
+        {status.pinSet && (
+          <div className="flex items-center gap-3">
+            <Label htmlFor="app-lock-current" className="w-36 shrink-0">
+              Current PIN
+            </Label>
+            <Input
+              id="app-lock-current"
+              type="password"
+              value={current}
+              onChange={(e) => setCurrent(e.target.value)}
+            />
+          </div>
+        )}
+        <div className="flex items-center gap-3">
+          <Label htmlFor="app-lock-new" className="w-36 shrink-0">
+            {status.pinSet ? 'New PIN' : 'PIN'}
+          </Label>
+          <Input
+            id="app-lock-new"
+            type="password"
+            value={pin}
+            onChange={(e) => setPin(e.target.value)}
+          />
+        </div>
+        <div className="flex items-center gap-3">
+          <Label htmlFor="app-lock-confirm" className="w-36 shrink-0">
+            Confirm
+          </Label>
+          <Input
+            id="app-lock-confirm"
+            type="password"
+            value={confirm}
+            onChange={(e) => setConfirm(e.target.value)}
+            aria-invalid={mismatch}
+          />
+        </div>
+        <div className="flex flex-wrap gap-3">
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={() => applyPin(pin)}
+            disabled={busy || !pin || pin !== confirm}
+          >
+            {status.pinSet ? 'Change PIN' : 'Set PIN'}
+          </Button>
+          {status.pinSet && (
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() => applyPin('')}
+              disabled={busy || !current}
+            >
+              Remove PIN
+            </Button>
+          )}
+        </div>
+      </div>
+
+      {status.pinSet && (
+        <div className="flex flex-wrap items-center gap-3">
+          <Label htmlFor="app-lock-after" className="shrink-0">
+            Lock after
+          </Label>
+          <select
+            id="app-lock-after"
+            className="h-9 rounded-md border border-input bg-transparent px-3 text-sm"
+            value={settings.autoLockMinutes}
+            onChange={(e) => handleAutoLockChange(Number(e.target.value))}
+          >
+            {AUTO_LOCK_OPTIONS.map((minutes) => (
+              <option key={minutes} value={minutes}>
+                {minutes === 0
+                  ? 'Never'
+                  : minutes === 60
+                    ? '1 hour idle'
+                    : `${minutes} minute${minutes === 1 ? '' : 's'} idle`}
+              </option>
+            ))}
+          </select>
+          <Button variant="outline" size="sm" onClick={handleLockNow}>
+            Lock now
+          </Button>
+        </div>
+      )}
+    </div>
+  )
+}
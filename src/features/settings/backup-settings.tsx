@@ -0,0 +1,112 @@
+import { ask, open, save } from '@tauri-apps/plugin-dialog'
+import { useCallback, useState } from 'react'
+import { toast } from 'sonner'
+import { Button } from '@/components/ui/button'
+import { exportArchive, importArchive } from '@/lib/archive'
+import { getEncryptionStatus } from '@/lib/encryption'
+import type { ArchiveImportMode } from '@/lib/types'
+import { RiDatabase2Line } from '@remixicon/react'
+
+const ARCHIVE_FILTERS = [{ name: 'Glimt archive', extensions: ['zip'] }]
+
+function defaultFileName(): string {
+  return `glimt-backup-${new Date().toISOString().slice(0, 10)}.zip`
+}
+
+export function BackupSettings() {
+  const [busy, setBusy] = useState(false)
+
+  const handleExport = useCallback(async () => {
+    const { encrypted } = await getEncryptionStatus()
+    if (
+      encrypted &&
+      !(await ask(
+        'The database is encrypted, but the archive will not be. Anyone with the file can read your ideas.',
+        { title: 'Export without encryption?', kind: 'warning' },
+      ))
+    ) {
+      return
+    }
+
+    const path = await save({
+      title: 'Export Glimt archive',
+      defaultPath: defaultFileName(),
+      filters: ARCHIVE_FILTERS,
+    })
+    if (!path) return
+
+    setBusy(true)
+    try {
+      const manifest = await exportArchive(path, encrypted)
+      toast.success(
+        `Exported ${manifest.ideas} ${manifest.ideas === 1 ? 'idea' : 'ideas'} to the archive`,
+      )
+    } catch (error) {
+      toast.error(`Export failed: ${String(error)}`)
+    } finally {
+      setBusy(false)
+    }
+  }, [])
+
+  const handleImport = useCallback(async (mode: ArchiveImportMode) => {
+    const selected = await open({
+      title: 'Select a Glimt archive',
+      filters: ARCHIVE_FILTERS,
+    })
+    if (!selected) return
+
+    if (
+      mode === 'replace' &&
+      !(await ask('Every idea in Glimt will be deleted and replaced by the archive.', {
+        title: 'Replace all ideas?',
+        kind: 'warning',
+      }))
+    ) {
+      return
+    }
+
+    setBusy(true)
+    try {
+      const summary = await importArchive(selected as string, mode)
+      toast.success(
+        `${summary.added} added · ${summary.updated} updated · ${summary.skipped} already up to date`,
+      )
+    } catch (error) {
+      toast.error(`Import failed: ${String(error)}`)
+    } finally {
+      setBusy(false)
+    }
+  }, [])
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center gap-2">
+        <RiDatabase2Line className="size-5 text-primary" />
+        <h3 className="text-lg font-semibold">Backup</h3>
+      </div>
+
+      <div className="flex flex-wrap items-center gap-3">
+        <Button variant="outline" size="sm" onClick={handleExport} disabled={busy}>
+          Export archive
+        </Button>
+        <Button variant="outline" size="sm" onClick={() => handleImport('merge')} disabled={busy}>
+          Merge archive
+        </Button>
+        <Button
+          variant="outline"
+          size="sm"
+          onClick={() => handleImport('replace')}
+          disabled={busy}
+        >
+          Restore archive
+        </Button>
+      </div>
+
+      <p className="text-sm text-muted-foreground">
+        Save all ideas, embeddings and settings to a single file. Merging keeps the newer copy of
+        each idea; restoring replaces everything with the archive. Your export folder, microphone
+        and shortcuts stay as they are.
+      </p>
+    </div>
+  )
+}
@@ -0,0 +1,120 @@
+import { useCallback, useEffect, useState } from 'react'
+import { toast } from 'sonner'
+import { Button } from '@/components/ui/button'
+import { Input } from '@/components/ui/input'
+import { Label } from '@/components/ui/label'
+import { Switch } from '@/components/ui/switch'
+import { getApiConfig, regenerateApiToken, setApiConfig } from '@/lib/capture-api'
+import type { ApiConfig } from '@/lib/types'
+import { RiCodeSSlashLine } from '@remixicon/react'
+
+export function CaptureApiSettings() {
+  const [config, setConfig] = useState<ApiConfig | null>(null)
+  const [port, setPort] = useState('')
+
+  useEffect(() => {
+    getApiConfig()
+      .then((loaded) => {
+        setConfig(loaded)
+        setPort(String(loaded.port))
+      })
+      .catch((err) => console.error('[Settings] Failed to read API config:', err))
+  }, [])
+
+  const apply = useCallback(async (enabled: boolean, nextPort?: number) => {
+    try {
+      const updated = await setApiConfig(enabled, nextPort)
+      setConfig(updated)
+      setPort(String(updated.port))
+    } catch (error) {
+      toast.error(String(error))
+      setConfig(await getApiConfig())
+    }
+  }, [])
+
+  const handlePortCommit = useCallback(() => {
+    const parsed = Number(port)
+    if (!config || parsed === config.port) return
+    if (!Number.isInteger(parsed) || parsed < 1024 || parsed > 65535) {
+      toast.error('Port must be a number between 1024 and 65535')
+      setPort(String(config.port))
+      return
+    }
+    apply(config.enabled, parsed)
+  }, [apply, config, port])
+
+  const handleRegenerate = useCallback(async () => {
+    try {
+      setConfig(await regenerateApiToken())
+      toast.success('New token generated')
+    } catch (error) {
+      toast.error(String(error))
+    }
+  }, [])
+
+  const handleCopy = useCallback(async () => {
+    if (!config) return
+    await navigator.clipboard.writeText(config.token)
+    toast.success('Token copied')
+  }, [config])
+
+  if (!config) return null
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center gap-2">
+        <RiCodeSSlashLine className="size-5 text-primary" />
+        <h3 className="text-lg font-semibold">Capture API</h3>
+      </div>
+
+      <div className="flex items-center gap-3">
+        <Switch
+          id="capture-api-toggle"
+          checked={config.enabled}
+          onCheckedChange={(checked) => apply(checked)}
+        />
+        <Label htmlFor="capture-api-toggle">Accept ideas from local tools</Label>
+      </div>
+
+      <p className="text-sm text-muted-foreground">
+        Editor plugins, bookmarklets and scripts on this computer can save and search ideas over
+        HTTP on 127.0.0.1. Every request needs the token below.
+      </p>
+
+      {config.enabled && (
+        <div className="space-y-3 pl-1">
+          <div className="flex items-center gap-3">
+            <Label htmlFor="capture-api-port" className="w-12">
+              Port
+            </Label>
+            <Input
+              id="capture-api-port"
+              className="w-28"
+              inputMode="numeric"
+              value={port}
+              onChange={(e) => setPort(e.target.value)}
+              onBlur={handlePortCommit}
+              onKeyDown={(e) => e.key === 'Enter' && handlePortCommit()}
+            />
+          </div>
+          <div className="flex items-center gap-3">
+            <Label className="w-12">Token</Label>
+            <code className="truncate rounded bg-muted px-2 py-1 text-xs">{config.token}</code>
+            <Button variant="outline" size="sm" onClick={handleCopy}>
+              Copy
+            </Button>
+            <Button variant="outline" size="sm" onClick={handleRegenerate}>
+              Regenerate
+            </Button>
+          </div>
+          <pre className="overflow-x-auto rounded bg-muted p-3 text-xs">
+            {`curl -X POST http://127.0.0.1:${config.port}/ideas \\
+  -H "Authorization: Bearer <token>" \\
+  -H "Content-Type: application/json" \\
+  -d '{"text": "My idea"}'`}
+          </pre>
+        </div>
+      )}
+    </div>
+  )
+}
@@ -0,0 +1,137 @@
+import { useCallback, useEffect, useState } from 'react'
+import { toast } from 'sonner'
+import { Button } from '@/components/ui/button'
+import { Input } from '@/components/ui/input'
+import { Label } from '@/components/ui/label'
+import { Switch } from '@/components/ui/switch'
+import { getEncryptionStatus, setDatabasePassphrase } from '@/lib/encryption'
+import type { EncryptionStatus } from '@/lib/types'
+import { RiLockPasswordLine } from '@remixicon/react'
+
+export function EncryptionSettings() {
+  const [status, setStatus] = useState<EncryptionStatus | null>(null)
+  const [current, setCurrent] = useState('')
+  const [passphrase, setPassphrase] = useState('')
+  const [confirm, setConfirm] = useState('')
+  const [remember, setRemember] = useState(true)
+  const [busy, setBusy] = useState(false)
+
+  const refresh = useCallback(() => {
+    getEncryptionStatus()
+      .then((loaded) => {
+        setStatus(loaded)
+        setRemember(loaded.remembered || !loaded.encrypted)
+      })
+      .catch((err) => console.error('[Settings] Failed to read encryption status:', err))
+  }, [])
+
+  useEffect(refresh, [refresh])
+
+  const apply = useCallback(
+    async (next: string) => {
+      if (!status) return
+      setBusy(true)
+      try {
+        await setDatabasePassphrase(status.encrypted ? current : null, next, remember)
+        toast.success(
+          !next
+            ? 'Database decrypted'
+            : status.encrypted
+              ? 'Passphrase changed'
+              : 'Database encrypted',
+        )
+        setCurrent('')
+        setPassphrase('')
+        setConfirm('')
+      } catch (error) {
+        toast.error(String(error))
+      } finally {
+        setBusy(false)
+        refresh()
+      }
+    },
+    [status, current, remember, refresh],
+  )
+
+  if (!status?.available) return null
+
+  const mismatch = confirm.length > 0 && confirm !== passphrase
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center gap-2">
+        <RiLockPasswordLine className="size-5 text-primary" />
+        <h3 className="text-lg font-semibold">Encryption</h3>
+      </div>
+
+      <p className="text-sm text-muted-foreground">
+        {status.encrypted
+          ? 'Your ideas and automatic backups are encrypted on disk. Archives and Markdown exports are not.'
+          : 'Encrypt your ideas and automatic backups on disk with a passphrase. There is no way to recover a forgotten passphrase.'}
+      </p>
+
+      <div className="space-y-3 pl-1">
+        {status.encrypted && (
+          <div className="flex items-center gap-3">
+            <Label htmlFor="encryption-current" className="w-36 shrink-0">
+              Current passphrase
+            </Label>
+            <Input
+              id="encryption-current"
+              type="password"
+              value={current}
+              onChange={(e) => setCurrent(e.target.value)}
+            />
+          </div>
+        )}
+        <div className="flex items-center gap-3">
+          <Label htmlFor="encryption-new" className="w-36 shrink-0">
+            {status.encrypted ? 'New passphrase' : 'Passphrase'}
+          </Label>
+          <Input
+            id="encryption-new"
+            type="password"
+            value={passphrase}
+            onChange={(e) => setPassphrase(e.target.value)}
+          />
+        </div>
+        <div className="flex items-center gap-3">
+          <Label htmlFor="encryption-confirm" className="w-36 shrink-0">
+            Confirm
+          </Label>
+          <Input
+            id="encryption-confirm"
+            type="password"
+            value={confirm}
+            onChange={(e) => setConfirm(e.target.value)}
+            aria-invalid={mismatch}
+          />
+        </div>
+        <div className="flex items-center gap-2">
+          <Switch id="encryption-remember" checked={remember} onCheckedChange={setRemember} />
+          <Label htmlFor="encryption-remember">Remember in the system keychain</Label>
+        </div>
+        <div className="flex flex-wrap gap-3">
+          <Button
+            variant="outline"
+            size="sm"
+            onClick={() => apply(passphrase)}
+            disabled={busy || !passphrase || passphrase !== confirm}
+          >
+            {status.encrypted ? 'Change passphrase' : 'Encrypt database'}
+          </Button>
+          {status.encrypted && (
+            <Button
+              variant="outline"
+              size="sm"
+              onClick={() => apply('')}
+              disabled={busy || !current}
+            >
+              Decrypt database
+            </Button>
+          )}
+        </div>
+      </div>
+    </div>
+  )
+}
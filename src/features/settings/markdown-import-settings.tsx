@@ -0,0 +1,84 @@
+import { open } from '@tauri-apps/plugin-dialog'
+import { useCallback, useState } from 'react'
+import { toast } from 'sonner'
+import { Button } from '@/components/ui/button'
+import { importMarkdown } from '@/lib/markdown-import'
+import type { ImportReport } from '@/lib/types'
+import { RiFolderDownloadLine } from '@remixicon/react'
+
+const MAX_LISTED = 20
+
+function fileName(path: string): string {
+  return path.split(/[\\/]/).pop() ?? path
+}
+
+export function MarkdownImportSettings() {
+  const [importing, setImporting] = useState(false)
+  const [report, setReport] = useState<ImportReport | null>(null)
+
+  const handleImport = useCallback(async () => {
+    const selected = await open({
+      directory: true,
+      recursive: true,
+      title: 'Select a folder of Markdown notes to import',
+    })
+    if (!selected) return
+
+    setImporting(true)
+    try {
+      const result = await importMarkdown(selected as string)
+      setReport(result)
+      if (result.imported > 0) {
+        toast.success(`Imported ${result.imported} ${result.imported === 1 ? 'note' : 'notes'}`)
+      } else {
+        toast.info('No new notes to import')
+      }
+    } catch (error) {
+      toast.error(`Import failed: ${String(error)}`)
+    } finally {
+      setImporting(false)
+    }
+  }, [])
+
+  const problems = report?.files.filter((file) => file.status === 'failed') ?? []
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center gap-2">
+        <RiFolderDownloadLine className="size-5 text-primary" />
+        <h3 className="text-lg font-semibold">Import Notes</h3>
+      </div>
+
+      <div className="flex items-center gap-3">
+        <Button variant="outline" size="sm" onClick={handleImport} disabled={importing}>
+          {importing ? 'Importing…' : 'Import folder'}
+        </Button>
+      </div>
+
+      <p className="text-sm text-muted-foreground">
+        Bring in an Obsidian vault or exported notes. Every Markdown and text file in the folder
+        and its subfolders becomes an idea. Notes exported from Glimt keep their dates and are
+        skipped if they are already here.
+      </p>
+
+      {report && (
+        <div className="space-y-2 rounded-md border p-3 text-sm">
+          <p>
+            {report.imported} imported · {report.duplicates} already in Glimt · {report.empty}{' '}
+            empty · {report.failed} failed
+          </p>
+          {problems.length > 0 && (
+            <ul className="space-y-1 text-muted-foreground">
+              {problems.slice(0, MAX_LISTED).map((file) => (
+                <li key={file.path} title={file.path} className="truncate">
+                  {fileName(file.path)}: {file.error}
+                </li>
+              ))}
+              {problems.length > MAX_LISTED && <li>and {problems.length - MAX_LISTED} more</li>}
+            </ul>
+          )}
+        </div>
+      )}
+    </div>
+  )
+}
@@ -0,0 +1,132 @@
+import { useCallback, useEffect, useState } from 'react'
+import { toast } from 'sonner'
+import { Input } from '@/components/ui/input'
+import { Label } from '@/components/ui/label'
+import { Switch } from '@/components/ui/switch'
+import { useSettings } from '@/lib/hooks/use-settings'
+import { listInputDevices } from '@/lib/recorder'
+import type { InputDevice, SettingsPatch } from '@/lib/types'
+import { RiMicLine } from '@remixicon/react'
+
+const SYSTEM_DEFAULT = ''
+
+export function MicrophoneSettings() {
+  const [devices, setDevices] = useState<InputDevice[] | null>(null)
+  const { settings, updateSettings } = useSettings()
+  const [quota, setQuota] = useState('')
+  const selected = settings?.inputDevice ?? SYSTEM_DEFAULT
+
+  useEffect(() => {
+    listInputDevices()
+      .then(setDevices)
+      .catch((err) => console.error('[Settings] Failed to list microphones:', err))
+  }, [])
+
+  useEffect(() => {
+    if (settings) setQuota(String(settings.recordingQuotaMb))
+  }, [settings])
+
+  const save = useCallback(
+    async (patch: SettingsPatch) => {
+      try {
+        await updateSettings(patch)
+      } catch (error) {
+        toast.error(String(error))
+      }
+    },
+    [updateSettings],
+  )
+
+  const handleChange = useCallback(
+    (device: string) => save({ inputDevice: device === SYSTEM_DEFAULT ? null : device }),
+    [save],
+  )
+
+  const handleQuotaCommit = useCallback(() => {
+    const value = Number.parseInt(quota, 10)
+    if (!Number.isInteger(value) || value < 0) {
+      setQuota(String(settings?.recordingQuotaMb ?? ''))
+      return
+    }
+    if (value !== settings?.recordingQuotaMb) save({ recordingQuotaMb: value })
+  }, [quota, settings, save])
+
+  if (!devices) return null
+
+  const defaultName = devices.find((device) => device.isDefault)?.name
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center gap-2">
+        <RiMicLine className="size-5 text-primary" />
+        <h3 className="text-lg font-semibold">Microphone</h3>
+      </div>
+
+      <div className="flex items-center gap-3">
+        <Label htmlFor="microphone-select" className="shrink-0">
+          Input device
+        </Label>
+        <select
+          id="microphone-select"
+          className="h-9 min-w-0 flex-1 rounded-md border border-input bg-transparent px-3 text-sm"
+          value={selected}
+          onChange={(e) => handleChange(e.target.value)}
+        >
+          <option value={SYSTEM_DEFAULT}>
+            System default{defaultName ? ` (${defaultName})` : ''}
+          </option>
+          {devices.map((device) => (
+            <option key={device.name} value={device.name}>
+              {device.name}
+            </option>
+          ))}
+          {selected !== SYSTEM_DEFAULT && !devices.some((device) => device.name === selected) && (
+            <option value={selected}>{selected} (not connected)</option>
+          )}
+        </select>
+      </div>
+
+      <p className="text-sm text-muted-foreground">
+        Used by the Quick Record shortcut. Falls back to the system default when the chosen device
+        is unplugged.
+      </p>
+
+      {settings && (
+        <>
+          <div className="flex items-center gap-3">
+            <Switch
+              id="keep-recordings"
+              checked={settings.keepRecordings}
+              onCheckedChange={(checked) => save({ keepRecordings: checked })}
+            />
+            <Label htmlFor="keep-recordings">Keep recordings</Label>
+          </div>
+
+          {settings.keepRecordings && (
+            <div className="flex items-center gap-3 pl-1">
+              <Label htmlFor="recording-quota" className="shrink-0">
+                Use at most
+              </Label>
+              <Input
+                id="recording-quota"
+                className="w-24"
+                inputMode="numeric"
+                value={quota}
+                onChange={(e) => setQuota(e.target.value)}
+                onBlur={handleQuotaCommit}
+                onKeyDown={(e) => e.key === 'Enter' && handleQuotaCommit()}
+              />
+              <span className="text-sm text-muted-foreground">MB</span>
+            </div>
+          )}
+
+          <p className="text-sm text-muted-foreground">
+            Saves the audio of each Quick Record idea so you can play it back or transcribe it again
+            with another model. The oldest recordings are removed when a new one goes over the
+            limit; 0 means no limit.
+          </p>
+        </>
+      )}
+    </div>
+  )
+}
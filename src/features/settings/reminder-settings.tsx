@@ -0,0 +1,109 @@
+import { useCallback, useEffect, useState } from 'react'
+import { toast } from 'sonner'
+import { Input } from '@/components/ui/input'
+import { Label } from '@/components/ui/label'
+import { Switch } from '@/components/ui/switch'
+import { useSettings } from '@/lib/hooks/use-settings'
+import type { Settings } from '@/lib/types'
+import { RiAlarmLine } from '@remixicon/react'
+
+type ResurfacePatch = Partial<
+  Pick<Settings, 'resurfaceEnabled' | 'resurfaceEveryHours' | 'resurfaceAfterDays'>
+>
+
+export function ReminderSettings() {
+  const { settings, updateSettings } = useSettings()
+  const [everyHours, setEveryHours] = useState('')
+  const [afterDays, setAfterDays] = useState('')
+
+  useEffect(() => {
+    if (!settings) return
+    setEveryHours(String(settings.resurfaceEveryHours))
+    setAfterDays(String(settings.resurfaceAfterDays))
+  }, [settings])
+
+  const save = useCallback(
+    async (patch: ResurfacePatch) => {
+      try {
+        await updateSettings(patch)
+      } catch (error) {
+        toast.error(String(error))
+      }
+    },
+    [updateSettings],
+  )
+
+  const handleEveryCommit = useCallback(() => {
+    const value = Number.parseInt(everyHours, 10)
+    if (!Number.isInteger(value) || value < 1) {
+      setEveryHours(String(settings?.resurfaceEveryHours ?? ''))
+      return
+    }
+    if (value !== settings?.resurfaceEveryHours) save({ resurfaceEveryHours: value })
+  }, [everyHours, settings, save])
+
+  const handleAfterCommit = useCallback(() => {
+    const value = Number.parseInt(afterDays, 10)
+    if (!Number.isInteger(value) || value < 0) {
+      setAfterDays(String(settings?.resurfaceAfterDays ?? ''))
+      return
+    }
+    if (value !== settings?.resurfaceAfterDays) save({ resurfaceAfterDays: value })
+  }, [afterDays, settings, save])
+
+  if (!settings) return null
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center gap-2">
+        <RiAlarmLine className="size-5 text-primary" />
+        <h3 className="text-lg font-semibold">Reminders</h3>
+      </div>
+
+      <div className="flex items-center justify-between">
+        <Label htmlFor="resurface-enabled">Resurface old ideas</Label>
+        <Switch
+          id="resurface-enabled"
+          checked={settings.resurfaceEnabled}
+          onCheckedChange={(checked) => save({ resurfaceEnabled: checked })}
+        />
+      </div>
+
+      {settings.resurfaceEnabled && (
+        <div className="flex flex-wrap items-center gap-3">
+          <Label htmlFor="resurface-every" className="shrink-0">
+            Every
+          </Label>
+          <Input
+            id="resurface-every"
+            className="w-20"
+            inputMode="numeric"
+            value={everyHours}
+            onChange={(e) => setEveryHours(e.target.value)}
+            onBlur={handleEveryCommit}
+            onKeyDown={(e) => e.key === 'Enter' && handleEveryCommit()}
+          />
+          <Label htmlFor="resurface-after" className="shrink-0">
+            hours, an idea older than
+          </Label>
+          <Input
+            id="resurface-after"
+            className="w-20"
+            inputMode="numeric"
+            value={afterDays}
+            onChange={(e) => setAfterDays(e.target.value)}
+            onBlur={handleAfterCommit}
+            onKeyDown={(e) => e.key === 'Enter' && handleAfterCommit()}
+          />
+          <span className="text-sm">days</span>
+        </div>
+      )}
+
+      <p className="text-sm text-muted-foreground">
+        Reminders you set on ideas show as desktop notifications. Resurfacing also shows a random
+        unarchived idea now and then, and the same idea won't come back for as many days. Click a
+        notification to open its idea.
+      </p>
+    </div>
+  )
+}
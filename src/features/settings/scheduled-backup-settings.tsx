@@ -0,0 +1,163 @@
+import { ask } from '@tauri-apps/plugin-dialog'
+import { useCallback, useEffect, useState } from 'react'
+import { toast } from 'sonner'
+import { Button } from '@/components/ui/button'
+import { Input } from '@/components/ui/input'
+import { Label } from '@/components/ui/label'
+import { backupNow, listBackups, restoreBackup } from '@/lib/backups'
+import { useSettings } from '@/lib/hooks/use-settings'
+import type { BackupInfo, BackupSchedule } from '@/lib/types'
+import { RiHistoryLine } from '@remixicon/react'
+
+const MAX_LISTED = 10
+
+function formatSize(bytes: number): string {
+  if (bytes < 1024 * 1024) return `${Math.max(1, Math.round(bytes / 1024))} KB`
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`
+}
+
+export function ScheduledBackupSettings() {
+  const { settings, updateSettings } = useSettings()
+  const [backups, setBackups] = useState<BackupInfo[]>([])
+  const [keep, setKeep] = useState('')
+  const [busy, setBusy] = useState(false)
+
+  const refresh = useCallback(() => {
+    listBackups()
+      .then(setBackups)
+      .catch((err) => console.error('[Settings] Failed to list backups:', err))
+  }, [])
+
+  useEffect(refresh, [refresh])
+
+  useEffect(() => {
+    if (settings) setKeep(String(settings.backupKeep))
+  }, [settings])
+
+  const save = useCallback(
+    async (patch: { backupSchedule?: BackupSchedule; backupKeep?: number }) => {
+      try {
+        await updateSettings(patch)
+      } catch (error) {
+        toast.error(String(error))
+      }
+    },
+    [updateSettings],
+  )
+
+  const handleKeepCommit = useCallback(() => {
+    const value = Number.parseInt(keep, 10)
+    if (!Number.isInteger(value) || value < 1) {
+      setKeep(String(settings?.backupKeep ?? ''))
+      return
+    }
+    if (value !== settings?.backupKeep) save({ backupKeep: value })
+  }, [keep, settings, save])
+
+  const handleBackupNow = useCallback(async () => {
+    setBusy(true)
+    try {
+      await backupNow()
+      toast.success('Backup created')
+    } catch (error) {
+      toast.error(`Backup failed: ${String(error)}`)
+    } finally {
+      setBusy(false)
+      refresh()
+    }
+  }, [refresh])
+
+  const handleRestore = useCallback(
+    async (backup: BackupInfo) => {
+      const when = new Date(backup.createdAt).toLocaleString()
+      const confirmed = await ask(
+        `Glimt will go back to the ideas saved on ${when}. The current database is backed up first.`,
+        { title: 'Restore this backup?', kind: 'warning' },
+      )
+      if (!confirmed) return
+
+      setBusy(true)
+      try {
+        await restoreBackup(backup.name)
+        toast.success(`Restored the backup from ${when}`)
+      } catch (error) {
+        toast.error(`Restore failed: ${String(error)}`)
+      } finally {
+        setBusy(false)
+        refresh()
+      }
+    },
+    [refresh],
+  )
+
+  if (!settings) return null
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center gap-2">
+        <RiHistoryLine className="size-5 text-primary" />
+        <h3 className="text-lg font-semibold">Automatic Backups</h3>
+      </div>
+
+      <div className="flex flex-wrap items-center gap-3">
+        <Label htmlFor="backup-schedule" className="shrink-0">
+          Back up
+        </Label>
+        <select
+          id="backup-schedule"
+          className="h-9 rounded-md border border-input bg-transparent px-3 text-sm"
+          value={settings.backupSchedule}
+          onChange={(e) => save({ backupSchedule: e.target.value as BackupSchedule })}
+        >
+          <option value="hourly">Every hour</option>
+          <option value="daily">Every day</option>
+          <option value="off">Never</option>
+        </select>
+        <Label htmlFor="backup-keep" className="shrink-0">
+          Keep
+        </Label>
+        <Input
+          id="backup-keep"
+          className="w-20"
+          inputMode="numeric"
+          value={keep}
+          onChange={(e) => setKeep(e.target.value)}
+          onBlur={handleKeepCommit}
+          onKeyDown={(e) => e.key === 'Enter' && handleKeepCommit()}
+        />
+        <Button variant="outline" size="sm" onClick={handleBackupNow} disabled={busy}>
+          Back up now
+        </Button>
+      </div>
+
+      <p className="text-sm text-muted-foreground">
+        Snapshots of the database are checked for corruption and the oldest are removed once there
+        are more than you keep.
+      </p>
+
+      {backups.length > 0 && (
+        <ul className="space-y-1 rounded-md border p-3 text-sm">
+          {backups.slice(0, MAX_LISTED).map((backup) => (
+            <li key={backup.name} className="flex items-center gap-3">
+              <span className="flex-1 truncate" title={backup.name}>
+                {new Date(backup.createdAt).toLocaleString()}
+              </span>
+              <span className="text-muted-foreground">{formatSize(backup.size)}</span>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => handleRestore(backup)}
+                disabled={busy}
+              >
+                Restore
+              </Button>
+            </li>
+          ))}
+          {backups.length > MAX_LISTED && (
+            <li className="text-muted-foreground">and {backups.length - MAX_LISTED} older</li>
+          )}
+        </ul>
+      )}
+    </div>
+  )
+}
@@ -12,10 +12,21 @@ import { toast } from 'sonner'
 import { titleLifecycle } from '@/lib/ai/title-generation'
 import { whisperLifecycle } from '@/lib/ai/whisper'
 import { useModelLifecycle } from '@/lib/hooks/use-model-lifecycle'
+import { useSettings } from '@/lib/hooks/use-settings'
+import { AppLockSettings } from '@/features/settings/app-lock-settings'
+import { BackupSettings } from '@/features/settings/backup-settings'
+import { CaptureApiSettings } from '@/features/settings/capture-api-settings'
+import { EncryptionSettings } from '@/features/settings/encryption-settings'
+import { MarkdownImportSettings } from '@/features/settings/markdown-import-settings'
+import { MicrophoneSettings } from '@/features/settings/microphone-settings'
 import { ModelManager } from '@/features/settings/model-manager'
+import { ReminderSettings } from '@/features/settings/reminder-settings'
+import { ScheduledBackupSettings } from '@/features/settings/scheduled-backup-settings'
+import { TagSettings } from '@/features/settings/tag-settings'
 import { UpdateChecker } from '@/features/settings/update-checker'
 import { STORAGE_KEYS } from '@/lib/storage-keys'
 import { keyEventToShortcut, parseShortcutKeys } from '@/lib/shortcut'
+import type { ClipMode, ShortcutAction } from '@/lib/types'
 import {
   RiArrowDownSLine,
   RiArrowLeftLine,
@@ -46,6 +57,78 @@ const THEME_OPTIONS: { value: Theme; label: string; icon: typeof RiSunLine }[] =
   { value: 'system', label: 'System', icon: RiComputerLine },
 ]
 
+interface ShortcutRowProps {
+  label: string
+  description: string
+  shortcut: string
+  error: string | null
+  /** Waiting for the new keys. */
+  recording: boolean
+  heldKeys: string[]
+  recorderRef: React.RefObject<HTMLButtonElement | null>
+  onOpen: () => void
+  onKeyDown: (e: React.KeyboardEvent<HTMLButtonElement>) => void
+  onKeyUp: (e: React.KeyboardEvent<HTMLButtonElement>) => void
+  onBlur: () => void
+}
+
+function ShortcutRow({
+  label,
+  description,
+  shortcut,
+  error,
+  recording,
+  heldKeys,
+  recorderRef,
+  onOpen,
+  onKeyDown,
+  onKeyUp,
+  onBlur,
+}: ShortcutRowProps) {
+  return (
+    <div className="flex items-center justify-between">
+      <div className="space-y-0.5">
+        <p className="text-sm font-medium text-foreground">{label}</p>
+        <p className="text-xs text-muted-foreground">{description}</p>
+        {error && <p className="text-xs text-red-500 dark:text-red-400">{error}</p>}
+      </div>
+      {recording ? (
+        <button
+          ref={recorderRef}
+          onKeyDown={onKeyDown}
+          onKeyUp={onKeyUp}
+          onBlur={onBlur}
+          aria-live="polite"
+          className="shortcut-recorder-recording flex items-center gap-1 rounded-lg border-2 px-3 py-2 outline-none"
+        >
+          {heldKeys.length > 0 ? (
+            heldKeys.map((key, i) => (
+              <span key={i}>
+                {i > 0 && <span className="mx-0.5 text-xs text-muted-foreground">+</span>}
+                <kbd className="shortcut-key">{key}</kbd>
+              </span>
+            ))
+          ) : (
+            <span className="text-sm text-muted-foreground">Press keys...</span>
+          )}
+        </button>
+      ) : (
+        <button
+          onClick={onOpen}
+          className="flex items-center gap-1 rounded-lg border border-border px-3 py-2 transition-colors hover:border-muted-foreground/30"
+        >
+          {parseShortcutKeys(shortcut).map((key, i) => (
+            <span key={i}>
+              {i > 0 && <span className="mx-0.5 text-xs text-muted-foreground">+</span>}
+              <kbd className="shortcut-key">{key}</kbd>
+            </span>
+          ))}
+        </button>
+      )}
+    </div>
+  )
+}
+
 export function Settings({ onBack }: SettingsProps) {
   const {
     exportEnabled,
@@ -60,15 +143,21 @@ export function Settings({ onBack }: SettingsProps) {
     theme,
     onThemeChange,
     captureShortcut,
+    captureShortcutError,
     onCaptureShortcutChange,
     recordShortcut,
+    recordShortcutError,
     onRecordShortcutChange,
+    clipShortcut,
+    clipShortcutError,
+    onClipShortcutChange,
     autoTitleEnabled,
     onAutoTitleEnabledChange,
   } = useAppContext()
 
   const [autostartEnabled, setAutostartEnabled] = useState(false)
-  const [activeRecorder, setActiveRecorder] = useState<'capture' | 'record' | null>(null)
+  const { settings, updateSettings } = useSettings()
+  const [activeRecorder, setActiveRecorder] = useState<ShortcutAction | null>(null)
   const [heldKeys, setHeldKeys] = useState<string[]>([])
   const recorderRef = useRef<HTMLButtonElement>(null)
 
@@ -91,7 +180,18 @@ export function Settings({ onBack }: SettingsProps) {
     }
   }, [])
 
-  const openRecorder = useCallback((type: 'capture' | 'record') => {
+  const handleClipModeChange = useCallback(
+    async (clipMode: ClipMode) => {
+      try {
+        await updateSettings({ clipMode })
+      } catch (error) {
+        toast.error(String(error))
+      }
+    },
+    [updateSettings],
+  )
+
+  const openRecorder = useCallback((type: ShortcutAction) => {
     setActiveRecorder(type)
     setHeldKeys([])
     requestAnimationFrame(() => recorderRef.current?.focus())
@@ -126,8 +226,11 @@ export function Settings({ onBack }: SettingsProps) {
       const shortcut = keyEventToShortcut(e.nativeEvent)
       if (shortcut) {
         setHeldKeys(parseShortcutKeys(shortcut))
-        const handler =
-          activeRecorder === 'capture' ? onCaptureShortcutChange : onRecordShortcutChange
+        const handler = {
+          capture: onCaptureShortcutChange,
+          record: onRecordShortcutChange,
+          clip: onClipShortcutChange,
+        }[activeRecorder ?? 'capture']
         // Brief flash of the full combo before closing
         setTimeout(() => {
           setActiveRecorder(null)
@@ -136,7 +239,7 @@ export function Settings({ onBack }: SettingsProps) {
         }, 150)
       }
     },
-    [activeRecorder, onCaptureShortcutChange, onRecordShortcutChange],
+    [activeRecorder, onCaptureShortcutChange, onRecordShortcutChange, onClipShortcutChange],
   )
 
   const handleRecorderKeyUp = useCallback((e: React.KeyboardEvent<HTMLButtonElement>) => {
@@ -189,98 +292,76 @@ export function Settings({ onBack }: SettingsProps) {
               </div>
 
               <div className="space-y-3">
-                {/* Quick Capture */}
-                <div className="flex items-center justify-between">
-                  <div className="space-y-0.5">
-                    <p className="text-sm font-medium text-foreground">Quick Capture</p>
-                    <p className="text-xs text-muted-foreground">Open the capture window</p>
-                  </div>
-                  {activeRecorder === 'capture' ? (
-                    <button
-                      ref={recorderRef}
-                      onKeyDown={handleRecorderKeyDown}
-                      onKeyUp={handleRecorderKeyUp}
-                      onBlur={handleRecorderBlur}
-                      aria-live="polite"
-                      className="shortcut-recorder-recording flex items-center gap-1 rounded-lg border-2 px-3 py-2 outline-none"
-                    >
-                      {heldKeys.length > 0 ? (
-                        heldKeys.map((key, i) => (
-                          <span key={i}>
-                            {i > 0 && (
-                              <span className="mx-0.5 text-xs text-muted-foreground">+</span>
-                            )}
-                            <kbd className="shortcut-key">{key}</kbd>
-                          </span>
-                        ))
-                      ) : (
-                        <span className="text-sm text-muted-foreground">Press keys...</span>
-                      )}
-                    </button>
-                  ) : (
-                    <button
-                      onClick={() => openRecorder('capture')}
-                      className="flex items-center gap-1 rounded-lg border border-border px-3 py-2 transition-colors hover:border-muted-foreground/30"
-                    >
-                      {parseShortcutKeys(captureShortcut).map((key, i) => (
-                        <span key={i}>
-                          {i > 0 && <span className="mx-0.5 text-xs text-muted-foreground">+</span>}
-                          <kbd className="shortcut-key">{key}</kbd>
-                        </span>
-                      ))}
-                    </button>
-                  )}
-                </div>
+                <ShortcutRow
+                  label="Quick Capture"
+                  description="Open the capture window"
+                  shortcut={captureShortcut}
+                  error={captureShortcutError}
+                  recording={activeRecorder === 'capture'}
+                  heldKeys={heldKeys}
+                  recorderRef={recorderRef}
+                  onOpen={() => openRecorder('capture')}
+                  onKeyDown={handleRecorderKeyDown}
+                  onKeyUp={handleRecorderKeyUp}
+                  onBlur={handleRecorderBlur}
+                />
 
-                {/* Quick Record */}
-                <div className="flex items-center justify-between">
-                  <div className="space-y-0.5">
-                    <p className="text-sm font-medium text-foreground">Quick Record</p>
-                    <p className="text-xs text-muted-foreground">
-                      Open capture and start voice recording
-                    </p>
-                  </div>
-                  {activeRecorder === 'record' ? (
-                    <button
-                      ref={recorderRef}
-                      onKeyDown={handleRecorderKeyDown}
-                      onKeyUp={handleRecorderKeyUp}
-                      onBlur={handleRecorderBlur}
-                      aria-live="polite"
-                      className="shortcut-recorder-recording flex items-center gap-1 rounded-lg border-2 px-3 py-2 outline-none"
+                <ShortcutRow
+                  label="Quick Record"
+                  description="Open capture and start voice recording"
+                  shortcut={recordShortcut}
+                  error={recordShortcutError}
+                  recording={activeRecorder === 'record'}
+                  heldKeys={heldKeys}
+                  recorderRef={recorderRef}
+                  onOpen={() => openRecorder('record')}
+                  onKeyDown={handleRecorderKeyDown}
+                  onKeyUp={handleRecorderKeyUp}
+                  onBlur={handleRecorderBlur}
+                />
+
+                <ShortcutRow
+                  label="Capture Clipboard"
+                  description="Capture copied text (on Linux, the highlighted text)"
+                  shortcut={clipShortcut}
+                  error={clipShortcutError}
+                  recording={activeRecorder === 'clip'}
+                  heldKeys={heldKeys}
+                  recorderRef={recorderRef}
+                  onOpen={() => openRecorder('clip')}
+                  onKeyDown={handleRecorderKeyDown}
+                  onKeyUp={handleRecorderKeyUp}
+                  onBlur={handleRecorderBlur}
+                />
+
+                {settings && (
+                  <div className="flex items-center justify-between gap-3">
+                    <Label
+                      htmlFor="clip-mode"
+                      className="text-sm font-normal text-muted-foreground"
                     >
-                      {heldKeys.length > 0 ? (
-                        heldKeys.map((key, i) => (
-                          <span key={i}>
-                            {i > 0 && (
-                              <span className="mx-0.5 text-xs text-muted-foreground">+</span>
-                            )}
-                            <kbd className="shortcut-key">{key}</kbd>
-                          </span>
-                        ))
-                      ) : (
-                        <span className="text-sm text-muted-foreground">Press keys...</span>
-                      )}
-                    </button>
-                  ) : (
-                    <button
-                      onClick={() => openRecorder('record')}
-                      className="flex items-center gap-1 rounded-lg border border-border px-3 py-2 transition-colors hover:border-muted-foreground/30"
+                      Copied text
+                    </Label>
+                    <select
+                      id="clip-mode"
+                      className="h-9 rounded-md border border-input bg-transparent px-3 text-sm"
+                      value={settings.clipMode}
+                      onChange={(e) => handleClipModeChange(e.target.value as ClipMode)}
                     >
-                      {parseShortcutKeys(recordShortcut).map((key, i) => (
-                        <span key={i}>
-                          {i > 0 && <span className="mx-0.5 text-xs text-muted-foreground">+</span>}
-                          <kbd className="shortcut-key">{key}</kbd>
-                        </span>
-                      ))}
-                    </button>
-                  )}
-                </div>
+                      <option value="prefill">Opens in the capture window</option>
+                      <option value="save">Is saved right away</option>
+                    </select>
+                  </div>
+                )}
               </div>
             </div>
 
             <hr className="border-border" />
 
+            <MicrophoneSettings />
+
+            <hr className="border-border" />
+
             {/* Autostart */}
             <div className="space-y-4">
               <div className="flex items-center gap-2">
@@ -362,17 +443,57 @@ export function Settings({ onBack }: SettingsProps) {
                       <span className="truncate text-sm text-muted-foreground">{exportDir}</span>
                     )}
                   </div>
-                  {!exportDir && (
-                    <p className="text-sm text-muted-foreground">
-                      Choose a folder where ideas will be exported as Markdown files.
-                    </p>
-                  )}
+                  <p className="text-sm text-muted-foreground">
+                    {exportDir
+                      ? 'Edits, renames and deletes made to these files sync back to Glimt. Deleting a file archives its idea.'
+                      : 'Choose a folder where ideas will be exported as Markdown files.'}
+                  </p>
                 </div>
               )}
             </div>
 
             <hr className="border-border" />
 
+            {/* Tags */}
+            <TagSettings />
+
+            <hr className="border-border" />
+
+            {/* Markdown import */}
+            <MarkdownImportSettings />
+
+            <hr className="border-border" />
+
+            {/* Backup */}
+            <BackupSettings />
+
+            <hr className="border-border" />
+
+            {/* Automatic backups */}
+            <ScheduledBackupSettings />
+
+            <hr className="border-border" />
+
+            {/* Encryption */}
+            <EncryptionSettings />
+
+            <hr className="border-border" />
+
+            {/* App lock */}
+            <AppLockSettings />
+
+            <hr className="border-border" />
+
+            {/* Reminders */}
+            <ReminderSettings />
+
+            <hr className="border-border" />
+
+            {/* Capture API */}
+            <CaptureApiSettings />
+
+            <hr className="border-border" />
+
             {/* Appearance */}
             <div className="space-y-4">
               <div className="flex items-center gap-2">
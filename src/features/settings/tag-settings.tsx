@@ -0,0 +1,149 @@
+import { ask } from '@tauri-apps/plugin-dialog'
+import { useCallback, useEffect, useState, type FormEvent } from 'react'
+import { toast } from 'sonner'
+import { Button } from '@/components/ui/button'
+import { Input } from '@/components/ui/input'
+import { createTag, deleteTag, listTags, renameTag } from '@/lib/tags'
+import type { TagCount } from '@/lib/types'
+import { RiHashtag } from '@remixicon/react'
+
+export function TagSettings() {
+  const [tags, setTags] = useState<TagCount[]>([])
+  const [newTag, setNewTag] = useState('')
+  const [renaming, setRenaming] = useState<string | null>(null)
+  const [renameTo, setRenameTo] = useState('')
+  const [busy, setBusy] = useState(false)
+
+  const refresh = useCallback(() => {
+    listTags()
+      .then(setTags)
+      .catch((err) => console.error('[Settings] Failed to list tags:', err))
+  }, [])
+
+  useEffect(refresh, [refresh])
+
+  const run = useCallback(
+    async (action: () => Promise<unknown>, success: string) => {
+      setBusy(true)
+      try {
+        await action()
+        toast.success(success)
+        return true
+      } catch (error) {
+        toast.error(String(error))
+        return false
+      } finally {
+        setBusy(false)
+        refresh()
+      }
+    },
+    [refresh],
+  )
+
+  const handleCreate = useCallback(
+    async (event: FormEvent) => {
+      event.preventDefault()
+      if (!newTag.trim()) return
+      if (await run(() => createTag(newTag), 'Tag created')) setNewTag('')
+    },
+    [newTag, run],
+  )
+
+  const handleRename = useCallback(
+    async (event: FormEvent) => {
+      event.preventDefault()
+      if (!renaming || !renameTo.trim()) return
+      if (await run(() => renameTag(renaming, renameTo), 'Tag renamed')) setRenaming(null)
+    },
+    [renaming, renameTo, run],
+  )
+
+  const handleDelete = useCallback(
+    async (tag: TagCount) => {
+      const confirmed = await ask(
+        `#${tag.name} is removed from ${tag.count} idea${tag.count === 1 ? '' : 's'}. Hashtags in their text keep the word without the #.`,
+        { title: 'Delete this tag?', kind: 'warning' },
+      )
+      if (confirmed) await run(() => deleteTag(tag.name), 'Tag deleted')
+    },
+    [run],
+  )
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center gap-2">
+        <RiHashtag className="size-5 text-primary" />
+        <h3 className="text-lg font-semibold">Tags</h3>
+      </div>
+
+      <p className="text-sm text-muted-foreground">
+        Write a #hashtag in an idea to tag it. Renaming a tag updates the hashtags in every idea
+        that uses it.
+      </p>
+
+      <form onSubmit={handleCreate} className="flex items-center gap-3">
+        <Input
+          value={newTag}
+          onChange={(e) => setNewTag(e.target.value)}
+          placeholder="New tag"
+          className="max-w-xs"
+        />
+        <Button type="submit" variant="outline" size="sm" disabled={busy || !newTag.trim()}>
+          Create tag
+        </Button>
+      </form>
+
+      {tags.length > 0 && (
+        <ul className="space-y-1 rounded-md border p-3 text-sm">
+          {tags.map((tag) => (
+            <li key={tag.name} className="flex items-center gap-3">
+              {renaming === tag.name ? (
+                <form onSubmit={handleRename} className="flex flex-1 items-center gap-2">
+                  <Input
+                    autoFocus
+                    value={renameTo}
+                    onChange={(e) => setRenameTo(e.target.value)}
+                    onKeyDown={(e) => e.key === 'Escape' && setRenaming(null)}
+                    className="h-8"
+                  />
+                  <Button type="submit" size="sm" disabled={busy || !renameTo.trim()}>
+                    Rename
+                  </Button>
+                  <Button type="button" variant="ghost" size="sm" onClick={() => setRenaming(null)}>
+                    Cancel
+                  </Button>
+                </form>
+              ) : (
+                <>
+                  <span className="flex-1 truncate">#{tag.name}</span>
+                  <span className="text-muted-foreground">
+                    {tag.count} idea{tag.count === 1 ? '' : 's'}
+                  </span>
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    disabled={busy}
+                    onClick={() => {
+                      setRenaming(tag.name)
+                      setRenameTo(tag.name)
+                    }}
+                  >
+                    Rename
+                  </Button>
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    disabled={busy}
+                    onClick={() => handleDelete(tag)}
+                  >
+                    Delete
+                  </Button>
+                </>
+              )}
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  )
+}
@@ -1,12 +1,15 @@
 import { embedForStorage } from '@/lib/ai/embeddings'
-import { STORAGE_KEYS } from '@/lib/storage-keys'
 import { preloadWhisperModel } from '@/lib/ai/whisper'
-import { createIdea, getIdea, initDb, storeEmbedding } from '@/lib/db'
+import { createIdea, initDb, storeEmbedding } from '@/lib/db'
 import { exportIdea } from '@/lib/export-service'
-import { useAudioRecording } from '@/lib/use-audio-recording'
+import { useTheme } from '@/lib/hooks/use-theme'
+import { keepRecording } from '@/lib/recordings'
+import { getSettings } from '@/lib/settings'
+import type { ShortcutsStatus, TranscriptionProgress } from '@/lib/types'
+import { useNativeRecording } from '@/lib/use-native-recording'
 import { useCallback, useEffect, useRef, useState } from 'react'
 import { RecordingPill } from './features/indicator/recording-pill'
-import { getSavedRecordShortcut, parseShortcutKeys } from './lib/shortcut'
+import { DEFAULT_RECORD_SHORTCUT, getShortcuts, parseShortcutKeys } from './lib/shortcut'
 
 type IndicatorState = 'recording' | 'transcribing' | 'saved' | 'error'
 
@@ -33,10 +36,13 @@ export function IndicatorApp() {
   const [pillState, setPillState] = useState<IndicatorState>('recording')
   const [elapsedSeconds, setElapsedSeconds] = useState(0)
   const [errorMessage, setErrorMessage] = useState<string | null>(null)
+  const [progress, setProgress] = useState<number | null>(null)
   const timerRef = useRef<ReturnType<typeof setInterval> | null>(null)
   const hideTimerRef = useRef<ReturnType<typeof setTimeout> | null>(null)
 
-  const shortcutLabel = parseShortcutKeys(getSavedRecordShortcut()).join('+')
+  useTheme()
+  const [recordShortcut, setRecordShortcut] = useState(DEFAULT_RECORD_SHORTCUT)
+  const shortcutLabel = parseShortcutKeys(recordShortcut).join('+')
 
   // Cleanup function for timers
   const clearTimers = useCallback(() => {
@@ -73,6 +79,8 @@ export function IndicatorApp() {
 
       try {
         const idea = await createIdea(text)
+        // Keeps the audio only when the keepRecordings setting is on
+        keepRecording(idea.id).catch(console.error)
 
         // Await embedding before notifying dashboard so semantic search finds the new idea
         try {
@@ -92,14 +100,7 @@ export function IndicatorApp() {
         scheduleHide(1500)
 
         // Background: export
-        const exportEnabled = localStorage.getItem(STORAGE_KEYS.EXPORT_ENABLED) === 'true'
-        const exportDir = localStorage.getItem(STORAGE_KEYS.EXPORT_DIR)
-        if (exportEnabled && exportDir) {
-          const fullIdea = await getIdea(idea.id)
-          if (fullIdea) {
-            exportIdea(fullIdea, exportDir).catch(console.error)
-          }
-        }
+        exportIdea(idea.id).catch(console.error)
       } catch (saveError) {
         console.error('Failed to save idea from indicator:', saveError)
         setErrorMessage('Save failed')
@@ -120,12 +121,17 @@ export function IndicatorApp() {
     [clearTimers, scheduleHide],
   )
 
-  const {
-    state: recordingState,
-    stream,
-    startRecording,
-    stopRecording,
-  } = useAudioRecording({
+  const handleStart = useCallback(() => {
+    clearTimers()
+    setErrorMessage(null)
+    setPillState('recording')
+    setElapsedSeconds(0)
+    positionBottomRight().catch(console.error)
+  }, [clearTimers])
+
+  // The record shortcut captures the microphone in Rust; this only follows it
+  const { state: recordingState, level } = useNativeRecording({
+    onStart: handleStart,
     onTranscriptionComplete: handleTranscriptionComplete,
     onError: handleError,
   })
@@ -136,6 +142,7 @@ export function IndicatorApp() {
       setPillState('recording')
     } else if (recordingState === 'transcribing') {
       setPillState('transcribing')
+      setProgress(null)
       if (timerRef.current) {
         clearInterval(timerRef.current)
         timerRef.current = null
@@ -159,7 +166,7 @@ export function IndicatorApp() {
     }
   }, [recordingState])
 
-  // Initialize DB and theme
+  // Initialize DB
   useEffect(() => {
     document.documentElement.classList.add('capture-transparent')
     import('@tauri-apps/api/webview')
@@ -170,24 +177,14 @@ export function IndicatorApp() {
         // Expected when not running in Tauri context (e.g. Vite dev server)
       })
 
-    // Apply theme
-    const theme = localStorage.getItem(STORAGE_KEYS.THEME) ?? 'system'
-    if (
-      theme === 'dark' ||
-      (theme === 'system' && matchMedia('(prefers-color-scheme:dark)').matches)
-    ) {
-      document.documentElement.classList.add('dark')
-    } else {
-      document.documentElement.classList.remove('dark')
-    }
-
     initDb()
       .then(() => {
         setDbReady(true)
-        const savedSttModel = localStorage.getItem(STORAGE_KEYS.STT_MODEL)
-        if (savedSttModel) {
-          preloadWhisperModel(savedSttModel)
-        }
+        getSettings()
+          .then(({ sttModel }) => {
+            if (sttModel) preloadWhisperModel(sttModel)
+          })
+          .catch(console.error)
       })
       .catch((error: unknown) => {
         console.error('Indicator DB init failed:', error)
@@ -196,51 +193,60 @@ export function IndicatorApp() {
     return () => document.documentElement.classList.remove('capture-transparent')
   }, [])
 
-  // Listen for start-recording / stop-recording events
+  // Keep the stop hint in sync with the record shortcut
   useEffect(() => {
-    let unlistenStart: (() => void) | undefined
-    let unlistenStop: (() => void) | undefined
+    let unlisten: (() => void) | undefined
 
+    getShortcuts()
+      .then((status) => setRecordShortcut(status.record.shortcut))
+      .catch(() => {
+        // Expected when not running in Tauri context (e.g. Vite dev server)
+      })
     import('@tauri-apps/api/event')
-      .then(({ listen }) => {
-        listen('start-recording', () => {
-          setErrorMessage(null)
-          setPillState('recording')
-          setElapsedSeconds(0)
-          positionBottomRight()
-            .then(() => startRecording())
-            .catch((error) => {
-              console.error('Failed to start recording:', error)
-              handleError(error instanceof Error ? error.message : 'Could not access microphone')
-            })
-        }).then((fn) => {
-          unlistenStart = fn
-        })
-
-        listen('stop-recording', () => {
-          stopRecording()
-        }).then((fn) => {
-          unlistenStop = fn
-        })
+      .then(({ listen }) =>
+        listen<ShortcutsStatus>('shortcuts-changed', (event) =>
+          setRecordShortcut(event.payload.record.shortcut),
+        ),
+      )
+      .then((fn) => {
+        unlisten = fn
       })
       .catch(() => {
         // Expected when not running in Tauri context (e.g. Vite dev server)
       })
 
-    return () => {
-      unlistenStart?.()
-      unlistenStop?.()
-    }
-  }, [startRecording, stopRecording, handleError])
+    return () => unlisten?.()
+  }, [])
+
+  // Native transcription reports its progress from Rust
+  useEffect(() => {
+    let unlisten: (() => void) | undefined
+
+    import('@tauri-apps/api/event')
+      .then(({ listen }) =>
+        listen<TranscriptionProgress>('transcription-progress', (event) => {
+          setProgress(event.payload.stage === 'transcribing' ? event.payload.percent : null)
+        }),
+      )
+      .then((fn) => {
+        unlisten = fn
+      })
+      .catch(() => {
+        // Expected when not running in Tauri context (e.g. Vite dev server)
+      })
+
+    return () => unlisten?.()
+  }, [])
 
   if (!dbReady) return null
 
   return (
     <RecordingPill
       state={pillState}
-      stream={stream}
+      level={level}
       elapsedSeconds={elapsedSeconds}
       error={errorMessage}
+      progress={progress}
       shortcutLabel={shortcutLabel}
     />
   )
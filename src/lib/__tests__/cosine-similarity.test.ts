@@ -6,7 +6,7 @@ vi.mock('@/lib/ai/embeddings', () => ({
 }))
 
 vi.mock('@/lib/db', () => ({
-  getAllEmbeddings: vi.fn(),
+  searchSimilar: vi.fn(),
   getIdeasByIds: vi.fn(),
 }))
 
@@ -12,6 +12,12 @@ function makeIdea(overrides: Partial<Idea> = {}): Idea {
     archived: false,
     sourceApp: null,
     markdownPath: null,
+    sourceUrl: null,
+    deletedAt: null,
+    remindAt: null,
+    attachments: [],
+    recording: null,
+    tags: [],
     ...overrides,
   }
 }
@@ -49,9 +55,9 @@ describe('generateMarkdown', () => {
     expect(md).toContain('title: "He said \\"hello\\""')
   })
 
-  it('preserves newlines in title (within YAML quoted string)', () => {
-    const md = generateMarkdown(makeIdea({ title: 'Line1\nLine2' }))
-    expect(md).toContain('title: "Line1\nLine2"')
+  it('escapes newlines and backslashes in title', () => {
+    const md = generateMarkdown(makeIdea({ title: 'C:\\new\nLine2' }))
+    expect(md).toContain('title: "C:\\\\new\\nLine2"\n')
   })
 
   it('preserves colons in title', () => {
@@ -69,6 +75,16 @@ describe('generateMarkdown', () => {
     expect(md).toContain('title: "It\'s great"')
   })
 
+  it('omits tags from frontmatter when there are none', () => {
+    const md = generateMarkdown(makeIdea())
+    expect(md).not.toContain('tags:')
+  })
+
+  it('lists tags in frontmatter after the title', () => {
+    const md = generateMarkdown(makeIdea({ title: 'My Idea', tags: ['garden', 'work/client'] }))
+    expect(md).toContain('title: "My Idea"\ntags:\n  - "garden"\n  - "work/client"\n---')
+  })
+
   it('separates frontmatter from body with a blank line', () => {
     const md = generateMarkdown(makeIdea({ text: 'Body content' }))
     expect(md).toContain('---\n\nBody content\n')
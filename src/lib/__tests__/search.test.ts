@@ -1,46 +1,54 @@
-import { beforeAll, beforeEach, describe, expect, it, vi } from 'vitest'
+import { beforeEach, describe, expect, it, vi } from 'vitest'
 import type { Idea } from '../types'
-import { serializeEmbedding } from '../embedding-codec'
 
 // --- Mocks ---
 
-const mockSelect = vi.fn().mockResolvedValue([])
-const mockExecute = vi.fn().mockResolvedValue(undefined)
+const mockInvoke = vi.fn()
 
-vi.mock('@tauri-apps/plugin-sql', () => ({
-  default: {
-    load: vi.fn().mockResolvedValue({
-      select: (...args: unknown[]) => mockSelect(...args),
-      execute: (...args: unknown[]) => mockExecute(...args),
-    }),
-  },
+vi.mock('@tauri-apps/api/core', () => ({
+  invoke: (...args: unknown[]) => mockInvoke(...args),
 }))
 
 vi.mock('@/lib/ai/embeddings', () => ({
+  EMBEDDING_MODEL: 'multilingual-e5-small',
   embedForQuery: vi.fn(),
 }))
 
 // Import after mocks are set up
-import { searchIdeas } from '../search'
-import { initDb, searchIdeasFts } from '../db'
+import { hybridSearch, searchIdeas } from '../search'
+import { searchIdeasFts } from '../db'
 import { embedForQuery } from '@/lib/ai/embeddings'
 
 // --- Helpers ---
 
-function makeRow(overrides: Record<string, unknown> = {}): Record<string, unknown> {
+function makeIdea(overrides: Partial<Idea> = {}): Idea {
   return {
     id: 'idea-1',
-    created_at: 1700000000000,
-    updated_at: 1700000000000,
+    createdAt: 1700000000000,
+    updatedAt: 1700000000000,
     text: 'test idea',
     title: null,
-    archived: 0,
-    source_app: null,
-    markdown_path: null,
+    archived: false,
+    sourceApp: null,
+    markdownPath: null,
+    sourceUrl: null,
+    deletedAt: null,
+    remindAt: null,
+    attachments: [],
+    recording: null,
+    tags: [],
     ...overrides,
   }
 }
 
+/** Route invoke() calls by command name, as the Rust handlers would. */
+function mockCommands(handlers: Record<string, (args: Record<string, unknown>) => unknown>): void {
+  mockInvoke.mockImplementation((cmd: string, args: Record<string, unknown> = {}) => {
+    const handler = handlers[cmd]
+    return Promise.resolve(handler ? handler(args) : [])
+  })
+}
+
 function assertIdea(idea: Idea, expected: { id: string; text: string; archived: boolean }): void {
   expect(idea.id).toBe(expected.id)
   expect(idea.text).toBe(expected.text)
@@ -49,24 +57,21 @@ function assertIdea(idea: Idea, expected: { id: string; text: string; archived:
 
 // --- Setup ---
 
-beforeAll(async () => {
-  await initDb()
-})
-
 beforeEach(() => {
-  mockSelect.mockReset()
-  mockExecute.mockReset().mockResolvedValue(undefined)
+  mockInvoke.mockReset().mockResolvedValue([])
   vi.mocked(embedForQuery).mockReset()
 })
 
 // --- Tests ---
 
 describe('FTS search', () => {
-  it('returns relevant results with correct idea mapping', async () => {
-    mockSelect.mockResolvedValue([
-      makeRow({ id: 'fts-1', text: 'meeting notes from standup', title: 'Standup' }),
-      makeRow({ id: 'fts-2', text: 'meeting agenda for Q4', title: null }),
-    ])
+  it('returns relevant results from the search_ideas_fts command', async () => {
+    mockCommands({
+      search_ideas_fts: () => [
+        makeIdea({ id: 'fts-1', text: 'meeting notes from standup', title: 'Standup' }),
+        makeIdea({ id: 'fts-2', text: 'meeting agenda for Q4', title: null }),
+      ],
+    })
 
     const results = await searchIdeasFts('meeting')
 
@@ -76,91 +81,61 @@ describe('FTS search', () => {
     expect(results[0]!.title).toBe('Standup')
     expect(results[1]!.id).toBe('fts-2')
 
-    // Verify the SQL calls the FTS table
-    expect(mockSelect).toHaveBeenCalledWith(expect.stringContaining('fts_ideas MATCH'), ['meeting'])
-  })
-
-  it('maps snake_case columns to camelCase idea fields', async () => {
-    mockSelect.mockResolvedValue([
-      makeRow({
-        id: 'map-test',
-        created_at: 1000,
-        updated_at: 2000,
-        source_app: 'browser',
-        markdown_path: '/export/idea.md',
-        archived: 1,
-      }),
-    ])
-
-    const results = await searchIdeasFts('anything')
-
-    expect(results).toHaveLength(1)
-    expect(results[0]!.createdAt).toBe(1000)
-    expect(results[0]!.updatedAt).toBe(2000)
-    expect(results[0]!.sourceApp).toBe('browser')
-    expect(results[0]!.markdownPath).toBe('/export/idea.md')
-    expect(results[0]!.archived).toBe(true)
+    expect(mockInvoke).toHaveBeenCalledWith('search_ideas_fts', { query: 'meeting' })
   })
 })
 
 describe('semantic search', () => {
-  it('ranks results by cosine similarity (highest first)', async () => {
-    // Query vector points in X direction
+  it('keeps the ranking returned by the search_similar command', async () => {
     vi.mocked(embedForQuery).mockResolvedValue([1, 0, 0])
 
-    mockSelect.mockImplementation((sql: string) => {
-      if (sql.includes('embeddings')) {
-        return Promise.resolve([
-          {
-            idea_id: 'far',
-            dims: 3,
-            vector: serializeEmbedding([0, 1, 0]),
-          },
-          {
-            idea_id: 'close',
-            dims: 3,
-            vector: serializeEmbedding([0.9, 0.1, 0]),
-          },
-          {
-            idea_id: 'medium',
-            dims: 3,
-            vector: serializeEmbedding([0.5, 0.5, 0]),
-          },
-        ])
-      }
-      if (sql.includes('ideas')) {
-        return Promise.resolve([
-          makeRow({ id: 'close', text: 'close idea' }),
-          makeRow({ id: 'far', text: 'far idea' }),
-          makeRow({ id: 'medium', text: 'medium idea' }),
-        ])
-      }
-      return Promise.resolve([])
+    mockCommands({
+      search_similar: () => [
+        { ideaId: 'close', score: 0.99 },
+        { ideaId: 'medium', score: 0.7 },
+        { ideaId: 'far', score: 0.4 },
+      ],
+      get_ideas_by_ids: () => [
+        makeIdea({ id: 'far', text: 'far idea' }),
+        makeIdea({ id: 'close', text: 'close idea' }),
+        makeIdea({ id: 'medium', text: 'medium idea' }),
+      ],
     })
 
     const results = await searchIdeas('test query')
 
-    expect(results).toHaveLength(3)
-    expect(results[0]!.idea.id).toBe('close')
-    expect(results[1]!.idea.id).toBe('medium')
-    expect(results[2]!.idea.id).toBe('far')
-
-    // Scores should be descending
+    expect(results.map((r) => r.idea.id)).toEqual(['close', 'medium', 'far'])
     expect(results[0]!.score).toBeGreaterThan(results[1]!.score)
     expect(results[1]!.score).toBeGreaterThan(results[2]!.score)
+    expect(mockInvoke).toHaveBeenCalledWith('search_similar', {
+      model: 'multilingual-e5-small',
+      vector: [1, 0, 0],
+      topK: 20,
+    })
+  })
+
+  it('drops neighbours below the similarity threshold', async () => {
+    vi.mocked(embedForQuery).mockResolvedValue([1, 0])
+
+    mockCommands({
+      search_similar: () => [
+        { ideaId: 'match', score: 0.8 },
+        { ideaId: 'noise', score: 0.1 },
+      ],
+      get_ideas_by_ids: ({ ids }) =>
+        (ids as string[]).map((id) => makeIdea({ id, text: `${id} idea` })),
+    })
+
+    const results = await searchIdeas('query')
+    expect(results.map((r) => r.idea.id)).toEqual(['match'])
   })
 
   it('sets source to "semantic" for all results', async () => {
     vi.mocked(embedForQuery).mockResolvedValue([1, 0])
 
-    mockSelect.mockImplementation((sql: string) => {
-      if (sql.includes('embeddings')) {
-        return Promise.resolve([{ idea_id: 'id-1', dims: 2, vector: serializeEmbedding([1, 0]) }])
-      }
-      if (sql.includes('ideas')) {
-        return Promise.resolve([makeRow({ id: 'id-1' })])
-      }
-      return Promise.resolve([])
+    mockCommands({
+      search_similar: () => [{ ideaId: 'id-1', score: 1 }],
+      get_ideas_by_ids: () => [makeIdea({ id: 'id-1' })],
     })
 
     const results = await searchIdeas('query')
@@ -173,20 +148,15 @@ describe('archive filtering', () => {
   it('semantic search includes archived ideas (no archive filter in search)', async () => {
     vi.mocked(embedForQuery).mockResolvedValue([1, 0])
 
-    mockSelect.mockImplementation((sql: string) => {
-      if (sql.includes('embeddings')) {
-        return Promise.resolve([
-          { idea_id: 'archived-idea', dims: 2, vector: serializeEmbedding([1, 0]) },
-          { idea_id: 'active-idea', dims: 2, vector: serializeEmbedding([0.8, 0.2]) },
-        ])
-      }
-      if (sql.includes('ideas')) {
-        return Promise.resolve([
-          makeRow({ id: 'archived-idea', text: 'old idea', archived: 1 }),
-          makeRow({ id: 'active-idea', text: 'new idea', archived: 0 }),
-        ])
-      }
-      return Promise.resolve([])
+    mockCommands({
+      search_similar: () => [
+        { ideaId: 'archived-idea', score: 1 },
+        { ideaId: 'active-idea', score: 0.97 },
+      ],
+      get_ideas_by_ids: () => [
+        makeIdea({ id: 'archived-idea', text: 'old idea', archived: true }),
+        makeIdea({ id: 'active-idea', text: 'new idea', archived: false }),
+      ],
     })
 
     const results = await searchIdeas('test')
@@ -201,10 +171,12 @@ describe('archive filtering', () => {
   })
 
   it('FTS search includes archived ideas (no archive filter in FTS)', async () => {
-    mockSelect.mockResolvedValue([
-      makeRow({ id: 'archived', archived: 1, text: 'archived content' }),
-      makeRow({ id: 'active', archived: 0, text: 'active content' }),
-    ])
+    mockCommands({
+      search_ideas_fts: () => [
+        makeIdea({ id: 'archived', archived: true, text: 'archived content' }),
+        makeIdea({ id: 'active', archived: false, text: 'active content' }),
+      ],
+    })
 
     const results = await searchIdeasFts('content')
 
@@ -227,3 +199,47 @@ describe('empty query', () => {
     expect(embedForQuery).not.toHaveBeenCalled()
   })
 })
+
+describe('hybrid search', () => {
+  it('sends the query vector and filters in one hybrid_search call', async () => {
+    vi.mocked(embedForQuery).mockResolvedValue([1, 0])
+    const page = {
+      results: [{ idea: makeIdea(), score: 0.03, source: 'both', snippet: [] }],
+      total: 1,
+    }
+    mockCommands({ hybrid_search: () => page })
+
+    const result = await hybridSearch('idea', { filters: { archived: false }, offset: 50 })
+
+    expect(result).toEqual(page)
+    expect(mockInvoke).toHaveBeenCalledWith('hybrid_search', {
+      request: {
+        query: 'idea',
+        vector: [1, 0],
+        model: 'multilingual-e5-small',
+        filters: { archived: false },
+        offset: 50,
+        limit: 50,
+      },
+    })
+  })
+
+  it('falls back to keyword-only search when embedding fails', async () => {
+    vi.mocked(embedForQuery).mockRejectedValue(new Error('model not loaded'))
+    mockCommands({ hybrid_search: () => ({ results: [], total: 0 }) })
+
+    await hybridSearch('idea')
+
+    expect(mockInvoke).toHaveBeenCalledWith(
+      'hybrid_search',
+      expect.objectContaining({
+        request: expect.objectContaining({ vector: null, model: null }),
+      }),
+    )
+  })
+
+  it('skips the backend for blank queries', async () => {
+    expect(await hybridSearch('  ')).toEqual({ results: [], total: 0 })
+    expect(mockInvoke).not.toHaveBeenCalled()
+  })
+})
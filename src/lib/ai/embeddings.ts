@@ -109,3 +109,22 @@ export async function reembedAllIdeas(): Promise<{ total: number; failed: number
 
   return { total: ideas.length, failed }
 }
+
+/** Embed ideas that were saved without a vector (CLI, or the app closed mid-embed). */
+export async function embedMissingIdeas(): Promise<number> {
+  const { getIdeasMissingEmbeddings, storeEmbedding } = await import('@/lib/db')
+  const ideas = await getIdeasMissingEmbeddings(EMBEDDING_MODEL)
+  let embedded = 0
+
+  for (const idea of ideas) {
+    try {
+      const vector = await embedForStorage(idea.id, idea.text)
+      await storeEmbedding(idea.id, EMBEDDING_MODEL, vector)
+      embedded++
+    } catch (error) {
+      console.error(`Failed to embed idea ${idea.id}:`, error)
+    }
+  }
+
+  return embedded
+}
@@ -1,5 +1,6 @@
 import { experimental_transcribe as transcribe } from 'ai'
 import { transformersJS, TransformersJSTranscriptionModel } from '@browser-ai/transformers-js'
+import { downloadWhisperModel, getNativeSttStatus, transcribeNative } from '../native-transcription'
 import { ModelLifecycle } from './model-lifecycle'
 
 export const whisperLifecycle = new ModelLifecycle('Xenova/whisper-tiny')
@@ -7,6 +8,18 @@ export const whisperLifecycle = new ModelLifecycle('Xenova/whisper-tiny')
 let model: TransformersJSTranscriptionModel | null = null
 let currentModelId = 'Xenova/whisper-tiny'
 
+// Builds with the native-stt feature transcribe on the CPU in Rust, which
+// also works where the webview has no WebGPU. Otherwise use the worker.
+async function isNativeAvailable(): Promise<boolean> {
+  const status = await getNativeSttStatus()
+  return status?.available ?? false
+}
+
+/** Mirrors `transcribe::ggml_name`: "Xenova/whisper-base" -> "base". */
+export function ggmlName(modelId: string): string {
+  return (modelId.split('/').pop() ?? modelId).replace(/^whisper-/, '')
+}
+
 function getModel(modelId?: string): TransformersJSTranscriptionModel {
   const id = modelId ?? currentModelId
   if (model && currentModelId === id) return model
@@ -22,17 +35,30 @@ function getModel(modelId?: string): TransformersJSTranscriptionModel {
   return model
 }
 
-export function checkWhisperAvailability(): Promise<'unavailable' | 'downloadable' | 'available'> {
+export async function checkWhisperAvailability(): Promise<
+  'unavailable' | 'downloadable' | 'available'
+> {
+  const status = await getNativeSttStatus()
+  if (status?.available) {
+    return status.installed.includes(ggmlName(currentModelId)) ? 'available' : 'downloadable'
+  }
   return getModel().availability()
 }
 
 export async function loadWhisperWithProgress(modelId: string): Promise<void> {
   whisperLifecycle.startLoading(modelId)
   try {
-    const m = getModel(modelId)
-    await m.createSessionWithProgress((progress) => {
-      whisperLifecycle.setProgress(progress * 100)
-    })
+    if (await isNativeAvailable()) {
+      currentModelId = modelId
+      await downloadWhisperModel(modelId, (percent) => {
+        whisperLifecycle.setProgress(percent)
+      })
+    } else {
+      const m = getModel(modelId)
+      await m.createSessionWithProgress((progress) => {
+        whisperLifecycle.setProgress(progress * 100)
+      })
+    }
     whisperLifecycle.setReady()
   } catch (e: unknown) {
     whisperLifecycle.setError(e instanceof Error ? e.message : String(e))
@@ -41,6 +67,10 @@ export async function loadWhisperWithProgress(modelId: string): Promise<void> {
 }
 
 export async function transcribeAudio(audio: Blob): Promise<string> {
+  if (await isNativeAvailable()) {
+    const result = await transcribeNative(audio, currentModelId)
+    return result.text
+  }
   const buffer = await audio.arrayBuffer()
   const result = await transcribe({
     model: getModel(),
@@ -1,7 +1,7 @@
 import { createContext, useContext, type ReactNode } from 'react'
 import type { Theme } from '@/lib/hooks/use-theme'
 import type { UpdateStatus } from '@/lib/updater'
-import type { Idea } from '@/lib/types'
+import type { Idea, SnippetPart, SourceAppCount, TagCount } from '@/lib/types'
 
 export interface AppContextValue {
   // Theme
@@ -12,17 +12,38 @@ export interface AppContextValue {
   ideas: Idea[]
   showArchive: boolean
   archiveCount: number
+  /** Ideas waiting in the trash. */
+  trashCount: number
+  /** Hits for the active search across all pages; 0 when not searching. */
+  searchTotal: number
+  /** Highlighted keyword excerpts for the current search results, by idea id. */
+  searchSnippets: Record<string, SnippetPart[]>
+  /** Tags with their counts in the current list, archived or not. */
+  tags: TagCount[]
+  /** Only ideas with this tag are listed and searched. */
+  activeTag: string | null
+  /** Apps ideas in the current list were captured from, with their counts. */
+  sourceApps: SourceAppCount[]
+  /** Only ideas captured from this app are listed and searched. */
+  activeSourceApp: string | null
   exportEnabled: boolean
   exportDir: string | null
   autoTitleEnabled: boolean
   loadIdeas: (archived?: boolean) => Promise<void>
   onSearch: (query: string) => Promise<void>
+  onLoadMoreResults: () => Promise<void>
   onUpdate: (id: string, text: string) => Promise<void>
   onDelete: (id: string) => Promise<void>
   onArchive: (id: string) => Promise<void>
   onToggleArchive: (archived: boolean) => Promise<void>
+  onTagFilter: (tag: string | null) => Promise<void>
+  onSourceAppFilter: (sourceApp: string | null) => Promise<void>
   onCapture: () => Promise<void>
   onRegenerateTitle: (id: string) => Promise<void>
+  /** Put an earlier version of the idea back. */
+  onRestoreRevision: (id: string, revision: number) => Promise<void>
+  /** Remind about the idea at `remindAt` (ms); `null` removes the reminder. */
+  onSetReminder: (id: string, remindAt: number | null) => Promise<void>
   onExportEnabledChange: (enabled: boolean) => void
   onExportDirChange: (dir: string) => void
   onAutoTitleEnabledChange: (enabled: boolean) => void
@@ -36,9 +57,15 @@ export interface AppContextValue {
 
   // Shortcuts
   captureShortcut: string
+  /** Set when the shortcut is saved but could not be registered. */
+  captureShortcutError: string | null
   onCaptureShortcutChange: (shortcut: string) => Promise<void>
   recordShortcut: string
+  recordShortcutError: string | null
   onRecordShortcutChange: (shortcut: string) => Promise<void>
+  clipShortcut: string
+  clipShortcutError: string | null
+  onClipShortcutChange: (shortcut: string) => Promise<void>
 }
 
 const AppContext = createContext<AppContextValue | null>(null)
@@ -0,0 +1,34 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { AppLockStatus } from './types'
+
+// Sent by Rust to every window with `true` on lock and `false` on unlock.
+// While locked, Rust refuses every command that reads ideas back.
+export const APP_LOCK_CHANGED_EVENT = 'app-lock-changed'
+
+export async function getAppLockStatus(): Promise<AppLockStatus> {
+  return invoke<AppLockStatus>('app_lock_status')
+}
+
+/** Postpones auto-lock. Cheap, but callers should still throttle it. */
+export async function reportActivity(): Promise<void> {
+  await invoke('report_activity')
+}
+
+export async function lockApp(): Promise<void> {
+  await invoke('lock_app')
+}
+
+/** Rejects with "wrong PIN" after a short delay. */
+export async function unlockApp(pin: string): Promise<void> {
+  await invoke('unlock_app', { pin })
+}
+
+/** Set, change or remove (empty `pin`) the PIN. `current` is needed once one is set. */
+export async function setAppLockPin(current: string | null, pin: string): Promise<AppLockStatus> {
+  return invoke<AppLockStatus>('set_app_lock_pin', { current, pin })
+}
+
+export async function onAppLockChanged(handler: (locked: boolean) => void): Promise<() => void> {
+  const { listen } = await import('@tauri-apps/api/event')
+  return listen<boolean>(APP_LOCK_CHANGED_EVENT, (event) => handler(event.payload))
+}
@@ -0,0 +1,27 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { ArchiveImportMode, ArchiveImportSummary, ArchiveManifest } from './types'
+
+/**
+ * Write every idea, embedding and the settings to a zip archive at `path`.
+ * Archives are not encrypted; exporting an encrypted database fails unless
+ * `allowPlaintext` is set.
+ */
+export async function exportArchive(
+  path: string,
+  allowPlaintext = false,
+): Promise<ArchiveManifest> {
+  return invoke<ArchiveManifest>('export_archive', { path, allowPlaintext })
+}
+
+/**
+ * Import an archive from {@link exportArchive}. `merge` adds missing ideas and
+ * takes newer versions of existing ones; `replace` deletes every idea first
+ * and restores the archived theme and model preferences. Changes arrive
+ * through the `ideas-changed` event.
+ */
+export async function importArchive(
+  path: string,
+  mode: ArchiveImportMode,
+): Promise<ArchiveImportSummary> {
+  return invoke<ArchiveImportSummary>('import_archive', { path, mode })
+}
@@ -0,0 +1,33 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { Attachment } from './types'
+
+/** Attach a file from disk, e.g. one picked in a file dialog. */
+export async function attachFile(ideaId: string, path: string): Promise<Attachment> {
+  return invoke<Attachment>('attach_file', { id: ideaId, path })
+}
+
+/** Attach in-memory data such as a pasted screenshot, sent as the raw request body. */
+export async function attachData(ideaId: string, name: string, data: Blob): Promise<Attachment> {
+  const bytes = new Uint8Array(await data.arrayBuffer())
+  const headers = { 'x-idea-id': ideaId, 'x-name': encodeURIComponent(name) }
+  return invoke<Attachment>('attach_data', bytes, { headers })
+}
+
+export async function removeAttachment(id: string): Promise<void> {
+  return invoke<void>('remove_attachment', { id })
+}
+
+/** The attachment's contents, or its PNG thumbnail when it has one. */
+export async function readAttachment(
+  attachment: Pick<Attachment, 'id' | 'mime' | 'thumbnail'>,
+  options?: { thumbnail?: boolean },
+): Promise<Blob> {
+  const thumbnail = (options?.thumbnail ?? false) && attachment.thumbnail
+  const bytes = await invoke<ArrayBuffer>('read_attachment', { id: attachment.id, thumbnail })
+  return new Blob([bytes], { type: thumbnail ? 'image/png' : attachment.mime })
+}
+
+/** Copy the attachment out to `path`, e.g. one chosen in a save dialog. */
+export async function saveAttachment(id: string, path: string): Promise<void> {
+  return invoke<void>('save_attachment', { id, path })
+}
@@ -0,0 +1,19 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { BackupInfo } from './types'
+
+/** Database snapshots, newest first. The schedule lives in settings. */
+export async function listBackups(): Promise<BackupInfo[]> {
+  return invoke<BackupInfo[]>('list_backups')
+}
+
+export async function backupNow(): Promise<BackupInfo> {
+  return invoke<BackupInfo>('backup_now')
+}
+
+/**
+ * Replace the database with a snapshot. The current database is backed up
+ * first. Lists reload through the `ideas-changed` event.
+ */
+export async function restoreBackup(name: string): Promise<void> {
+  return invoke<void>('restore_backup', { name })
+}
@@ -0,0 +1,15 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { ApiConfig } from './types'
+
+export async function getApiConfig(): Promise<ApiConfig> {
+  return invoke<ApiConfig>('get_api_config')
+}
+
+/** Rejects (and leaves the API off) when the port cannot be bound. */
+export async function setApiConfig(enabled: boolean, port?: number): Promise<ApiConfig> {
+  return invoke<ApiConfig>('set_api_config', { enabled, port })
+}
+
+export async function regenerateApiToken(): Promise<ApiConfig> {
+  return invoke<ApiConfig>('regenerate_api_token')
+}
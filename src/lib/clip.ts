@@ -0,0 +1,10 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { Clip } from './types'
+
+/** Sent to the capture window when a clip arrives while it already has focus. */
+export const CLIP_CAPTURED_EVENT = 'clip-captured'
+
+/** The copied text the clip shortcut left for the capture window. Can only be taken once. */
+export async function takeClip(): Promise<Clip | null> {
+  return invoke<Clip | null>('take_clip')
+}
@@ -1,140 +1,86 @@
-import Database from '@tauri-apps/plugin-sql'
-import { deserializeEmbedding, serializeEmbedding } from './embedding-codec'
-import { runMigrations } from './migrations'
-import type { Idea, IdeaUpdate } from './types'
+import { invoke } from '@tauri-apps/api/core'
+import type { DbStartupError, Idea, IdeaUpdate, SourceAppCount } from './types'
 
-let db: Database | null = null
 let initPromise: Promise<void> | null = null
 
-export async function initDb(): Promise<void> {
-  if (db) return
-  if (!initPromise) {
-    initPromise = (async () => {
-      db = await Database.load('sqlite:glimt.db')
-      await runMigrations(db)
-    })()
+/** The database is encrypted and waiting for `unlockDatabase`. */
+export class DatabaseLockedError extends Error {
+  constructor() {
+    super('The database is locked')
+    this.name = 'DatabaseLockedError'
   }
-  return initPromise
 }
 
-async function getDb(): Promise<Database> {
-  if (!db) await initDb()
-  if (!db) throw new Error('Database failed to initialize.')
-  return db
+function describeStartupError(error: unknown): string {
+  if (typeof error === 'object' && error !== null && 'version' in error) {
+    const { version, description, message, backupPath } = error as DbStartupError
+    const base = `Migration V${version} ("${description}") failed: ${message}`
+    return backupPath ? `${base}. Your previous database was saved to ${backupPath}` : base
+  }
+  return error instanceof Error ? error.message : String(error)
 }
 
-/** Map a DB row (snake_case columns) to an Idea (camelCase fields). */
-function rowToIdea(row: Record<string, unknown>): Idea {
-  return {
-    id: row.id as string,
-    createdAt: row.created_at as number,
-    updatedAt: row.updated_at as number,
-    text: row.text as string,
-    title: (row.title as string) ?? null,
-    archived: (row.archived as number) === 1,
-    sourceApp: (row.source_app as string) ?? null,
-    markdownPath: (row.markdown_path as string) ?? null,
+// The Rust setup hook opens and migrates the database before any window is
+// shown; this only asks whether that succeeded. A locked database is asked
+// about again next time, since it can be unlocked from any window.
+export async function initDb(): Promise<void> {
+  if (!initPromise) {
+    initPromise = invoke<void>('db_status').catch((error: unknown) => {
+      if (typeof error === 'object' && error !== null && 'locked' in error) {
+        initPromise = null
+        throw new DatabaseLockedError()
+      }
+      throw new Error(describeStartupError(error))
+    })
   }
+  return initPromise
 }
 
-export async function createIdea(text: string): Promise<Idea> {
-  const conn = await getDb()
-  const id = crypto.randomUUID()
-  const now = Date.now()
-  await conn.execute(
-    'INSERT INTO ideas (id, created_at, updated_at, text) VALUES ($1, $2, $3, $4)',
-    [id, now, now, text],
-  )
-  return {
-    id,
-    createdAt: now,
-    updatedAt: now,
-    text,
-    title: null,
-    archived: false,
-    sourceApp: null,
-    markdownPath: null,
-  }
+export async function createIdea(
+  text: string,
+  options?: { sourceUrl?: string | null },
+): Promise<Idea> {
+  return invoke<Idea>('create_idea', { text, sourceUrl: options?.sourceUrl ?? null })
+}
+
+export async function getIdeas(options?: {
+  archived?: boolean
+  sourceApp?: string | null
+}): Promise<Idea[]> {
+  return invoke<Idea[]>('get_ideas', {
+    archived: options?.archived ?? false,
+    sourceApp: options?.sourceApp ?? null,
+  })
 }
 
-export async function getIdeas(options?: { archived?: boolean }): Promise<Idea[]> {
-  const conn = await getDb()
-  const archived = options?.archived ? 1 : 0
-  const rows = await conn.select<Record<string, unknown>[]>(
-    'SELECT * FROM ideas WHERE archived = $1 ORDER BY created_at DESC',
-    [archived],
-  )
-  return rows.map(rowToIdea)
+/** Apps ideas were captured from, busiest first. */
+export async function listSourceApps(options?: { archived?: boolean }): Promise<SourceAppCount[]> {
+  return invoke<SourceAppCount[]>('list_source_apps', { archived: options?.archived ?? null })
 }
 
 export async function getIdea(id: string): Promise<Idea | null> {
-  const conn = await getDb()
-  const rows = await conn.select<Record<string, unknown>[]>('SELECT * FROM ideas WHERE id = $1', [
-    id,
-  ])
-  const row = rows[0]
-  return row ? rowToIdea(row) : null
+  return invoke<Idea | null>('get_idea', { id })
 }
 
 export async function getIdeasByIds(ids: string[]): Promise<Idea[]> {
   if (ids.length === 0) return []
-  const conn = await getDb()
-  const placeholders = ids.map((_, i) => `$${i + 1}`).join(', ')
-  const rows = await conn.select<Record<string, unknown>[]>(
-    `SELECT * FROM ideas WHERE id IN (${placeholders})`,
-    ids,
-  )
-  return rows.map(rowToIdea)
+  return invoke<Idea[]>('get_ideas_by_ids', { ids })
 }
 
 export async function updateIdea(id: string, updates: IdeaUpdate): Promise<void> {
-  const conn = await getDb()
-  const now = Date.now()
-
-  const setClauses: string[] = ['updated_at = $1']
-  const params: unknown[] = [now]
-  let paramIndex = 2
-
-  if (updates.text !== undefined) {
-    setClauses.push(`text = $${paramIndex}`)
-    params.push(updates.text)
-    paramIndex++
-  }
-  if (updates.title !== undefined) {
-    setClauses.push(`title = $${paramIndex}`)
-    params.push(updates.title)
-    paramIndex++
-  }
-
-  params.push(id)
-  await conn.execute(`UPDATE ideas SET ${setClauses.join(', ')} WHERE id = $${paramIndex}`, params)
+  await invoke('update_idea', { id, updates })
 }
 
 export async function deleteIdea(id: string): Promise<void> {
-  const conn = await getDb()
-  await conn.execute('DELETE FROM ideas WHERE id = $1', [id])
+  await invoke('delete_idea', { id })
 }
 
 export async function archiveIdea(id: string, archived = true): Promise<void> {
-  const conn = await getDb()
-  const now = Date.now()
-  await conn.execute('UPDATE ideas SET archived = $1, updated_at = $2 WHERE id = $3', [
-    archived ? 1 : 0,
-    now,
-    id,
-  ])
+  await invoke('archive_idea', { id, archived })
 }
 
 export async function searchIdeasFts(query: string): Promise<Idea[]> {
-  const conn = await getDb()
-  const rows = await conn.select<Record<string, unknown>[]>(
-    `SELECT ideas.* FROM fts_ideas
-     JOIN ideas ON ideas.id = fts_ideas.id
-     WHERE fts_ideas MATCH $1
-     ORDER BY rank`,
-    [query],
-  )
-  return rows.map(rowToIdea)
+  return invoke<Idea[]>('search_ideas_fts', { query })
 }
 
 /** Store (or replace) an embedding vector for a given idea and model. */
@@ -143,51 +89,48 @@ export async function storeEmbedding(
   model: string,
   vector: number[],
 ): Promise<void> {
-  const conn = await getDb()
-  const blob = serializeEmbedding(vector)
-  await conn.execute(
-    'INSERT OR REPLACE INTO embeddings (idea_id, model, dims, vector, created_at) VALUES ($1, $2, $3, $4, $5)',
-    [ideaId, model, vector.length, blob, Date.now()],
-  )
+  await invoke('store_embedding', { ideaId, model, vector })
 }
 
 /** Retrieve all embeddings for a given model. */
 export async function getAllEmbeddings(
   model: string,
 ): Promise<Array<{ ideaId: string; vector: Float32Array }>> {
-  const conn = await getDb()
-  const rows = await conn.select<Record<string, unknown>[]>(
-    'SELECT idea_id, dims, vector FROM embeddings WHERE model = $1',
-    [model],
-  )
-  return rows.flatMap((row) => {
-    const dims = row.dims as number
-    const vector = deserializeEmbedding(row.vector, dims)
-    if (!vector) {
-      console.warn(`Skipping corrupted embedding for idea ${row.idea_id as string}`)
-      return []
-    }
-    return [{ ideaId: row.idea_id as string, vector }]
+  const rows = await invoke<Array<{ ideaId: string; vector: number[] }>>('get_all_embeddings', {
+    model,
   })
+  return rows.map(({ ideaId, vector }) => ({ ideaId, vector: new Float32Array(vector) }))
+}
+
+/** Nearest ideas to an embedded query from the Rust ANN index, best first. */
+export async function searchSimilar(
+  model: string,
+  vector: number[],
+  topK: number,
+): Promise<Array<{ ideaId: string; score: number }>> {
+  return invoke<Array<{ ideaId: string; score: number }>>('search_similar', {
+    model,
+    vector,
+    topK,
+  })
+}
+
+/** Ideas with no embedding for `model`, e.g. ones added from the CLI. */
+export async function getIdeasMissingEmbeddings(model: string): Promise<Idea[]> {
+  return invoke<Idea[]>('get_ideas_missing_embeddings', { model })
 }
 
 /** Delete all embeddings for a given idea. */
 export async function deleteEmbedding(ideaId: string): Promise<void> {
-  const conn = await getDb()
-  await conn.execute('DELETE FROM embeddings WHERE idea_id = $1', [ideaId])
+  await invoke('delete_embedding', { ideaId })
 }
 
 /** Delete all embeddings (used when re-embedding with a new model/config). */
 export async function deleteAllEmbeddings(): Promise<void> {
-  const conn = await getDb()
-  await conn.execute('DELETE FROM embeddings')
+  await invoke('delete_all_embeddings')
 }
 
 /** Get all ideas regardless of archive status. */
 export async function getAllIdeas(): Promise<Idea[]> {
-  const conn = await getDb()
-  const rows = await conn.select<Record<string, unknown>[]>(
-    'SELECT * FROM ideas ORDER BY created_at DESC',
-  )
-  return rows.map(rowToIdea)
+  return invoke<Idea[]>('get_all_ideas')
 }
@@ -3,7 +3,7 @@ export function serializeEmbedding(vector: number[]): number[] {
   return Array.from(new Uint8Array(new Float32Array(vector).buffer))
 }
 
-/** Normalize raw BLOB data from tauri-plugin-sql into a byte array. */
+/** Normalize raw BLOB data (JSON string, array or buffer) into a byte array. */
 function normalizeToBytes(raw: unknown): number[] | null {
   if (typeof raw === 'string') {
     try {
@@ -0,0 +1,34 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { EncryptionStatus } from './types'
+
+// Sent by Rust to every window once the database is unlocked.
+export const DATABASE_UNLOCKED_EVENT = 'database-unlocked'
+
+export async function getEncryptionStatus(): Promise<EncryptionStatus> {
+  return invoke<EncryptionStatus>('encryption_status')
+}
+
+/**
+ * Open the encrypted database. With `remember`, the passphrase is saved to
+ * the OS keyring and Glimt unlocks on its own from then on.
+ */
+export async function unlockDatabase(passphrase: string, remember: boolean): Promise<void> {
+  await invoke('unlock_database', { passphrase, remember })
+}
+
+/**
+ * Encrypt the database, change its passphrase, or decrypt it when
+ * `passphrase` is empty. `current` is required once it is encrypted.
+ */
+export async function setDatabasePassphrase(
+  current: string | null,
+  passphrase: string,
+  remember: boolean,
+): Promise<void> {
+  await invoke('set_database_passphrase', { current, passphrase, remember })
+}
+
+export async function onDatabaseUnlocked(handler: () => void): Promise<() => void> {
+  const { listen } = await import('@tauri-apps/api/event')
+  return listen(DATABASE_UNLOCKED_EVENT, handler)
+}
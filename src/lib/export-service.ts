@@ -1,20 +1,14 @@
-import { exists, mkdir, writeTextFile } from '@tauri-apps/plugin-fs'
-import { generateFilename, generateMarkdown } from './export'
-import type { Idea } from './types'
+import { invoke } from '@tauri-apps/api/core'
 
-export async function exportIdea(idea: Idea, exportDir: string): Promise<void> {
+/**
+ * Write an idea to the Markdown export folder, if export is enabled in
+ * settings. Rust records the file (`markdown_path`) and watches the folder,
+ * so edits made in Obsidian or Logseq sync back. Resolves to the file path,
+ * or `null` when export is off.
+ */
+export async function exportIdea(id: string): Promise<string | null> {
   try {
-    // Ensure directory exists (M4.4 resilience)
-    const dirExists = await exists(exportDir)
-    if (!dirExists) {
-      await mkdir(exportDir, { recursive: true })
-    }
-
-    const filename = generateFilename(idea)
-    const content = generateMarkdown(idea)
-    const filepath = `${exportDir}/${filename}`
-
-    await writeTextFile(filepath, content)
+    return await invoke<string | null>('export_idea_markdown', { id })
   } catch (error) {
     // Log but don't block capture (M4.4 resilience)
     console.error(
@@ -1,5 +1,10 @@
 import type { Idea } from './types'
 
+/** A double-quoted YAML string, escaped so the value stays on its line. */
+function quote(value: string): string {
+  return `"${value.replace(/\\/g, '\\\\').replace(/"/g, '\\"').replace(/\n/g, '\\n')}"`
+}
+
 export function generateMarkdown(idea: Idea): string {
   const created = new Date(idea.createdAt).toISOString()
   const updated = new Date(idea.updatedAt).toISOString()
@@ -12,7 +17,11 @@ export function generateMarkdown(idea: Idea): string {
   ]
 
   if (idea.title) {
-    lines.push(`title: "${idea.title.replace(/"/g, '\\"')}"`)
+    lines.push(`title: ${quote(idea.title)}`)
+  }
+
+  if (idea.tags.length > 0) {
+    lines.push('tags:', ...idea.tags.map((tag) => `  - ${quote(tag)}`))
   }
 
   lines.push('---', '', idea.text, '')
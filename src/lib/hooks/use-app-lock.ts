@@ -0,0 +1,58 @@
+import { getAppLockStatus, onAppLockChanged, reportActivity } from '@/lib/app-lock'
+import { useEffect, useState } from 'react'
+
+const ACTIVITY_THROTTLE_MS = 10_000
+const ACTIVITY_EVENTS = ['keydown', 'pointerdown', 'wheel'] as const
+
+/**
+ * Whether the app is locked, kept in sync across windows. With
+ * `trackActivity`, input in this window postpones auto-lock.
+ */
+export function useAppLock({ trackActivity = false } = {}) {
+  const [locked, setLocked] = useState(false)
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined
+    let cancelled = false
+
+    getAppLockStatus()
+      .then((status) => {
+        if (!cancelled) setLocked(status.locked)
+      })
+      .catch(() => {
+        // Expected when not running in Tauri context (e.g. Vite dev server)
+      })
+    onAppLockChanged(setLocked)
+      .then((fn) => {
+        if (cancelled) fn()
+        else unlisten = fn
+      })
+      .catch(() => {
+        // Expected when not running in Tauri context (e.g. Vite dev server)
+      })
+
+    return () => {
+      cancelled = true
+      unlisten?.()
+    }
+  }, [])
+
+  useEffect(() => {
+    if (!trackActivity || locked) return
+    let last = 0
+    function onActivity() {
+      const now = Date.now()
+      if (now - last < ACTIVITY_THROTTLE_MS) return
+      last = now
+      reportActivity().catch(() => {
+        // Expected when not running in Tauri context (e.g. Vite dev server)
+      })
+    }
+    for (const name of ACTIVITY_EVENTS) window.addEventListener(name, onActivity, { passive: true })
+    return () => {
+      for (const name of ACTIVITY_EVENTS) window.removeEventListener(name, onActivity)
+    }
+  }, [trackActivity, locked])
+
+  return { locked }
+}
@@ -1,29 +1,55 @@
-import { preloadEmbeddingModel } from '@/lib/ai/embeddings'
+import { embedMissingIdeas, preloadEmbeddingModel } from '@/lib/ai/embeddings'
 import { preloadWhisperModel } from '@/lib/ai/whisper'
-import { initDb } from '@/lib/db'
-import { STORAGE_KEYS } from '@/lib/storage-keys'
-import { useEffect, useState } from 'react'
+import { useDbReady } from '@/lib/hooks/use-db-ready'
+import { getSettings } from '@/lib/settings'
+import { useEffect } from 'react'
+
+function backfillEmbeddings(): void {
+  embedMissingIdeas().catch((error: unknown) => {
+    console.error('Embedding backfill failed:', error)
+  })
+}
+
+function preloadSavedSttModel(): void {
+  getSettings()
+    .then(({ sttModel }) => {
+      if (sttModel) preloadWhisperModel(sttModel)
+    })
+    .catch(console.error)
+}
 
 export function useDatabase() {
-  const [dbReady, setDbReady] = useState(false)
-  const [dbError, setDbError] = useState<string | null>(null)
+  const { dbReady, dbError, locked } = useDbReady()
+
+  useEffect(() => {
+    if (!dbReady) return
+    preloadEmbeddingModel()
+    backfillEmbeddings()
+    preloadSavedSttModel()
+  }, [dbReady])
 
+  // Ideas added through `glimt --add` arrive without an embedding, and
+  // vault edits drop the stale one
   useEffect(() => {
-    initDb()
-      .then(() => {
-        setDbReady(true)
-        preloadEmbeddingModel()
-        const savedSttModel = localStorage.getItem(STORAGE_KEYS.STT_MODEL)
-        if (savedSttModel) {
-          preloadWhisperModel(savedSttModel)
-        }
+    if (!dbReady) return
+    const unlisteners: (() => void)[] = []
+    import('@tauri-apps/api/event')
+      .then(({ listen }) =>
+        Promise.all([
+          listen('idea-saved', backfillEmbeddings),
+          listen('ideas-changed', backfillEmbeddings),
+        ]),
+      )
+      .then((fns) => {
+        unlisteners.push(...fns)
       })
-      .catch((error: unknown) => {
-        const message = error instanceof Error ? error.message : String(error)
-        console.error('DB init failed:', message)
-        setDbError(message)
+      .catch(() => {
+        // Not running in Tauri context
       })
-  }, [])
+    return () => {
+      for (const unlisten of unlisteners) unlisten()
+    }
+  }, [dbReady])
 
-  return { dbReady, dbError }
+  return { dbReady, dbError, locked }
 }
@@ -0,0 +1,55 @@
+import { DatabaseLockedError, initDb } from '@/lib/db'
+import { onDatabaseUnlocked } from '@/lib/encryption'
+import { useEffect, useState } from 'react'
+
+/**
+ * Whether the database can be used. While an encrypted database is locked,
+ * `locked` is set and the check runs again once any window unlocks it.
+ */
+export function useDbReady() {
+  const [dbReady, setDbReady] = useState(false)
+  const [dbError, setDbError] = useState<string | null>(null)
+  const [locked, setLocked] = useState(false)
+
+  useEffect(() => {
+    let cancelled = false
+    let unlisten: (() => void) | undefined
+
+    function check() {
+      initDb()
+        .then(() => {
+          if (cancelled) return
+          setLocked(false)
+          setDbReady(true)
+        })
+        .catch((error: unknown) => {
+          if (cancelled) return
+          if (error instanceof DatabaseLockedError) {
+            setLocked(true)
+            return
+          }
+          const message = error instanceof Error ? error.message : String(error)
+          console.error('DB init failed:', message)
+          setLocked(false)
+          setDbError(message)
+        })
+    }
+
+    check()
+    onDatabaseUnlocked(check)
+      .then((fn) => {
+        if (cancelled) fn()
+        else unlisten = fn
+      })
+      .catch(() => {
+        // Expected when not running in Tauri context (e.g. Vite dev server)
+      })
+
+    return () => {
+      cancelled = true
+      unlisten?.()
+    }
+  }, [])
+
+  return { dbReady, dbError, locked }
+}
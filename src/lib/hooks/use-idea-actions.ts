@@ -1,22 +1,49 @@
 import { embedForStorage } from '@/lib/ai/embeddings'
 import { ensureTitleModel, generateTitle } from '@/lib/ai/title-generation'
-import { archiveIdea, deleteIdea, getIdea, getIdeas, storeEmbedding, updateIdea } from '@/lib/db'
+import {
+  archiveIdea,
+  deleteIdea,
+  getIdeas,
+  listSourceApps,
+  storeEmbedding,
+  updateIdea,
+} from '@/lib/db'
 import { exportIdea } from '@/lib/export-service'
-import { searchIdeas } from '@/lib/search'
-import { STORAGE_KEYS } from '@/lib/storage-keys'
-import type { Idea } from '@/lib/types'
+import { useSettings } from '@/lib/hooks/use-settings'
+import { setReminder } from '@/lib/reminders'
+import { restoreRevision } from '@/lib/revisions'
+import { hybridSearch } from '@/lib/search'
+import { getIdeasByTag, listTags } from '@/lib/tags'
+import { listTrash, restoreIdea } from '@/lib/trash'
+import type { Idea, SnippetPart, SourceAppCount, TagCount, VaultConflict } from '@/lib/types'
 import { useCallback, useEffect, useRef, useState } from 'react'
 import { toast } from 'sonner'
 
+function snippetsById(
+  results: Array<{ idea: Idea; snippet?: SnippetPart[] | null }>,
+): Record<string, SnippetPart[]> {
+  const byId: Record<string, SnippetPart[]> = {}
+  for (const { idea, snippet } of results) {
+    if (snippet?.length) byId[idea.id] = snippet
+  }
+  return byId
+}
+
 export function useIdeaActions() {
   const [ideas, setIdeas] = useState<Idea[]>([])
   const [showArchive, setShowArchive] = useState(false)
   const [archiveCount, setArchiveCount] = useState(0)
-  const [exportEnabled, setExportEnabled] = useState(false)
-  const [exportDir, setExportDir] = useState<string | null>(null)
-  const [autoTitleEnabled, setAutoTitleEnabled] = useState(
-    () => localStorage.getItem(STORAGE_KEYS.AUTO_TITLE_ENABLED) === 'true',
-  )
+  const [trashCount, setTrashCount] = useState(0)
+  const [searchTotal, setSearchTotal] = useState(0)
+  const [searchSnippets, setSearchSnippets] = useState<Record<string, SnippetPart[]>>({})
+  const [tags, setTags] = useState<TagCount[]>([])
+  const [activeTag, setActiveTag] = useState<string | null>(null)
+  const [sourceApps, setSourceApps] = useState<SourceAppCount[]>([])
+  const [activeSourceApp, setActiveSourceApp] = useState<string | null>(null)
+  const { settings, updateSettings } = useSettings()
+  const exportEnabled = settings?.exportEnabled ?? false
+  const exportDir = settings?.exportDir ?? null
+  const autoTitleEnabled = settings?.autoTitleEnabled ?? false
 
   // Use ref to avoid stale closures in event listeners
   const showArchiveRef = useRef(showArchive)
@@ -25,29 +52,58 @@ export function useIdeaActions() {
   const ideasRef = useRef(ideas)
   ideasRef.current = ideas
 
-  const exportEnabledRef = useRef(exportEnabled)
-  exportEnabledRef.current = exportEnabled
+  const searchQueryRef = useRef('')
 
-  const exportDirRef = useRef(exportDir)
-  exportDirRef.current = exportDir
+  const activeTagRef = useRef(activeTag)
+  activeTagRef.current = activeTag
+
+  const activeSourceAppRef = useRef(activeSourceApp)
+  activeSourceAppRef.current = activeSourceApp
 
   const loadIdeas = useCallback(async (archived?: boolean) => {
     const showingArchive = archived ?? showArchiveRef.current
     try {
-      const loaded = await getIdeas({ archived: showingArchive })
+      const [tagCounts, appCounts] = await Promise.all([
+        listTags({ archived: showingArchive }),
+        listSourceApps({ archived: showingArchive }),
+      ])
+      setTags(tagCounts)
+      setSourceApps(appCounts)
+      // A renamed or deleted tag can't filter anything any more
+      let tag = activeTagRef.current
+      if (tag && !tagCounts.some((t) => t.name === tag)) {
+        tag = null
+        activeTagRef.current = null
+        setActiveTag(null)
+      }
+      let sourceApp = activeSourceAppRef.current
+      if (sourceApp && !appCounts.some((a) => a.sourceApp === sourceApp)) {
+        sourceApp = null
+        activeSourceAppRef.current = null
+        setActiveSourceApp(null)
+      }
+      const loaded = tag
+        ? await getIdeasByTag(tag, { archived: showingArchive, sourceApp })
+        : await getIdeas({ archived: showingArchive, sourceApp })
       setIdeas(loaded)
       getIdeas({ archived: true })
         .then((archivedIdeas) => setArchiveCount(archivedIdeas.length))
         .catch(console.error)
+      listTrash()
+        .then((trashed) => setTrashCount(trashed.length))
+        .catch(console.error)
     } catch (error) {
       console.error('Failed to load ideas:', error)
     }
   }, [])
 
-  // Listen for idea-saved and title-generated events from the capture window
+  // Listen for idea-saved and title-generated events from the capture window,
+  // and for edits synced back from the Markdown vault
   useEffect(() => {
     let unlistenSaved: (() => void) | undefined
     let unlistenTitle: (() => void) | undefined
+    let unlistenVault: (() => void) | undefined
+    let unlistenConflict: (() => void) | undefined
     import('@tauri-apps/api/event')
       .then(({ listen }) => {
         listen('idea-saved', () => {
@@ -61,6 +117,17 @@ export function useIdeaActions() {
         }).then((fn) => {
           unlistenTitle = fn
         })
+        listen('ideas-changed', () => {
+          loadIdeas()
+        }).then((fn) => {
+          unlistenVault = fn
+        })
+        listen<VaultConflict>('vault-conflict', (event) => {
+          const file = event.payload.path.split(/[\\/]/).pop()
+          toast.warning(`A vault edit conflicted with a newer change and was saved as ${file}`)
+        }).then((fn) => {
+          unlistenConflict = fn
+        })
       })
       .catch(() => {
         // Not running in Tauri context
@@ -68,26 +135,59 @@ export function useIdeaActions() {
     return () => {
       unlistenSaved?.()
       unlistenTitle?.()
+      unlistenVault?.()
+      unlistenConflict?.()
     }
   }, [loadIdeas])
 
+  const currentFilters = useCallback(
+    () => ({
+      archived: showArchiveRef.current,
+      tag: activeTagRef.current ?? undefined,
+      sourceApp: activeSourceAppRef.current ?? undefined,
+    }),
+    [],
+  )
+
   const handleSearch = useCallback(
     async (query: string) => {
+      searchQueryRef.current = query
       try {
         if (!query.trim()) {
+          setSearchTotal(0)
+          setSearchSnippets({})
           await loadIdeas()
           return
         }
-        const results = await searchIdeas(query)
-        const filtered = results.filter((r) => r.idea.archived === showArchiveRef.current)
-        setIdeas(filtered.map((r) => r.idea))
+        const page = await hybridSearch(query, { filters: currentFilters() })
+        if (searchQueryRef.current !== query) return
+        setIdeas(page.results.map((r) => r.idea))
+        setSearchTotal(page.total)
+        setSearchSnippets(snippetsById(page.results))
       } catch (error) {
         console.error('Failed to search ideas:', error)
       }
     },
-    [loadIdeas],
+    [loadIdeas, currentFilters],
   )
 
+  const handleLoadMoreResults = useCallback(async () => {
+    const query = searchQueryRef.current
+    if (!query.trim()) return
+    try {
+      const page = await hybridSearch(query, {
+        filters: currentFilters(),
+        offset: ideasRef.current.length,
+      })
+      if (searchQueryRef.current !== query) return
+      setIdeas((current) => [...current, ...page.results.map((r) => r.idea)])
+      setSearchTotal(page.total)
+      setSearchSnippets((current) => ({ ...current, ...snippetsById(page.results) }))
+    } catch (error) {
+      console.error('Failed to load more results:', error)
+    }
+  }, [currentFilters])
+
   const handleUpdate = useCallback(
     async (id: string, text: string) => {
       try {
@@ -106,16 +206,7 @@ export function useIdeaActions() {
 
         await loadIdeas()
         toast.success('Idea updated')
-        if (exportEnabledRef.current && exportDirRef.current) {
-          const dir = exportDirRef.current
-          getIdea(id)
-            .then((idea) => {
-              if (idea) {
-                return exportIdea(idea, dir)
-              }
-            })
-            .catch(console.error)
-        }
+        exportIdea(id).catch(console.error)
       } catch (error) {
         console.error('Failed to update idea:', error)
         toast.error('Failed to update idea')
@@ -124,12 +215,58 @@ export function useIdeaActions() {
     [loadIdeas],
   )
 
+  const handleRestoreRevision = useCallback(
+    async (id: string, revision: number) => {
+      try {
+        const idea = await restoreRevision(id, revision)
+        try {
+          const vector = await embedForStorage(id, idea.text)
+          await storeEmbedding(id, 'multilingual-e5-small', vector)
+        } catch (error) {
+          console.error('Re-embedding failed during restore:', error)
+        }
+        await loadIdeas()
+        toast.success('Earlier version restored')
+        exportIdea(id).catch(console.error)
+      } catch (error) {
+        console.error('Failed to restore revision:', error)
+        toast.error('Failed to restore earlier version')
+      }
+    },
+    [loadIdeas],
+  )
+
+  const handleSetReminder = useCallback(
+    async (id: string, remindAt: number | null) => {
+      try {
+        await setReminder(id, remindAt)
+        await loadIdeas()
+        toast.success(
+          remindAt ? `Reminder set for ${new Date(remindAt).toLocaleString()}` : 'Reminder removed',
+        )
+      } catch (error) {
+        console.error('Failed to set reminder:', error)
+        toast.error('Failed to set reminder')
+      }
+    },
+    [loadIdeas],
+  )
+
   const handleDelete = useCallback(
     async (id: string) => {
       try {
         await deleteIdea(id)
         await loadIdeas()
-        toast.success('Idea deleted')
+        toast.success('Idea moved to Trash', {
+          action: {
+            label: 'Undo',
+            onClick: () => {
+              restoreIdea(id)
+                .then(() => loadIdeas())
+                .catch((error) => toast.error(`Could not restore idea: ${String(error)}`))
+            },
+          },
+        })
       } catch (error) {
         console.error('Failed to delete idea:', error)
         toast.error('Failed to delete idea')
@@ -144,6 +281,8 @@ export function useIdeaActions() {
         const idea = ideasRef.current.find((i) => i.id === id)
         if (idea) {
           await archiveIdea(id, !idea.archived)
+          // Keeps the file's `updated` current so vault edits don't conflict
+          exportIdea(id).catch(console.error)
           await loadIdeas()
           toast.success(idea.archived ? 'Idea restored to Ideas' : 'Idea moved to Archive')
         }
@@ -157,19 +296,54 @@ export function useIdeaActions() {
 
   const handleToggleArchive = useCallback(
     async (archived: boolean) => {
+      searchQueryRef.current = ''
       setShowArchive(archived)
       await loadIdeas(archived)
     },
     [loadIdeas],
   )
 
-  const handleAutoTitleEnabledChange = useCallback((enabled: boolean) => {
-    setAutoTitleEnabled(enabled)
-    localStorage.setItem(STORAGE_KEYS.AUTO_TITLE_ENABLED, String(enabled))
-    if (enabled) {
-      ensureTitleModel()
-    }
-  }, [])
+  const handleTagFilter = useCallback(
+    async (tag: string | null) => {
+      activeTagRef.current = tag
+      setActiveTag(tag)
+      await handleSearch(searchQueryRef.current)
+    },
+    [handleSearch],
+  )
+
+  const handleSourceAppFilter = useCallback(
+    async (sourceApp: string | null) => {
+      activeSourceAppRef.current = sourceApp
+      setActiveSourceApp(sourceApp)
+      await handleSearch(searchQueryRef.current)
+    },
+    [handleSearch],
+  )
+
+  const handleExportEnabledChange = useCallback(
+    (enabled: boolean) => {
+      updateSettings({ exportEnabled: enabled }).catch(console.error)
+    },
+    [updateSettings],
+  )
+
+  const handleExportDirChange = useCallback(
+    (dir: string) => {
+      updateSettings({ exportDir: dir }).catch(console.error)
+    },
+    [updateSettings],
+  )
+
+  const handleAutoTitleEnabledChange = useCallback(
+    (enabled: boolean) => {
+      updateSettings({ autoTitleEnabled: enabled }).catch(console.error)
+      if (enabled) {
+        ensureTitleModel()
+      }
+    },
+    [updateSettings],
+  )
 
   const handleRegenerateTitle = useCallback(
     async (id: string) => {
@@ -178,6 +352,7 @@ export function useIdeaActions() {
       try {
         const title = await generateTitle(idea.text)
         await updateIdea(id, { title })
+        exportIdea(id).catch(console.error)
         await loadIdeas()
         toast.success('Title regenerated')
       } catch (error) {
@@ -205,19 +380,31 @@ export function useIdeaActions() {
     ideas,
     showArchive,
     archiveCount,
+    trashCount,
     exportEnabled,
     exportDir,
     autoTitleEnabled,
+    searchTotal,
+    searchSnippets,
+    tags,
+    activeTag,
+    sourceApps,
+    activeSourceApp,
     loadIdeas,
     onSearch: handleSearch,
+    onLoadMoreResults: handleLoadMoreResults,
     onUpdate: handleUpdate,
     onDelete: handleDelete,
     onArchive: handleArchive,
     onToggleArchive: handleToggleArchive,
+    onTagFilter: handleTagFilter,
+    onSourceAppFilter: handleSourceAppFilter,
     onCapture: handleCapture,
     onRegenerateTitle: handleRegenerateTitle,
-    onExportEnabledChange: setExportEnabled,
-    onExportDirChange: setExportDir,
+    onRestoreRevision: handleRestoreRevision,
+    onSetReminder: handleSetReminder,
+    onExportEnabledChange: handleExportEnabledChange,
+    onExportDirChange: handleExportDirChange,
     onAutoTitleEnabledChange: handleAutoTitleEnabledChange,
   }
 }
@@ -3,7 +3,7 @@ import { toast } from 'sonner'
 import { embeddingLifecycle } from '@/lib/ai/embeddings'
 import { titleLifecycle } from '@/lib/ai/title-generation'
 import { whisperLifecycle } from '@/lib/ai/whisper'
-import { STORAGE_KEYS } from '@/lib/storage-keys'
+import { updateSettings } from '@/lib/settings'
 import { useModelLifecycle } from './use-model-lifecycle'
 import type { ModelState } from '@/lib/ai/model-lifecycle'
 
@@ -20,7 +20,7 @@ export function useModelNotifications(): void {
     if (prevStt.current !== 'ready' && stt.state === 'ready') {
       const name = stt.modelId.split('/').pop() ?? stt.modelId
       toast.success(`${name} model ready`)
-      localStorage.setItem(STORAGE_KEYS.STT_MODEL, stt.modelId)
+      updateSettings({ sttModel: stt.modelId }).catch(console.error)
     }
     prevStt.current = stt.state
   }, [stt.state, stt.modelId])
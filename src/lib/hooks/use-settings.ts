@@ -0,0 +1,40 @@
+import { getSettings, onSettingsChanged, updateSettings } from '@/lib/settings'
+import type { Settings, SettingsPatch } from '@/lib/types'
+import { useCallback, useEffect, useState } from 'react'
+
+/** Current settings, kept in sync across windows. `null` until loaded. */
+export function useSettings() {
+  const [settings, setSettings] = useState<Settings | null>(null)
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined
+    let cancelled = false
+
+    getSettings()
+      .then((loaded) => {
+        if (!cancelled) setSettings(loaded)
+      })
+      .catch(() => {
+        // Expected when not running in Tauri context (e.g. Vite dev server)
+      })
+    onSettingsChanged(setSettings)
+      .then((fn) => {
+        if (cancelled) fn()
+        else unlisten = fn
+      })
+      .catch(() => {
+        // Expected when not running in Tauri context (e.g. Vite dev server)
+      })
+
+    return () => {
+      cancelled = true
+      unlisten?.()
+    }
+  }, [])
+
+  const update = useCallback(async (patch: SettingsPatch) => {
+    setSettings(await updateSettings(patch))
+  }, [])
+
+  return { settings, updateSettings: update }
+}
@@ -1,9 +1,10 @@
-import { STORAGE_KEYS } from '@/lib/storage-keys'
-import { useCallback, useEffect, useState } from 'react'
+import { useSettings } from '@/lib/hooks/use-settings'
+import type { Theme } from '@/lib/types'
+import { useCallback, useEffect } from 'react'
 
-export type Theme = 'light' | 'dark' | 'system'
+export type { Theme }
 
-function applyThemeClass(theme: Theme) {
+export function applyThemeClass(theme: Theme) {
   const root = document.documentElement
   if (
     theme === 'dark' ||
@@ -15,14 +16,13 @@ function applyThemeClass(theme: Theme) {
   }
 }
 
+/** Applies the saved theme to this window and follows changes from any window. */
 export function useTheme() {
-  const [theme, setTheme] = useState<Theme>(
-    () => (localStorage.getItem(STORAGE_KEYS.THEME) as Theme) ?? 'system',
-  )
+  const { settings, updateSettings } = useSettings()
+  const theme = settings?.theme ?? 'system'
 
   useEffect(() => {
     applyThemeClass(theme)
-    localStorage.setItem(STORAGE_KEYS.THEME, theme)
 
     if (theme === 'system') {
       const mq = matchMedia('(prefers-color-scheme:dark)')
@@ -32,9 +32,12 @@ export function useTheme() {
     }
   }, [theme])
 
-  const handleThemeChange = useCallback((newTheme: Theme) => {
-    setTheme(newTheme)
-  }, [])
+  const handleThemeChange = useCallback(
+    (newTheme: Theme) => {
+      updateSettings({ theme: newTheme }).catch(console.error)
+    },
+    [updateSettings],
+  )
 
   return { theme, onThemeChange: handleThemeChange }
 }
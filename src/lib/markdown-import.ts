@@ -0,0 +1,12 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { ImportReport } from './types'
+
+/**
+ * Import every `.md`, `.markdown` and `.txt` note below `dir`. Notes with a
+ * Glimt `id` in their frontmatter that already exist are skipped. New ideas
+ * arrive through the `ideas-changed` event, which reloads lists and
+ * backfills embeddings.
+ */
+export async function importMarkdown(dir: string): Promise<ImportReport> {
+  return invoke<ImportReport>('import_markdown', { dir })
+}
@@ -0,0 +1,42 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { NativeSttStatus, Transcript } from './types'
+
+let statusPromise: Promise<NativeSttStatus | null> | null = null
+
+/** Resolves to null outside Tauri or when the Rust side cannot be reached. */
+export function getNativeSttStatus(): Promise<NativeSttStatus | null> {
+  if (!statusPromise) {
+    statusPromise = invoke<NativeSttStatus>('native_transcription_status').catch(() => null)
+  }
+  return statusPromise
+}
+
+export async function downloadWhisperModel(
+  model: string,
+  onProgress?: (percent: number) => void,
+): Promise<void> {
+  const { listen } = await import('@tauri-apps/api/event')
+  const unlisten = await listen<{ model: string; percent: number }>(
+    'whisper-model-progress',
+    (event) => {
+      if (event.payload.model === model) onProgress?.(event.payload.percent)
+    },
+  )
+  try {
+    await invoke<void>('download_whisper_model', { model })
+  } finally {
+    unlisten()
+  }
+}
+
+/** Sends the recording as the raw request body; Rust decodes WebM/Opus/WAV. */
+export async function transcribeNative(
+  audio: Blob,
+  model: string,
+  language?: string,
+): Promise<Transcript> {
+  const bytes = new Uint8Array(await audio.arrayBuffer())
+  const headers: Record<string, string> = { 'x-model': model }
+  if (language) headers['x-language'] = language
+  return invoke<Transcript>('transcribe_audio', bytes, { headers })
+}
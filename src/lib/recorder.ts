@@ -0,0 +1,17 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { InputDevice } from './types'
+
+export async function listInputDevices(): Promise<InputDevice[]> {
+  return invoke<InputDevice[]>('list_input_devices')
+}
+
+/** Start a native recording, or stop the current one. Same as the record shortcut. */
+export async function toggleRecording(): Promise<void> {
+  return invoke<void>('toggle_recording')
+}
+
+/** The last finished recording as a 16 kHz mono WAV. Can only be taken once. */
+export async function takeRecording(): Promise<Blob> {
+  const bytes = await invoke<ArrayBuffer>('take_recording')
+  return new Blob([bytes], { type: 'audio/wav' })
+}
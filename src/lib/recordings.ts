@@ -0,0 +1,33 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { Recording, Transcript } from './types'
+
+/**
+ * Keep the last Quick Record recording with the idea saved from it. Resolves
+ * to null when `keepRecordings` was off; lists reload through the
+ * `ideas-changed` event.
+ */
+export async function keepRecording(ideaId: string): Promise<Recording | null> {
+  return invoke<Recording | null>('keep_recording', { id: ideaId })
+}
+
+/** The idea's recording as FLAC, ready for an `<audio>` element. */
+export async function readRecording(ideaId: string): Promise<Blob> {
+  const bytes = await invoke<ArrayBuffer>('read_recording', { id: ideaId })
+  return new Blob([bytes], { type: 'audio/flac' })
+}
+
+export async function deleteRecording(ideaId: string): Promise<void> {
+  return invoke<void>('delete_recording', { id: ideaId })
+}
+
+/**
+ * Transcribe the recording again with another Whisper model. Needs native
+ * transcription; the idea itself is left unchanged.
+ */
+export async function retranscribeRecording(
+  ideaId: string,
+  model: string,
+  language?: string,
+): Promise<Transcript> {
+  return invoke<Transcript>('retranscribe_recording', { id: ideaId, model, language })
+}
@@ -0,0 +1,6 @@
+import { invoke } from '@tauri-apps/api/core'
+
+/** Remind about an idea at `remindAt` (ms); `null` removes the reminder. */
+export async function setReminder(id: string, remindAt: number | null): Promise<void> {
+  await invoke('set_reminder', { id, remindAt })
+}
@@ -0,0 +1,21 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { DiffPart, Idea, Revision } from './types'
+
+/** Earlier versions of an idea, newest first. Every edit adds one. */
+export async function listRevisions(ideaId: string): Promise<Revision[]> {
+  return invoke<Revision[]>('list_revisions', { id: ideaId })
+}
+
+/** Word diff from revision `from` to revision `to`, or to the current text. */
+export async function diffRevisions(
+  ideaId: string,
+  from: number,
+  to?: number,
+): Promise<DiffPart[]> {
+  return invoke<DiffPart[]>('diff_revisions', { id: ideaId, from, to })
+}
+
+/** Put an earlier version back; the text it replaces becomes a revision too. */
+export async function restoreRevision(ideaId: string, revision: number): Promise<Idea> {
+  return invoke<Idea>('restore_revision', { id: ideaId, revision })
+}
@@ -1,6 +1,7 @@
+import { invoke } from '@tauri-apps/api/core'
 import { EMBEDDING_MODEL, embedForQuery } from '@/lib/ai/embeddings'
-import { getAllEmbeddings, getIdeasByIds } from '@/lib/db'
-import type { SearchResult } from '@/lib/types'
+import { getIdeasByIds, searchSimilar } from '@/lib/db'
+import type { SearchFilters, SearchPage, SearchResult } from '@/lib/types'
 
 export function cosineSimilarity(a: number[] | Float32Array, b: number[] | Float32Array): number {
   let dot = 0
@@ -18,25 +19,16 @@ export function cosineSimilarity(a: number[] | Float32Array, b: number[] | Float
   return dot / denominator
 }
 
-// Loads all embeddings into memory for cosine similarity. Acceptable for <10k ideas
-// (~15MB at 384 dims). For larger datasets, consider chunked loading or moving
-// cosine similarity computation into a SQLite extension.
+// Nearest-neighbour lookup runs in Rust against an HNSW index that is kept in
+// sync with the embeddings table, so this stays fast for large databases.
 export async function searchIdeas(query: string, topK = 20): Promise<SearchResult[]> {
   if (!query.trim()) return []
 
   const queryVector = await embedForQuery(query)
-  const allEmbeddings = await getAllEmbeddings(EMBEDDING_MODEL)
-
-  const scored = allEmbeddings.map(({ ideaId, vector }) => ({
-    ideaId,
-    score: cosineSimilarity(queryVector, vector),
-  }))
+  const neighbours = await searchSimilar(EMBEDDING_MODEL, queryVector, topK)
 
   const MIN_SIMILARITY = 0.3
-  const topResults = scored
-    .filter((r) => r.score >= MIN_SIMILARITY)
-    .sort((a, b) => b.score - a.score)
-    .slice(0, topK)
+  const topResults = neighbours.filter((r) => r.score >= MIN_SIMILARITY)
 
   const topIds = topResults.map((r) => r.ideaId)
   const ideas = await getIdeasByIds(topIds)
@@ -51,3 +43,38 @@ export async function searchIdeas(query: string, topK = 20): Promise<SearchResul
   }
   return results
 }
+
+export interface HybridSearchOptions {
+  filters?: SearchFilters
+  offset?: number
+  limit?: number
+}
+
+/**
+ * Keyword and semantic search fused in one backend query. Falls back to
+ * keyword-only results when the embedding model cannot embed the query.
+ */
+export async function hybridSearch(
+  query: string,
+  { filters = {}, offset = 0, limit = 50 }: HybridSearchOptions = {},
+): Promise<SearchPage> {
+  if (!query.trim()) return { results: [], total: 0 }
+
+  let vector: number[] | null = null
+  try {
+    vector = await embedForQuery(query)
+  } catch (error) {
+    console.warn('Query embedding failed, searching keywords only:', error)
+  }
+
+  return invoke<SearchPage>('hybrid_search', {
+    request: {
+      query,
+      vector,
+      model: vector ? EMBEDDING_MODEL : null,
+      filters,
+      offset,
+      limit,
+    },
+  })
+}
@@ -0,0 +1,72 @@
+import { invoke } from '@tauri-apps/api/core'
+
+import { setShortcut } from '@/lib/shortcut'
+import { STORAGE_KEYS } from '@/lib/storage-keys'
+import type { Settings, SettingsPatch, ShortcutAction } from '@/lib/types'
+
+// Preferences are kept by Rust (`settings.rs`) so every window, and Rust
+// itself, sees the same values. Every change is broadcast as
+// `settings-changed` with the full settings.
+export const SETTINGS_CHANGED_EVENT = 'settings-changed'
+
+export async function getSettings(): Promise<Settings> {
+  return invoke<Settings>('get_settings')
+}
+
+/** Rejects on unknown keys or invalid values; nothing is saved then. */
+export async function updateSettings(patch: SettingsPatch): Promise<Settings> {
+  return invoke<Settings>('set_settings', { patch })
+}
+
+export async function onSettingsChanged(handler: (settings: Settings) => void): Promise<() => void> {
+  const { listen } = await import('@tauri-apps/api/event')
+  return listen<Settings>(SETTINGS_CHANGED_EVENT, (event) => handler(event.payload))
+}
+
+const LEGACY_KEYS = [
+  STORAGE_KEYS.THEME,
+  STORAGE_KEYS.EXPORT_ENABLED,
+  STORAGE_KEYS.EXPORT_DIR,
+  STORAGE_KEYS.STT_MODEL,
+  STORAGE_KEYS.AUTO_TITLE_ENABLED,
+  STORAGE_KEYS.CAPTURE_SHORTCUT,
+  STORAGE_KEYS.RECORD_SHORTCUT,
+]
+
+// Older versions kept preferences in localStorage. Hand them to Rust once
+// and drop them. Shortcuts go through `setShortcut` so they are registered.
+export async function migrateLegacySettings(): Promise<void> {
+  const read = (key: string) => localStorage.getItem(key)
+  const patch: SettingsPatch = {}
+
+  const theme = read(STORAGE_KEYS.THEME)
+  if (theme === 'light' || theme === 'dark' || theme === 'system') patch.theme = theme
+  const exportEnabled = read(STORAGE_KEYS.EXPORT_ENABLED)
+  if (exportEnabled !== null) patch.exportEnabled = exportEnabled === 'true'
+  const exportDir = read(STORAGE_KEYS.EXPORT_DIR)
+  if (exportDir) patch.exportDir = exportDir
+  const sttModel = read(STORAGE_KEYS.STT_MODEL)
+  if (sttModel) patch.sttModel = sttModel
+  const autoTitle = read(STORAGE_KEYS.AUTO_TITLE_ENABLED)
+  if (autoTitle !== null) patch.autoTitleEnabled = autoTitle === 'true'
+
+  if (Object.keys(patch).length > 0) {
+    await updateSettings(patch)
+  }
+
+  const shortcuts: [ShortcutAction, string][] = [
+    ['capture', STORAGE_KEYS.CAPTURE_SHORTCUT],
+    ['record', STORAGE_KEYS.RECORD_SHORTCUT],
+  ]
+  for (const [action, key] of shortcuts) {
+    const saved = read(key)
+    if (saved === null) continue
+    try {
+      await setShortcut(action, saved)
+    } catch (error) {
+      console.error(`Could not migrate ${action} shortcut:`, error)
+    }
+  }
+
+  for (const key of LEGACY_KEYS) localStorage.removeItem(key)
+}
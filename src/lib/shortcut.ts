@@ -1,146 +1,24 @@
-import { isRegistered, register, unregister } from '@tauri-apps/plugin-global-shortcut'
-import { emitTo } from '@tauri-apps/api/event'
-import { WebviewWindow } from '@tauri-apps/api/webviewWindow'
+import { invoke } from '@tauri-apps/api/core'
 
-import { STORAGE_KEYS } from '@/lib/storage-keys'
-
-// ── Defaults & storage keys ───────────────────────────
+import type { ShortcutAction, ShortcutsStatus } from '@/lib/types'
 
+// Shortcuts are registered by the Rust side (`shortcuts.rs`) and saved in
+// settings, so they work before this webview loads. These defaults mirror
+// its own and only fill the UI until `getShortcuts` answers.
 export const DEFAULT_CAPTURE_SHORTCUT = 'Alt+I'
 export const DEFAULT_RECORD_SHORTCUT = 'Alt+R'
+export const DEFAULT_CLIP_SHORTCUT = 'Alt+C'
 
-const CAPTURE_STORAGE_KEY = STORAGE_KEYS.CAPTURE_SHORTCUT
-const RECORD_STORAGE_KEY = STORAGE_KEYS.RECORD_SHORTCUT
-
-// ── Module state ──────────────────────────────────────
-
-let currentCaptureRegistered: string | null = null
-let currentRecordRegistered: string | null = null
-
-// ── Getters ───────────────────────────────────────────
-
-export function getSavedCaptureShortcut(): string {
-  return localStorage.getItem(CAPTURE_STORAGE_KEY) ?? DEFAULT_CAPTURE_SHORTCUT
-}
-
-export function getSavedRecordShortcut(): string {
-  return localStorage.getItem(RECORD_STORAGE_KEY) ?? DEFAULT_RECORD_SHORTCUT
-}
-
-// ── Shortcut actions ──────────────────────────────────
-
-async function toggleCaptureWindow(): Promise<void> {
-  const captureWindow = await WebviewWindow.getByLabel('capture')
-  if (!captureWindow) return
-
-  const visible = await captureWindow.isVisible()
-  if (visible) {
-    await captureWindow.hide()
-  } else {
-    await captureWindow.show()
-    await captureWindow.setFocus()
-  }
-}
-
-let lastRecordToggle = 0
-
-async function toggleIndicatorRecording(): Promise<void> {
-  const now = Date.now()
-  if (now - lastRecordToggle < 300) return
-  lastRecordToggle = now
-
-  const indicatorWindow = await WebviewWindow.getByLabel('indicator')
-  if (!indicatorWindow) return
-
-  const visible = await indicatorWindow.isVisible()
-  if (visible) {
-    await emitTo('indicator', 'stop-recording')
-  } else {
-    await indicatorWindow.show()
-    await emitTo('indicator', 'start-recording')
-  }
-}
-
-// ── Generic register/unregister ───────────────────────
-
-async function safeUnregister(shortcut: string): Promise<void> {
-  try {
-    if (await isRegistered(shortcut)) {
-      await unregister(shortcut)
-    }
-  } catch {
-    // Best effort
-  }
+export async function getShortcuts(): Promise<ShortcutsStatus> {
+  return invoke<ShortcutsStatus>('get_shortcuts')
 }
 
-// ── Capture shortcut ──────────────────────────────────
-
-export async function registerCaptureShortcut(shortcut?: string): Promise<void> {
-  const target = shortcut ?? getSavedCaptureShortcut()
-
-  if (currentCaptureRegistered && currentCaptureRegistered !== target) {
-    await safeUnregister(currentCaptureRegistered)
-    currentCaptureRegistered = null
-  }
-
-  await safeUnregister(target)
-  await register(target, (event) => {
-    if (event.state === 'Pressed') {
-      toggleCaptureWindow().catch(console.error)
-    }
-  })
-  currentCaptureRegistered = target
-}
-
-export async function changeCaptureShortcut(newShortcut: string): Promise<void> {
-  const previous = currentCaptureRegistered ?? getSavedCaptureShortcut()
-
-  try {
-    await registerCaptureShortcut(newShortcut)
-    localStorage.setItem(CAPTURE_STORAGE_KEY, newShortcut)
-  } catch (error) {
-    try {
-      await registerCaptureShortcut(previous)
-    } catch {
-      // Failed to restore
-    }
-    throw error
-  }
-}
-
-// ── Record shortcut ───────────────────────────────────
-
-export async function registerRecordShortcut(shortcut?: string): Promise<void> {
-  const target = shortcut ?? getSavedRecordShortcut()
-
-  if (currentRecordRegistered && currentRecordRegistered !== target) {
-    await safeUnregister(currentRecordRegistered)
-    currentRecordRegistered = null
-  }
-
-  await safeUnregister(target)
-  await register(target, (event) => {
-    if (event.state === 'Pressed') {
-      toggleIndicatorRecording().catch(console.error)
-    }
-  })
-  currentRecordRegistered = target
-}
-
-export async function changeRecordShortcut(newShortcut: string): Promise<void> {
-  const previous = currentRecordRegistered ?? getSavedRecordShortcut()
-
-  try {
-    await registerRecordShortcut(newShortcut)
-    localStorage.setItem(RECORD_STORAGE_KEY, newShortcut)
-  } catch (error) {
-    try {
-      await registerRecordShortcut(previous)
-    } catch {
-      // Failed to restore
-    }
-    throw error
-  }
+/** Rejects, keeping the previous keys, on a conflict or registration failure. */
+export async function setShortcut(
+  action: ShortcutAction,
+  shortcut: string,
+): Promise<ShortcutsStatus> {
+  return invoke<ShortcutsStatus>('set_shortcut', { action, shortcut })
 }
 
 // ── Parsing utilities ─────────────────────────────────
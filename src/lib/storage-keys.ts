@@ -3,6 +3,9 @@ export const STORAGE_KEYS = {
   LAST_IDEA_ID: 'glimt:last-idea-id',
   LAST_IDEA_PREVIEW: 'glimt:last-idea-preview',
   MD_HINT_SHOWN: 'glimt:md-hint-shown',
+  LAST_UPDATE_CHECK: 'glimt:last-update-check',
+  ADVANCED_OPEN: 'glimt:settings-advanced-open',
+  // Preferences now kept by the Rust settings store; only read to migrate them
   EXPORT_ENABLED: 'glimt:export-enabled',
   EXPORT_DIR: 'glimt:export-dir',
   THEME: 'glimt:theme',
@@ -10,6 +13,4 @@ export const STORAGE_KEYS = {
   AUTO_TITLE_ENABLED: 'glimt:auto-title-enabled',
   CAPTURE_SHORTCUT: 'glimt:capture-shortcut',
   RECORD_SHORTCUT: 'glimt:record-shortcut',
-  LAST_UPDATE_CHECK: 'glimt:last-update-check',
-  ADVANCED_OPEN: 'glimt:settings-advanced-open',
 } as const
@@ -0,0 +1,47 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { Idea, TagCount } from './types'
+
+/**
+ * Tags come from `#hashtags` in an idea's text, which Rust extracts on every
+ * save, or are added by hand. Renaming or deleting a tag rewrites the
+ * hashtags in place; lists reload through the `ideas-changed` event.
+ */
+
+/** Every tag, counting only the ideas in the given list. */
+export async function listTags(options?: { archived?: boolean }): Promise<TagCount[]> {
+  return invoke<TagCount[]>('list_tags', { archived: options?.archived ?? null })
+}
+
+export async function getIdeasByTag(
+  tag: string,
+  options?: { archived?: boolean; sourceApp?: string | null },
+): Promise<Idea[]> {
+  return invoke<Idea[]>('get_ideas_by_tag', {
+    tag,
+    archived: options?.archived ?? false,
+    sourceApp: options?.sourceApp ?? null,
+  })
+}
+
+/** Returns the tag's stored name: lowercase, without the `#`. */
+export async function createTag(name: string): Promise<string> {
+  return invoke<string>('create_tag', { name })
+}
+
+/** Merges into `newName` if that tag exists. Returns the stored name. */
+export async function renameTag(name: string, newName: string): Promise<string> {
+  return invoke<string>('rename_tag', { name, newName })
+}
+
+export async function deleteTag(name: string): Promise<void> {
+  await invoke('delete_tag', { name })
+}
+
+export async function addIdeaTag(id: string, tag: string): Promise<void> {
+  await invoke('add_idea_tag', { id, tag })
+}
+
+/** Fails for a tag that is a hashtag in the idea's text. */
+export async function removeIdeaTag(id: string, tag: string): Promise<void> {
+  await invoke('remove_idea_tag', { id, tag })
+}
@@ -0,0 +1,22 @@
+import { invoke } from '@tauri-apps/api/core'
+import type { Idea } from './types'
+
+/** Ideas in the trash, most recently deleted first. */
+export async function listTrash(): Promise<Idea[]> {
+  return invoke<Idea[]>('list_trash')
+}
+
+/** Take an idea out of the trash, back into Ideas or Archive. */
+export async function restoreIdea(id: string): Promise<void> {
+  await invoke('restore_idea', { id })
+}
+
+/** Delete an idea in the trash for good, with its embeddings and files. */
+export async function purgeIdea(id: string): Promise<void> {
+  await invoke('purge_idea', { id })
+}
+
+/** Delete every idea in the trash for good. Resolves to how many went. */
+export async function emptyTrash(): Promise<number> {
+  return invoke<number>('empty_trash')
+}
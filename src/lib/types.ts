@@ -7,6 +7,51 @@ export interface Idea {
   archived: boolean
   sourceApp: string | null
   markdownPath: string | null
+  /** Page a clipboard capture was copied from, when known. */
+  sourceUrl: string | null
+  /** When the idea was moved to the trash; `null` outside it. */
+  deletedAt: number | null
+  /** When a reminder notification is due; cleared once it fires. */
+  remindAt: number | null
+  /** Sorted, lowercase, without the `#`. */
+  tags: string[]
+  /** Oldest first. */
+  attachments: Attachment[]
+  /** Kept audio of a voice idea, when `keepRecordings` was on. */
+  recording: Recording | null
+}
+
+export interface Recording {
+  /** Bytes on disk. */
+  size: number
+  durationMs: number
+  createdAt: number
+}
+
+/** A file attached to an idea; its contents live in the app's blob store. */
+export interface Attachment {
+  id: string
+  name: string
+  mime: string
+  size: number
+  /** Whether a PNG preview exists (images only). */
+  thumbnail: boolean
+  createdAt: number
+}
+
+/** An earlier version of an idea, kept when it was edited. */
+export interface Revision {
+  id: number
+  text: string
+  title: string | null
+  /** When this version was saved. */
+  createdAt: number
+}
+
+/** A run of words in a diff, kept, added or removed. */
+export interface DiffPart {
+  text: string
+  change: 'equal' | 'insert' | 'delete'
 }
 
 export interface IdeaUpdate {
@@ -14,8 +59,224 @@ export interface IdeaUpdate {
   title?: string | null
 }
 
+/** A run of snippet text; `highlight` marks the words that matched. */
+export interface SnippetPart {
+  text: string
+  highlight: boolean
+}
+
 export interface SearchResult {
   idea: Idea
   score: number
   source: 'fts' | 'semantic' | 'both'
+  /** FTS excerpt around the match; absent for semantic-only hits. */
+  snippet?: SnippetPart[] | null
+}
+
+export interface SearchFilters {
+  archived?: boolean
+  /** Inclusive, ms since epoch. */
+  createdAfter?: number
+  /** Exclusive, ms since epoch. */
+  createdBefore?: number
+  /** Only ideas with this tag. */
+  tag?: string
+  /** Only ideas captured from this app. */
+  sourceApp?: string
+}
+
+export interface SearchPage {
+  results: SearchResult[]
+  total: number
+}
+
+/** Structured migration failure reported by the Rust `db_status` command. */
+export interface DbStartupError {
+  version: number
+  description: string
+  message: string
+  backupPath: string | null
+}
+
+/** Reported by `db_status` while an encrypted database waits for its passphrase. */
+export interface DbLockedError {
+  locked: true
+}
+
+/** From `encryption_status`. */
+export interface EncryptionStatus {
+  /** Whether this build can encrypt at all. */
+  available: boolean
+  encrypted: boolean
+  unlocked: boolean
+  /** The passphrase is kept in the OS keyring. */
+  remembered: boolean
+}
+
+/** Loopback capture API settings, persisted by the Rust side in `api.json`. */
+export interface ApiConfig {
+  enabled: boolean
+  port: number
+  token: string
+}
+
+export interface TranscriptSegment {
+  startMs: number
+  endMs: number
+  text: string
+}
+
+export interface Transcript {
+  text: string
+  segments: TranscriptSegment[]
+}
+
+export interface NativeSttStatus {
+  available: boolean
+  modelsDir: string
+  installed: string[]
+}
+
+export interface TranscriptionProgress {
+  stage: 'decoding' | 'loading' | 'transcribing' | 'done'
+  percent: number
+}
+
+export interface InputDevice {
+  name: string
+  isDefault: boolean
+}
+
+export type Theme = 'light' | 'dark' | 'system'
+
+/** Mirrors `settings::Settings`, persisted by Rust in `settings.json`. */
+export type BackupSchedule = 'off' | 'hourly' | 'daily'
+
+/** What the clip shortcut does: open capture with the text, or save it. */
+export type ClipMode = 'prefill' | 'save'
+
+export interface Settings {
+  version: number
+  theme: Theme
+  exportEnabled: boolean
+  exportDir: string | null
+  sttModel: string | null
+  autoTitleEnabled: boolean
+  shortcuts: { capture: string; record: string; clip: string }
+  /** `null` follows the system default input. */
+  inputDevice: string | null
+  backupSchedule: BackupSchedule
+  /** How many scheduled backups to keep; at least one. */
+  backupKeep: number
+  /** Lock the app after this many idle minutes; 0 never does. */
+  autoLockMinutes: number
+  clipMode: ClipMode
+  /** Keep the audio of Quick Record recordings with their ideas. */
+  keepRecordings: boolean
+  /** Space kept recordings may take, in MB; 0 sets no limit. */
+  recordingQuotaMb: number
+  /** Days ideas stay in the trash before they are purged; 0 keeps them. */
+  trashDays: number
+  /** Now and then, show a notification with an old idea. */
+  resurfaceEnabled: boolean
+  /** Hours between resurfaced ideas; at least one. */
+  resurfaceEveryHours: number
+  /** Only ideas at least this many days old are resurfaced. */
+  resurfaceAfterDays: number
+}
+
+/** Shortcuts change through `setShortcut`, which registers them first. */
+export type SettingsPatch = Partial<Omit<Settings, 'version' | 'shortcuts'>>
+
+export type ShortcutAction = 'capture' | 'record' | 'clip'
+
+export interface ShortcutBinding {
+  shortcut: string
+  registered: boolean
+  error: string | null
+}
+
+export interface ShortcutsStatus {
+  capture: ShortcutBinding
+  record: ShortcutBinding
+  clip: ShortcutBinding
+}
+
+/** Sent when a vault edit lost to a newer change in Glimt. */
+export interface VaultConflict {
+  id: string
+  /** The conflict copy holding the edit that was not applied. */
+  path: string
+}
+
+export type ImportFileStatus = 'imported' | 'duplicate' | 'empty' | 'failed'
+
+export interface ImportFileResult {
+  path: string
+  status: ImportFileStatus
+  id: string | null
+  error: string | null
+}
+
+/** Returned by `import_markdown`, one entry per file found. */
+export interface ImportReport {
+  imported: number
+  duplicates: number
+  empty: number
+  failed: number
+  files: ImportFileResult[]
+}
+
+/** Written to `manifest.json` and returned by `export_archive`. */
+export interface ArchiveManifest {
+  format: string
+  formatVersion: number
+  schemaVersion: number
+  appVersion: string
+  createdAt: number
+  ideas: number
+  embeddings: number
+}
+
+export type ArchiveImportMode = 'merge' | 'replace'
+
+/** Returned by `import_archive`. */
+export interface ArchiveImportSummary {
+  added: number
+  updated: number
+  /** Ideas already here in the same or a newer version. */
+  skipped: number
+  embeddings: number
+}
+
+/** A database snapshot in the backups folder, from `list_backups`. */
+export interface BackupInfo {
+  /** File name; what `restore_backup` takes. */
+  name: string
+  createdAt: number
+  size: number
+}
+
+/** From `app_lock_status`. */
+export interface AppLockStatus {
+  locked: boolean
+  pinSet: boolean
+}
+
+/** From `list_tags`: a tag and how many ideas in the listed view carry it. */
+export interface TagCount {
+  name: string
+  count: number
+}
+
+/** From `list_source_apps`: an app ideas were captured from, and how many. */
+export interface SourceAppCount {
+  sourceApp: string
+  count: number
+}
+
+/** Copied text waiting for the capture window, from `take_clip`. */
+export interface Clip {
+  text: string
+  sourceUrl: string | null
 }
@@ -0,0 +1,88 @@
+import { transcribeAudio } from '@/lib/ai/whisper'
+import { takeRecording } from '@/lib/recorder'
+import { useEffect, useRef, useState } from 'react'
+
+type RecordingState = 'idle' | 'recording' | 'transcribing'
+
+interface UseNativeRecordingOptions {
+  onStart?: () => void
+  onTranscriptionComplete: (text: string) => void
+  onError?: (error: string) => void
+}
+
+interface UseNativeRecordingReturn {
+  state: RecordingState
+  /** Input peak level in 0..1, updated while recording. */
+  level: number
+}
+
+/**
+ * Follows a recording captured by the Rust recorder: the record shortcut
+ * starts and stops it, this hook shows levels and transcribes the result.
+ */
+export function useNativeRecording(options: UseNativeRecordingOptions): UseNativeRecordingReturn {
+  const [state, setState] = useState<RecordingState>('idle')
+  const [level, setLevel] = useState(0)
+
+  const optionsRef = useRef(options)
+  useEffect(() => {
+    optionsRef.current = options
+  })
+
+  useEffect(() => {
+    const unlisteners: (() => void)[] = []
+    let disposed = false
+
+    async function handleStop(recorded: boolean): Promise<void> {
+      setLevel(0)
+      if (!recorded) {
+        setState('idle')
+        optionsRef.current.onTranscriptionComplete('')
+        return
+      }
+      setState('transcribing')
+      try {
+        const text = await transcribeAudio(await takeRecording())
+        setState('idle')
+        optionsRef.current.onTranscriptionComplete(text.trim())
+      } catch (error) {
+        setState('idle')
+        optionsRef.current.onError?.(error instanceof Error ? error.message : 'Transcription failed')
+      }
+    }
+
+    import('@tauri-apps/api/event')
+      .then(({ listen }) =>
+        Promise.all([
+          listen('start-recording', () => {
+            setLevel(0)
+            setState('recording')
+            optionsRef.current.onStart?.()
+          }),
+          listen<number>('audio-level', (event) => setLevel(event.payload)),
+          listen<boolean>('stop-recording', (event) => {
+            handleStop(event.payload).catch(console.error)
+          }),
+          listen<string>('recording-error', (event) => {
+            setLevel(0)
+            setState('idle')
+            optionsRef.current.onError?.(event.payload)
+          }),
+        ]),
+      )
+      .then((fns) => {
+        if (disposed) fns.forEach((fn) => fn())
+        else unlisteners.push(...fns)
+      })
+      .catch(() => {
+        // Expected when not running in Tauri context (e.g. Vite dev server)
+      })
+
+    return () => {
+      disposed = true
+      unlisteners.forEach((fn) => fn())
+    }
+  }, [])
+
+  return { state, level }
+}